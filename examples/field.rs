@@ -1,7 +1,8 @@
 use crossterm::event::{poll, read, Event, KeyCode, KeyEvent, KeyModifiers};
 use crossterm::style::{Color, ContentStyle};
-use idiom_tui::backend::{Backend, CrossTerm, StyleExt};
+use idiom_tui::backend::{prelude::*, CrossTerm};
 use idiom_tui::text_field::{Status, TextField};
+use idiom_tui::utils::PasteOptions;
 use std::time::Duration;
 
 fn main() -> std::io::Result<()> {
@@ -16,11 +17,21 @@ fn main() -> std::io::Result<()> {
     let mut screen = CrossTerm::screen()?;
     screen.width = 50;
     let mut text_field = TextField::default();
+    // run with --real-cursor to place the terminal cursor instead of painting it
+    let real_cursor = std::env::args().any(|arg| arg == "--real-cursor");
+    text_field.set_real_cursor(real_cursor);
 
     let line = screen.get_line(1).unwrap();
     text_field.widget(line, cursor_style, select_style, &mut backend);
 
     loop {
+        if real_cursor {
+            let line = screen.get_line(1).unwrap();
+            match text_field.cursor_screen_position(&line) {
+                Some(position) => backend.render_cursor_at(position.row, position.col),
+                None => backend.hide_cursor(),
+            }
+        }
         backend.flush_buf();
         if poll(Duration::from_millis(100))? {
             match read()? {
@@ -95,7 +106,10 @@ fn main() -> std::io::Result<()> {
                     }
                 }
                 Event::Paste(clip) => {
-                    if text_field.paste_passthrough(clip).is_updated() {
+                    if text_field
+                        .paste_sanitized(clip, PasteOptions::default())
+                        .is_updated()
+                    {
                         let line = screen.get_line(1).unwrap();
                         text_field.widget(line, cursor_style, select_style, &mut backend);
                         let line = screen.get_line(2).unwrap();
@@ -0,0 +1,49 @@
+use crossterm::event::{poll, read, Event, KeyCode};
+use idiom_tui::backend::{prelude::*, CrossTerm};
+use idiom_tui::widgets::{Prompt, PromptEvent};
+use std::time::Duration;
+
+fn main() -> std::io::Result<()> {
+    let mut backend = CrossTerm::init();
+    let mut screen = CrossTerm::screen()?;
+    let mut prompt = Prompt::<CrossTerm>::new("Enter name", Some("idiom".to_owned()), screen);
+    prompt.render(&mut backend);
+
+    loop {
+        backend.flush_buf();
+        if !poll(Duration::from_millis(100))? {
+            continue;
+        }
+        match read()? {
+            Event::Key(key) => match prompt.map(key) {
+                PromptEvent::Edited => prompt.render(&mut backend),
+                PromptEvent::Submitted(text) => {
+                    prompt.render(&mut backend);
+                    if let Some(line) = screen.get_line(0) {
+                        line.render(&format!("Submitted: {text}"), &mut backend);
+                    }
+                }
+                PromptEvent::Cancelled => return Ok(()),
+                PromptEvent::Skipped => {
+                    if key.code == KeyCode::Tab {
+                        prompt.field_mut().select_all();
+                        prompt.render(&mut backend);
+                    }
+                }
+            },
+            Event::Paste(clip) => {
+                let status = prompt.field_mut().paste_passthrough(clip);
+                if status.is_updated() {
+                    prompt.render(&mut backend);
+                }
+            }
+            Event::Resize(..) => {
+                screen = CrossTerm::screen()?;
+                backend.clear_all();
+                prompt.resize(screen);
+                prompt.render(&mut backend);
+            }
+            _ => (),
+        }
+    }
+}
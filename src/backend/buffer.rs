@@ -0,0 +1,410 @@
+use super::{CursorControl, ScreenControl, StyleControl, StyleExt, TextPrint};
+use crate::layout::Rect;
+use std::{
+    fmt::{Debug, Display},
+    io::Write,
+};
+use unicode_width::UnicodeWidthChar;
+
+/// grid size used by init / screen
+const DEFAULT_WIDTH: usize = 80;
+const DEFAULT_HEIGHT: u16 = 24;
+
+/// Single cell of BufferBackend grid - wide chars take two cells, the second one is a continuation (ch is None)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell<S = ()> {
+    pub ch: Option<char>,
+    pub style: S,
+}
+
+impl<S: Default> Cell<S> {
+    fn blank() -> Self {
+        Self {
+            ch: Some(' '),
+            style: S::default(),
+        }
+    }
+}
+
+/// In memory grid of cells, capturing rendered text and styles without terminal (useful for snapshot tests)
+/// Printing out of the grid is clipped, control chars are resolved to spaces.
+/// Style defaults to () - text only, any StyleExt type can be used to capture styles as well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferBackend<S = ()> {
+    cells: Vec<Cell<S>>,
+    width: usize,
+    height: u16,
+    row: u16,
+    col: u16,
+    saved: (u16, u16),
+    style: Option<S>,
+}
+
+impl BufferBackend {
+    /// unstyled grid
+    pub fn new(width: usize, height: u16) -> Self {
+        Self::sized(width, height)
+    }
+}
+
+impl<S: StyleExt + Default + Clone> BufferBackend<S> {
+    pub fn sized(width: usize, height: u16) -> Self {
+        Self {
+            cells: vec![Cell::blank(); width * height as usize],
+            width,
+            height,
+            row: 0,
+            col: 0,
+            saved: (0, 0),
+            style: None,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    #[inline]
+    pub fn cell(&self, row: u16, col: u16) -> Option<&Cell<S>> {
+        if row >= self.height || col as usize >= self.width {
+            return None;
+        }
+        self.cells.get(row as usize * self.width + col as usize)
+    }
+
+    /// grid rows (each is exactly width cols, continuation cells are skipped)
+    pub fn to_string_lines(&self) -> Vec<String> {
+        self.rows()
+            .map(|row| row.iter().flat_map(|cell| cell.ch).collect())
+            .collect()
+    }
+
+    /// grid as newline separated rows with trailing spaces trimmed
+    pub fn render_to_string(&self) -> String {
+        let rows: Vec<String> = self
+            .to_string_lines()
+            .into_iter()
+            .map(|row| row.trim_end().to_owned())
+            .collect();
+        rows.join("\n")
+    }
+
+    pub(super) fn rows(&self) -> impl Iterator<Item = &[Cell<S>]> {
+        // width 0 grid has no cells to chunk
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn put(&mut self, ch: char, style: &S) {
+        let ch = if ch.is_control() { ' ' } else { ch };
+        let width = match ch.width() {
+            Some(0) | None => return,
+            Some(width) => width,
+        };
+        if self.row >= self.height {
+            return;
+        }
+        let col = self.col as usize;
+        if col + width > self.width {
+            // clipped - cursor is moved to the end of the row
+            self.col = self.width as u16;
+            return;
+        }
+        let idx = self.row as usize * self.width + col;
+        self.clear_cells(idx, idx + width);
+        self.cells[idx] = Cell {
+            ch: Some(ch),
+            style: style.clone(),
+        };
+        if width == 2 {
+            self.cells[idx + 1] = Cell {
+                ch: None,
+                style: style.clone(),
+            };
+        }
+        self.col += width as u16;
+    }
+
+    /// clears cells in range (within single row), breaking wide chars on the edges leaves spaces
+    fn clear_cells(&mut self, from: usize, to: usize) {
+        if self.cells[from].ch.is_none() {
+            self.cells[from - 1] = Cell::blank();
+        }
+        if to % self.width != 0 && self.cells[to].ch.is_none() {
+            self.cells[to] = Cell::blank();
+        }
+        for cell in self.cells[from..to].iter_mut() {
+            *cell = Cell::blank();
+        }
+    }
+}
+
+impl<S: StyleExt + Default + Clone> TextPrint for BufferBackend<S> {
+    type Style = S;
+
+    fn print<D: Display>(&mut self, text: D) {
+        let style = self.style.clone().unwrap_or_default();
+        for ch in text.to_string().chars() {
+            self.put(ch, &style);
+        }
+    }
+
+    fn print_styled<D: Display>(&mut self, text: D, style: S) {
+        for ch in text.to_string().chars() {
+            self.put(ch, &style);
+        }
+    }
+}
+
+impl<S: StyleExt + Default + Clone> CursorControl for BufferBackend<S> {
+    fn go_to(&mut self, row: u16, col: u16) {
+        self.row = row;
+        self.col = col;
+    }
+
+    fn save_cursor(&mut self) {
+        self.saved = (self.row, self.col);
+    }
+
+    fn restore_cursor(&mut self) {
+        (self.row, self.col) = self.saved;
+    }
+
+    fn render_cursor_at(&mut self, row: u16, col: u16) {
+        self.go_to(row, col);
+    }
+
+    fn show_cursor(&mut self) {}
+
+    fn hide_cursor(&mut self) {}
+}
+
+impl<S: StyleExt + Default + Clone> StyleControl for BufferBackend<S> {
+    type Color = <S as StyleExt>::Color;
+
+    fn set_style(&mut self, style: S) {
+        self.style = Some(style);
+    }
+
+    fn get_style(&mut self) -> S {
+        self.style.clone().unwrap_or_default()
+    }
+
+    fn to_set_style(&mut self) {}
+
+    fn update_style(&mut self, style: S) {
+        match self.style.as_mut() {
+            Some(current) => current.update(style),
+            None => self.style = Some(style),
+        }
+    }
+
+    fn set_fg(&mut self, color: Option<Self::Color>) {
+        if let Some(current) = self.style.as_mut() {
+            current.set_fg(color);
+        } else if let Some(color) = color {
+            self.style = Some(S::fg(color));
+        }
+    }
+
+    fn set_bg(&mut self, color: Option<Self::Color>) {
+        if let Some(current) = self.style.as_mut() {
+            current.set_bg(color);
+        } else if let Some(color) = color {
+            self.style = Some(S::bg(color));
+        }
+    }
+
+    fn reset_style(&mut self) {
+        self.style = None;
+    }
+
+    fn merge_style(mut left: S, right: S) -> S {
+        left.update(right);
+        left
+    }
+
+    fn reversed_style() -> S {
+        S::reversed()
+    }
+
+    fn dim_style() -> S {
+        S::dim()
+    }
+
+    fn bold_style() -> S {
+        S::bold()
+    }
+
+    fn ital_style() -> S {
+        S::ital()
+    }
+
+    fn slow_blink_style() -> S {
+        S::slowblink()
+    }
+
+    fn underline_style(color: Option<Self::Color>) -> S {
+        S::underlined(color)
+    }
+
+    fn undercurle_style(color: Option<Self::Color>) -> S {
+        S::undercurled(color)
+    }
+
+    fn fg_style(color: Self::Color) -> S {
+        S::fg(color)
+    }
+
+    fn bg_style(color: Self::Color) -> S {
+        S::bg(color)
+    }
+}
+
+impl<S: StyleExt + Default + Clone> ScreenControl for BufferBackend<S> {
+    fn init() -> Self {
+        Self::sized(DEFAULT_WIDTH, DEFAULT_HEIGHT)
+    }
+
+    fn exit() -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn screen() -> std::io::Result<Rect> {
+        Ok(Rect::new(0, 0, DEFAULT_WIDTH, DEFAULT_HEIGHT))
+    }
+
+    fn freeze(&mut self) {}
+
+    fn unfreeze(&mut self) {}
+
+    fn flush_buf(&mut self) {}
+
+    fn clear_to_eol(&mut self) {
+        let col = self.col as usize;
+        if self.row >= self.height || col >= self.width {
+            return;
+        }
+        let row_start = self.row as usize * self.width;
+        self.clear_cells(row_start + col, row_start + self.width);
+    }
+
+    fn clear_line(&mut self) {
+        if self.row >= self.height || self.width == 0 {
+            return;
+        }
+        let row_start = self.row as usize * self.width;
+        self.clear_cells(row_start, row_start + self.width);
+    }
+
+    fn clear_all(&mut self) {
+        for cell in self.cells.iter_mut() {
+            *cell = Cell::blank();
+        }
+    }
+}
+
+impl<S: StyleExt + Default + Clone> Default for BufferBackend<S> {
+    fn default() -> Self {
+        Self::init()
+    }
+}
+
+impl<S: StyleExt + Default + Clone> Write for BufferBackend<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.print(String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferBackend, Cell};
+    use crate::{
+        backend::{CursorControl, MockedStyle, ScreenControl, StyleControl, StyleExt, TextPrint},
+        layout::{Rect, ASCII_BORDERS},
+    };
+
+    #[test]
+    fn render_bordered_box() {
+        let mut backend = BufferBackend::new(12, 5);
+        let rect = Rect::new(1, 1, 8, 4).with_borders();
+        rect.draw_borders(Some(ASCII_BORDERS), None, &mut backend);
+        rect.border_title("box", &mut backend);
+        rect.get_line(0).unwrap().render("hello", &mut backend);
+        assert_eq!(
+            backend.render_to_string(),
+            "\n +box---+\n |hello |\n |      |\n +------+"
+        );
+    }
+
+    #[test]
+    fn render_wide_and_clipped() {
+        let mut backend = BufferBackend::new(5, 2);
+        backend.print("a🚀b\tcdef");
+        backend.print_at(1, 3, "🚀🚀");
+        assert_eq!(backend.render_to_string(), "a🚀b\n   🚀");
+        // overwriting half of wide char
+        backend.print_at(0, 2, "x");
+        backend.print_at(1, 3, "y");
+        assert_eq!(backend.render_to_string(), "a xb\n   y");
+    }
+
+    #[test]
+    fn styled_cells() {
+        let mut backend = BufferBackend::<MockedStyle>::sized(6, 2);
+        backend.set_style(MockedStyle::fg(1));
+        backend.print("a");
+        backend.print_styled("字", MockedStyle::bold());
+        backend.reset_style();
+        backend.print("b");
+        assert_eq!(
+            backend.cell(0, 0),
+            Some(&Cell {
+                ch: Some('a'),
+                style: MockedStyle::fg(1)
+            })
+        );
+        assert_eq!(
+            backend.cell(0, 1),
+            Some(&Cell {
+                ch: Some('字'),
+                style: MockedStyle::bold()
+            })
+        );
+        assert_eq!(backend.cell(0, 2).and_then(|cell| cell.ch), None);
+        assert_eq!(backend.cell(0, 3).and_then(|cell| cell.ch), Some('b'));
+        assert_eq!(backend.cell(2, 0), None);
+        assert_eq!(backend.cell(0, 6), None);
+        assert_eq!(backend.to_string_lines(), vec!["a字b  ", "      "]);
+
+        // clearing half of wide char clears it all
+        backend.go_to(0, 2);
+        backend.clear_to_eol();
+        assert_eq!(backend.to_string_lines(), vec!["a     ", "      "]);
+        assert_eq!(backend.cell(0, 1), Some(&Cell::blank()));
+        backend.print_at(1, 0, "xyz");
+        backend.clear_line();
+        assert_eq!(backend.render_to_string(), "a\n");
+        backend.clear_all();
+        assert_eq!(backend.to_string_lines(), vec!["      ", "      "]);
+    }
+
+    #[test]
+    fn screen_sized() {
+        let backend = BufferBackend::<()>::init();
+        let screen = BufferBackend::<()>::screen().unwrap();
+        assert_eq!(backend.width(), screen.width);
+        assert_eq!(backend.height(), screen.height);
+        assert_eq!(backend.to_string_lines().len(), screen.height as usize);
+    }
+}
@@ -1,4 +1,7 @@
-use super::{style::StyleExt, ERR_MSG};
+use super::{
+    style::{StyleExt, StyleKey},
+    ERR_MSG,
+};
 use crossterm::style::Color;
 use crossterm::style::{Attribute, Attributes};
 use crossterm::{
@@ -16,7 +19,7 @@ use std::{
 
 use super::super::layout::Rect;
 
-use super::Backend;
+use super::{CursorControl, RenderStats, ScreenControl, StyleControl, TextPrint};
 
 /// Thin wrapper around rendering framework, allowing easy switching of backend
 /// If stdout gets an error Backend will crash the program as rendering is to priority
@@ -26,6 +29,91 @@ use super::Backend;
 pub struct CrossTerm {
     writer: Stdout, // could be moved to locked state for performance but current frame generation is about 200 µs
     default_styled: Option<ContentStyle>,
+    color_mode: ColorMode,
+    stats: RenderStats,
+}
+
+/// Colors supported by the terminal - on reduced modes Rgb colors are converted to the nearest ansi color
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorMode {
+    /// detects mode based on $COLORTERM / $TERM (missing $TERM is considered true color - windows)
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        Self::from_env(colorterm.as_deref(), term.as_deref())
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return Self::TrueColor;
+        }
+        match term {
+            None => Self::TrueColor,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => Self::TrueColor,
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some(..) => Self::Ansi16,
+        }
+    }
+
+    /// converts color to the nearest one supported by the mode
+    pub fn convert(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::TrueColor, ..) => color,
+            (Self::Ansi256, Color::Rgb { r, g, b }) => {
+                Color::AnsiValue(coolor::Rgb { r, g, b }.to_ansi().code)
+            }
+            (Self::Ansi256, ..) => color,
+            (Self::Ansi16, Color::Rgb { r, g, b }) => ansi16(coolor::Rgb { r, g, b }),
+            (Self::Ansi16, Color::AnsiValue(code)) if code > 15 => {
+                ansi16(coolor::AnsiColor { code }.to_rgb())
+            }
+            (Self::Ansi16, Color::AnsiValue(code)) => ansi16_color(code),
+            (Self::Ansi16, ..) => color,
+        }
+    }
+
+    /// converts all colors in the style
+    pub fn convert_style(self, mut style: ContentStyle) -> ContentStyle {
+        if self == Self::TrueColor {
+            return style;
+        }
+        style.foreground_color = style.foreground_color.map(|color| self.convert(color));
+        style.background_color = style.background_color.map(|color| self.convert(color));
+        style.underline_color = style.underline_color.map(|color| self.convert(color));
+        style
+    }
+}
+
+fn ansi16(rgb: coolor::Rgb) -> Color {
+    ansi16_color(rgb.nearest_ansi_in_range(0, 15).code)
+}
+
+fn ansi16_color(code: u8) -> Color {
+    match code {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        7 => Color::Grey,
+        8 => Color::DarkGrey,
+        9 => Color::Red,
+        10 => Color::Green,
+        11 => Color::Yellow,
+        12 => Color::Blue,
+        13 => Color::Magenta,
+        14 => Color::Cyan,
+        _ => Color::White,
+    }
 }
 
 impl Default for CrossTerm {
@@ -56,17 +144,16 @@ impl Write for CrossTerm {
 
     #[inline(always)]
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.writer.write(buf)
+        let written = self.writer.write(buf)?;
+        self.stats.bytes += written;
+        Ok(written)
     }
 
     #[inline(always)]
     fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
-        self.writer.write_all(buf)
-    }
-
-    #[inline(always)]
-    fn write_fmt(&mut self, fmt: std::fmt::Arguments<'_>) -> std::io::Result<()> {
-        self.writer.write_fmt(fmt)
+        self.writer.write_all(buf)?;
+        self.stats.bytes += buf.len();
+        Ok(())
     }
 }
 
@@ -78,82 +165,158 @@ impl CrossTerm {
     pub fn detached_show_cursor() {
         queue!(std::io::stdout(), Show).expect(ERR_MSG);
     }
+
+    /// overrides detected color mode
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+        if let Some(style) = self.default_styled {
+            self.set_style(style);
+        }
+    }
+
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
 }
 
-impl Backend for CrossTerm {
+impl TextPrint for CrossTerm {
     type Style = ContentStyle;
-    type Color = Color;
 
     #[inline]
-    fn init() -> Self {
-        init_terminal().expect(ERR_MSG);
-        Self {
-            writer: std::io::stdout(),
-            default_styled: None,
+    fn print<D: Display>(&mut self, text: D) {
+        self.stats.prints += 1;
+        queue!(self, Print(text)).expect(ERR_MSG);
+    }
+
+    /// prints styled text without affecting the writer set style
+    #[inline]
+    fn print_styled<D: Display>(&mut self, text: D, style: ContentStyle) {
+        self.stats.styled_prints += 1;
+        let style = self.color_mode.convert_style(style);
+        match self.default_styled {
+            Some(restore_style) => queue!(
+                self,
+                SetStyle(style),
+                Print(text),
+                ResetColor,
+                SetStyle(restore_style),
+            ),
+            None => queue!(self, SetStyle(style), Print(text), ResetColor,),
         }
+        .expect(ERR_MSG);
     }
 
     #[inline]
-    fn exit() -> std::io::Result<()> {
-        graceful_exit()
+    fn pad(&mut self, width: usize) {
+        self.stats.padded_cells += width;
+        queue!(self, Print(format!("{:width$}", ""))).expect(ERR_MSG);
     }
 
-    /// get whole screen as rect
     #[inline]
-    fn screen() -> std::io::Result<Rect> {
-        size().map(Rect::from)
+    fn pad_styled(&mut self, width: usize, style: ContentStyle) {
+        self.stats.padded_cells += width;
+        let style = self.color_mode.convert_style(style);
+        let text = format!("{:width$}", "");
+        match self.default_styled {
+            Some(restore_style) => queue!(
+                self,
+                SetStyle(style),
+                Print(text),
+                ResetColor,
+                SetStyle(restore_style)
+            ),
+            None => queue!(self, SetStyle(style), Print(text), ResetColor),
+        }
+        .expect(ERR_MSG);
     }
 
-    /// freeze screen allowing to build buffer
     #[inline]
-    fn freeze(&mut self) {
-        execute!(self, BeginSynchronizedUpdate).expect(ERR_MSG);
+    fn stats(&self) -> Option<&RenderStats> {
+        Some(&self.stats)
     }
+}
 
-    /// unfreeze allowing the buffer to render
+impl CursorControl for CrossTerm {
+    /// stores the cursor
     #[inline]
-    fn unfreeze(&mut self) {
-        execute!(self, EndSynchronizedUpdate).expect(ERR_MSG);
+    fn save_cursor(&mut self) {
+        execute!(self, SavePosition).expect(ERR_MSG);
     }
 
-    /// flushs buffer with panic on error
+    /// restores cursor position
     #[inline]
-    fn flush_buf(&mut self) {
-        self.writer.flush().expect(ERR_MSG);
+    fn restore_cursor(&mut self) {
+        queue!(self, RestorePosition).expect(ERR_MSG);
     }
 
-    /// clears from cursor until the End Of Line
+    /// sends the cursor to location
     #[inline]
-    fn clear_to_eol(&mut self) {
-        queue!(self, Clear(ClearType::UntilNewLine)).expect(ERR_MSG);
+    fn go_to(&mut self, row: u16, col: u16) {
+        self.stats.go_tos += 1;
+        queue!(self, MoveTo(col, row)).expect(ERR_MSG);
     }
 
-    /// clears current cursor line
+    /// direct adding cursor at location - no buffer queing
     #[inline]
-    fn clear_line(&mut self) {
-        queue!(self, Clear(ClearType::CurrentLine)).expect(ERR_MSG);
+    fn render_cursor_at(&mut self, row: u16, col: u16) {
+        queue!(self, MoveTo(col, row), Show).expect(ERR_MSG);
     }
 
+    /// direct showing cursor - no buffer queing
     #[inline]
-    fn clear_all(&mut self) {
-        queue!(self, Clear(ClearType::All)).expect(ERR_MSG);
+    fn show_cursor(&mut self) {
+        queue!(self, Show).expect(ERR_MSG);
     }
 
-    /// stores the cursor
+    /// direct hiding cursor - no buffer queing
     #[inline]
-    fn save_cursor(&mut self) {
-        execute!(self, SavePosition).expect(ERR_MSG);
+    fn hide_cursor(&mut self) {
+        queue!(self, Hide).expect(ERR_MSG);
     }
 
-    /// restores cursor position
+    /// goes to location and prints text
     #[inline]
-    fn restore_cursor(&mut self) {
-        queue!(self, RestorePosition).expect(ERR_MSG);
+    fn print_at<D: Display>(&mut self, row: u16, col: u16, text: D) {
+        self.stats.go_tos += 1;
+        self.stats.prints += 1;
+        queue!(self, MoveTo(col, row), Print(text)).expect(ERR_MSG);
     }
 
+    /// goes to location and prints styled text without affecting the writer set style
+    #[inline]
+    fn print_styled_at<D: Display>(&mut self, row: u16, col: u16, text: D, style: ContentStyle) {
+        self.stats.go_tos += 1;
+        self.stats.styled_prints += 1;
+        let style = self.color_mode.convert_style(style);
+        if let Some(restore_style) = self.default_styled {
+            queue!(
+                self,
+                SetStyle(style),
+                MoveTo(col, row),
+                Print(text),
+                ResetColor,
+                SetStyle(restore_style),
+            )
+        } else {
+            queue!(
+                self,
+                SetStyle(style),
+                MoveTo(col, row),
+                Print(text),
+                ResetColor,
+            )
+        }
+        .expect(ERR_MSG);
+    }
+}
+
+impl StyleControl for CrossTerm {
+    type Color = Color;
+
     /// sets the style for the print/print at
     #[inline]
     fn set_style(&mut self, style: ContentStyle) {
+        let style = self.color_mode.convert_style(style);
         self.default_styled.replace(style);
         queue!(self, ResetColor, SetStyle(style)).expect(ERR_MSG);
     }
@@ -176,6 +339,7 @@ impl Backend for CrossTerm {
     /// mods will be taken from updating and will replace fg and bg if present
     #[inline]
     fn update_style(&mut self, style: ContentStyle) {
+        let style = self.color_mode.convert_style(style);
         if let Some(current) = self.default_styled.as_mut() {
             current.update(style);
         } else {
@@ -187,6 +351,7 @@ impl Backend for CrossTerm {
     /// adds foreground to the already set style
     #[inline]
     fn set_fg(&mut self, color: Option<Color>) {
+        let color = color.map(|color| self.color_mode.convert(color));
         if let Some(current) = self.default_styled.as_mut() {
             current.set_fg(color);
         } else if let Some(color) = color {
@@ -198,6 +363,7 @@ impl Backend for CrossTerm {
     /// adds background to the already set style
     #[inline]
     fn set_bg(&mut self, color: Option<Color>) {
+        let color = color.map(|color| self.color_mode.convert(color));
         if let Some(current) = self.default_styled.as_mut() {
             current.set_bg(color);
         } else if let Some(color) = color {
@@ -214,144 +380,119 @@ impl Backend for CrossTerm {
         queue!(self, ResetColor).expect(ERR_MSG);
     }
 
-    /// sends the cursor to location
     #[inline]
-    fn go_to(&mut self, row: u16, col: u16) {
-        queue!(self, MoveTo(col, row)).expect(ERR_MSG);
+    fn merge_style(mut left: ContentStyle, right: ContentStyle) -> ContentStyle {
+        left.update(right);
+        left
     }
 
-    /// direct adding cursor at location - no buffer queing
     #[inline]
-    fn render_cursor_at(&mut self, row: u16, col: u16) {
-        queue!(self, MoveTo(col, row), Show).expect(ERR_MSG);
+    fn reversed_style() -> Self::Style {
+        Self::Style::reversed()
     }
 
-    /// direct showing cursor - no buffer queing
     #[inline]
-    fn show_cursor(&mut self) {
-        queue!(self, Show).expect(ERR_MSG);
+    fn dim_style() -> Self::Style {
+        Self::Style::dim()
     }
 
-    /// direct hiding cursor - no buffer queing
     #[inline]
-    fn hide_cursor(&mut self) {
-        queue!(self, Hide).expect(ERR_MSG);
+    fn bold_style() -> Self::Style {
+        Self::Style::bold()
     }
 
     #[inline]
-    fn print<D: Display>(&mut self, text: D) {
-        queue!(self, Print(text)).expect(ERR_MSG);
+    fn slow_blink_style() -> Self::Style {
+        Self::Style::slowblink()
     }
 
-    /// goes to location and prints text
     #[inline]
-    fn print_at<D: Display>(&mut self, row: u16, col: u16, text: D) {
-        queue!(self, MoveTo(col, row), Print(text)).expect(ERR_MSG);
+    fn ital_style() -> Self::Style {
+        Self::Style::ital()
     }
 
-    /// prints styled text without affecting the writer set style
     #[inline]
-    fn print_styled<D: Display>(&mut self, text: D, style: ContentStyle) {
-        match self.default_styled {
-            Some(restore_style) => queue!(
-                self,
-                SetStyle(style),
-                Print(text),
-                ResetColor,
-                SetStyle(restore_style),
-            ),
-            None => queue!(self, SetStyle(style), Print(text), ResetColor,),
-        }
-        .expect(ERR_MSG);
+    fn undercurle_style(color: Option<Self::Color>) -> Self::Style {
+        Self::Style::undercurled(color)
     }
 
-    /// goes to location and prints styled text without affecting the writer set style
     #[inline]
-    fn print_styled_at<D: Display>(&mut self, row: u16, col: u16, text: D, style: ContentStyle) {
-        if let Some(restore_style) = self.default_styled {
-            queue!(
-                self,
-                SetStyle(style),
-                MoveTo(col, row),
-                Print(text),
-                ResetColor,
-                SetStyle(restore_style),
-            )
-        } else {
-            queue!(
-                self,
-                SetStyle(style),
-                MoveTo(col, row),
-                Print(text),
-                ResetColor,
-            )
-        }
-        .expect(ERR_MSG);
+    fn underline_style(color: Option<Self::Color>) -> Self::Style {
+        Self::Style::underlined(color)
     }
 
-    #[inline]
-    fn pad(&mut self, width: usize) {
-        queue!(self, Print(format!("{:width$}", ""))).expect(ERR_MSG);
+    fn fg_style(color: Self::Color) -> Self::Style {
+        Self::Style::fg(color)
     }
 
+    fn bg_style(color: Self::Color) -> Self::Style {
+        Self::Style::bg(color)
+    }
+}
+
+impl ScreenControl for CrossTerm {
     #[inline]
-    fn pad_styled(&mut self, width: usize, style: ContentStyle) {
-        let text = format!("{:width$}", "");
-        match self.default_styled {
-            Some(restore_style) => queue!(
-                self,
-                SetStyle(style),
-                Print(text),
-                ResetColor,
-                SetStyle(restore_style)
-            ),
-            None => queue!(self, SetStyle(style), Print(text), ResetColor),
+    fn init() -> Self {
+        init_terminal().expect(ERR_MSG);
+        Self {
+            writer: std::io::stdout(),
+            default_styled: None,
+            color_mode: ColorMode::detect(),
+            stats: RenderStats::default(),
         }
-        .expect(ERR_MSG);
     }
 
     #[inline]
-    fn merge_style(mut left: ContentStyle, right: ContentStyle) -> ContentStyle {
-        left.update(right);
-        left
+    fn exit() -> std::io::Result<()> {
+        graceful_exit()
     }
 
+    /// get whole screen as rect
     #[inline]
-    fn reversed_style() -> Self::Style {
-        Self::Style::reversed()
+    fn screen() -> std::io::Result<Rect> {
+        size().map(Rect::from)
     }
 
+    /// freeze screen allowing to build buffer
     #[inline]
-    fn bold_style() -> Self::Style {
-        Self::Style::bold()
+    fn freeze(&mut self) {
+        self.stats.reset();
+        execute!(self, BeginSynchronizedUpdate).expect(ERR_MSG);
     }
 
+    /// unfreeze allowing the buffer to render
     #[inline]
-    fn slow_blink_style() -> Self::Style {
-        Self::Style::slowblink()
+    fn unfreeze(&mut self) {
+        execute!(self, EndSynchronizedUpdate).expect(ERR_MSG);
     }
 
+    /// flushs buffer with panic on error
     #[inline]
-    fn ital_style() -> Self::Style {
-        Self::Style::ital()
+    fn flush_buf(&mut self) {
+        self.writer.flush().expect(ERR_MSG);
     }
 
+    /// clears from cursor until the End Of Line
     #[inline]
-    fn undercurle_style(color: Option<Self::Color>) -> Self::Style {
-        Self::Style::undercurled(color)
+    fn clear_to_eol(&mut self) {
+        queue!(self, Clear(ClearType::UntilNewLine)).expect(ERR_MSG);
     }
 
+    /// clears current cursor line
     #[inline]
-    fn underline_style(color: Option<Self::Color>) -> Self::Style {
-        Self::Style::underlined(color)
+    fn clear_line(&mut self) {
+        queue!(self, Clear(ClearType::CurrentLine)).expect(ERR_MSG);
     }
 
-    fn fg_style(color: Self::Color) -> Self::Style {
-        Self::Style::fg(color)
+    #[inline]
+    fn clear_all(&mut self) {
+        queue!(self, Clear(ClearType::All)).expect(ERR_MSG);
     }
 
-    fn bg_style(color: Self::Color) -> Self::Style {
-        Self::Style::bg(color)
+    /// emits BEL char
+    #[inline]
+    fn bell(&mut self) {
+        queue!(self, Print('\x07')).expect(ERR_MSG);
     }
 }
 
@@ -401,6 +542,18 @@ fn graceful_exit() -> std::io::Result<()> {
     Ok(())
 }
 
+impl StyleKey for ContentStyle {
+    fn hash_key<H: std::hash::Hasher>(&self, state: &mut H) {
+        use std::hash::Hash;
+        self.foreground_color.hash(state);
+        self.background_color.hash(state);
+        self.underline_color.hash(state);
+        for attr in Attribute::iterator() {
+            self.attributes.has(attr).hash(state);
+        }
+    }
+}
+
 impl StyleExt for ContentStyle {
     type Attribute = Attribute;
     type Color = Color;
@@ -533,6 +686,21 @@ impl StyleExt for ContentStyle {
         }
     }
 
+    #[inline]
+    fn add_dim(&mut self) {
+        self.attributes.set(Attribute::Dim);
+    }
+
+    #[inline]
+    fn dim() -> Self {
+        ContentStyle {
+            background_color: None,
+            foreground_color: None,
+            underline_color: None,
+            attributes: Attribute::Dim.into(),
+        }
+    }
+
     #[inline]
     fn reset_mods(&mut self) {
         self.attributes = Attributes::default();
@@ -737,3 +905,67 @@ impl std::fmt::Display for ParseColorError {
 }
 
 impl std::error::Error for ParseColorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorMode;
+    use crossterm::style::{Color, ContentStyle};
+
+    #[test]
+    fn detect_color_mode() {
+        assert_eq!(
+            ColorMode::from_env(Some("truecolor"), Some("xterm")),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            ColorMode::from_env(None, Some("xterm-256color")),
+            ColorMode::Ansi256
+        );
+        assert_eq!(ColorMode::from_env(None, Some("linux")), ColorMode::Ansi16);
+        assert_eq!(ColorMode::from_env(None, None), ColorMode::TrueColor);
+    }
+
+    #[test]
+    fn rgb_to_256() {
+        let convert = |r, g, b| ColorMode::Ansi256.convert(Color::Rgb { r, g, b });
+        assert_eq!(convert(255, 0, 0), Color::AnsiValue(196));
+        assert_eq!(convert(0, 0, 255), Color::AnsiValue(21));
+        assert_eq!(convert(0, 95, 135), Color::AnsiValue(24));
+        assert_eq!(convert(250, 5, 3), Color::AnsiValue(196));
+        assert_eq!(convert(128, 128, 128), Color::AnsiValue(244));
+        assert_eq!(convert(0, 0, 0), Color::AnsiValue(16));
+        assert_eq!(ColorMode::Ansi256.convert(Color::Red), Color::Red);
+        assert_eq!(
+            ColorMode::TrueColor.convert(Color::Rgb { r: 1, g: 2, b: 3 }),
+            Color::Rgb { r: 1, g: 2, b: 3 }
+        );
+    }
+
+    #[test]
+    fn rgb_to_16() {
+        let mode = ColorMode::Ansi16;
+        assert_eq!(mode.convert(Color::Rgb { r: 250, g: 0, b: 0 }), Color::Red);
+        assert_eq!(
+            mode.convert(Color::Rgb {
+                r: 10,
+                g: 10,
+                b: 10
+            }),
+            Color::Black
+        );
+        assert_eq!(mode.convert(Color::AnsiValue(196)), Color::Red);
+        assert_eq!(mode.convert(Color::AnsiValue(4)), Color::DarkBlue);
+        let style = ContentStyle {
+            foreground_color: Some(Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            }),
+            background_color: Some(Color::Blue),
+            ..Default::default()
+        };
+        let converted = mode.convert_style(style);
+        assert_eq!(converted.foreground_color, Some(Color::White));
+        assert_eq!(converted.background_color, Some(Color::Blue));
+    }
+}
@@ -0,0 +1,546 @@
+use super::{
+    buffer::{BufferBackend, Cell},
+    CursorControl, RenderStats, ScreenControl, StyleControl, StyleExt, TextPrint,
+};
+use crate::layout::Rect;
+use std::{
+    fmt::{Debug, Display},
+    io::Write,
+};
+
+/// Wrapper rendering into in memory grid and emitting only the changed cells to the inner backend.
+/// Changes are emitted on flush_buf / unfreeze, style constructors are delegated to the inner backend.
+///
+/// Cost model: each emit compares the full grid (width * height cells) with the previous one,
+/// holding two grids in memory; the output is one go_to per run of changed cells on a row
+/// plus one print per style change within the run - unchanged frames produce no output.
+/// Cells printed with default style use print (inner set style is not touched).
+/// Cursor rendering (render_cursor_at / show_cursor) is replayed after the changes are emitted.
+pub struct DiffBackend<B: StyleControl>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    inner: B,
+    current: BufferBackend<<B as TextPrint>::Style>,
+    previous: BufferBackend<<B as TextPrint>::Style>,
+    /// counters of calls made on the wrapper (reset on freeze)
+    requested: RenderStats,
+    pending_cursor: Option<PendingCursor>,
+}
+
+/// last cursor request made before emit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingCursor {
+    At(u16, u16),
+    Show,
+}
+
+impl<B: StyleControl> DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    pub fn new(inner: B, width: usize, height: u16) -> Self {
+        Self {
+            inner,
+            current: BufferBackend::sized(width, height),
+            previous: BufferBackend::sized(width, height),
+            requested: RenderStats::default(),
+            pending_cursor: None,
+        }
+    }
+
+    /// counters of calls made on the wrapper in the current frame,
+    /// stats returns the counters of the inner backend (actually emitted)
+    #[inline]
+    pub fn requested_stats(&self) -> &RenderStats {
+        &self.requested
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// grid with the latest rendered content (not yet emitted changes included)
+    #[inline]
+    pub fn buffer(&self) -> &BufferBackend<<B as TextPrint>::Style> {
+        &self.current
+    }
+
+    /// resizes the grids - content is dropped, next emit redraws everything
+    pub fn resize(&mut self, width: usize, height: u16)
+    where
+        B: ScreenControl,
+    {
+        self.current = BufferBackend::sized(width, height);
+        self.invalidate();
+    }
+
+    /// screen state is unknown (cleared or overwritten) - clears it and next emit redraws all cells
+    pub fn invalidate(&mut self)
+    where
+        B: ScreenControl,
+    {
+        self.previous = BufferBackend::sized(self.current.width(), self.current.height());
+        self.inner.clear_all();
+    }
+
+    /// emits changed cells to the inner backend
+    fn emit_changes(&mut self)
+    where
+        B: CursorControl,
+    {
+        for (row_idx, (row, prev_row)) in self.current.rows().zip(self.previous.rows()).enumerate()
+        {
+            let mut col = 0;
+            while col < row.len() {
+                if row[col] == prev_row[col] {
+                    col += 1;
+                    continue;
+                }
+                // run should start at the head of wide char
+                let start = match row[col].ch {
+                    None if col != 0 => col - 1,
+                    _ => col,
+                };
+                let mut end = col + 1;
+                while end < row.len() && (row[end] != prev_row[end] || row[end].ch.is_none()) {
+                    end += 1;
+                }
+                self.inner.go_to(row_idx as u16, start as u16);
+                print_run(&mut self.inner, &row[start..end]);
+                col = end;
+            }
+        }
+        self.previous.clone_from(&self.current);
+        match self.pending_cursor.take() {
+            Some(PendingCursor::At(row, col)) => self.inner.render_cursor_at(row, col),
+            Some(PendingCursor::Show) => self.inner.show_cursor(),
+            None => (),
+        }
+    }
+}
+
+/// prints cells grouped by style
+fn print_run<B: TextPrint>(backend: &mut B, cells: &[Cell<<B as TextPrint>::Style>])
+where
+    <B as TextPrint>::Style: Default,
+{
+    let default_style = <B as TextPrint>::Style::default();
+    let mut text = String::new();
+    let mut style = None;
+    for cell in cells {
+        let Some(ch) = cell.ch else {
+            continue;
+        };
+        if style.as_ref() != Some(&cell.style) {
+            if let Some(style) = style.take() {
+                print_text(backend, &text, style, &default_style);
+                text.clear();
+            }
+            style = Some(cell.style.clone());
+        }
+        text.push(ch);
+    }
+    if let Some(style) = style {
+        print_text(backend, &text, style, &default_style);
+    }
+}
+
+#[inline]
+fn print_text<B: TextPrint>(
+    backend: &mut B,
+    text: &str,
+    style: <B as TextPrint>::Style,
+    default_style: &<B as TextPrint>::Style,
+) {
+    match &style == default_style {
+        true => backend.print(text),
+        false => backend.print_styled(text, style),
+    }
+}
+
+impl<B: StyleControl + Debug> Debug for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiffBackend")
+            .field("inner", &self.inner)
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+impl<B: StyleControl> PartialEq for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.current == other.current
+    }
+}
+
+impl<B: ScreenControl + StyleControl> Default for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    fn default() -> Self {
+        Self::init()
+    }
+}
+
+impl<B: StyleControl> Write for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.current.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<B: StyleControl> TextPrint for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    type Style = <B as TextPrint>::Style;
+
+    fn print<D: Display>(&mut self, text: D) {
+        let text = text.to_string();
+        self.requested.prints += 1;
+        self.requested.bytes += text.len();
+        self.current.print(text);
+    }
+
+    fn print_styled<D: Display>(&mut self, text: D, style: Self::Style) {
+        let text = text.to_string();
+        self.requested.styled_prints += 1;
+        self.requested.bytes += text.len();
+        self.current.print_styled(text, style);
+    }
+
+    fn pad(&mut self, width: usize) {
+        self.requested.padded_cells += width;
+        self.requested.bytes += width;
+        self.current.pad(width);
+    }
+
+    fn pad_styled(&mut self, width: usize, style: Self::Style) {
+        self.requested.padded_cells += width;
+        self.requested.bytes += width;
+        self.current.pad_styled(width, style);
+    }
+
+    fn stats(&self) -> Option<&RenderStats> {
+        self.inner.stats()
+    }
+}
+
+impl<B: CursorControl + StyleControl> CursorControl for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    fn go_to(&mut self, row: u16, col: u16) {
+        self.requested.go_tos += 1;
+        self.current.go_to(row, col);
+    }
+
+    fn save_cursor(&mut self) {
+        self.current.save_cursor();
+    }
+
+    fn restore_cursor(&mut self) {
+        self.current.restore_cursor();
+    }
+
+    /// rendered after the changes are emitted (flush_buf / unfreeze)
+    fn render_cursor_at(&mut self, row: u16, col: u16) {
+        self.pending_cursor = Some(PendingCursor::At(row, col));
+    }
+
+    /// shown after the changes are emitted (flush_buf / unfreeze)
+    fn show_cursor(&mut self) {
+        if self.pending_cursor.is_none() {
+            self.pending_cursor = Some(PendingCursor::Show);
+        }
+    }
+
+    /// hidden directly, drops pending cursor rendering
+    fn hide_cursor(&mut self) {
+        self.pending_cursor = None;
+        self.inner.hide_cursor();
+    }
+}
+
+impl<B: StyleControl> StyleControl for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    type Color = <B as StyleControl>::Color;
+
+    fn set_style(&mut self, style: Self::Style) {
+        self.current.set_style(style);
+    }
+
+    fn get_style(&mut self) -> Self::Style {
+        self.current.get_style()
+    }
+
+    fn to_set_style(&mut self) {}
+
+    fn update_style(&mut self, style: Self::Style) {
+        let merged = B::merge_style(self.current.get_style(), style);
+        self.current.set_style(merged);
+    }
+
+    fn set_fg(&mut self, color: Option<Self::Color>) {
+        let mut style = self.current.get_style();
+        match color {
+            Some(color) => style = B::merge_style(style, B::fg_style(color)),
+            None => style.set_fg(None),
+        }
+        self.current.set_style(style);
+    }
+
+    fn set_bg(&mut self, color: Option<Self::Color>) {
+        let mut style = self.current.get_style();
+        match color {
+            Some(color) => style = B::merge_style(style, B::bg_style(color)),
+            None => style.drop_bg(),
+        }
+        self.current.set_style(style);
+    }
+
+    fn reset_style(&mut self) {
+        self.current.reset_style();
+    }
+
+    fn merge_style(left: Self::Style, right: Self::Style) -> Self::Style {
+        B::merge_style(left, right)
+    }
+
+    fn reversed_style() -> Self::Style {
+        B::reversed_style()
+    }
+
+    fn dim_style() -> Self::Style {
+        B::dim_style()
+    }
+
+    fn bold_style() -> Self::Style {
+        B::bold_style()
+    }
+
+    fn ital_style() -> Self::Style {
+        B::ital_style()
+    }
+
+    fn slow_blink_style() -> Self::Style {
+        B::slow_blink_style()
+    }
+
+    fn underline_style(color: Option<Self::Color>) -> Self::Style {
+        B::underline_style(color)
+    }
+
+    fn undercurle_style(color: Option<Self::Color>) -> Self::Style {
+        B::undercurle_style(color)
+    }
+
+    fn fg_style(color: Self::Color) -> Self::Style {
+        B::fg_style(color)
+    }
+
+    fn bg_style(color: Self::Color) -> Self::Style {
+        B::bg_style(color)
+    }
+}
+
+impl<B: ScreenControl + StyleControl> ScreenControl for DiffBackend<B>
+where
+    <B as TextPrint>::Style: StyleExt + Default,
+{
+    /// grids are sized from the inner backend screen
+    fn init() -> Self {
+        let inner = B::init();
+        let screen = B::screen().unwrap_or(Rect::new(0, 0, 0, 0));
+        Self::new(inner, screen.width, screen.height)
+    }
+
+    fn exit() -> std::io::Result<()> {
+        B::exit()
+    }
+
+    fn screen() -> std::io::Result<Rect> {
+        B::screen()
+    }
+
+    fn freeze(&mut self) {
+        self.requested.reset();
+        self.inner.freeze();
+    }
+
+    fn unfreeze(&mut self) {
+        self.emit_changes();
+        self.inner.unfreeze();
+    }
+
+    fn flush_buf(&mut self) {
+        self.emit_changes();
+        self.inner.flush_buf();
+    }
+
+    fn clear_to_eol(&mut self) {
+        self.current.clear_to_eol();
+    }
+
+    fn clear_line(&mut self) {
+        self.current.clear_line();
+    }
+
+    fn clear_all(&mut self) {
+        self.current.clear_all();
+    }
+
+    fn bell(&mut self) {
+        self.inner.bell();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiffBackend;
+    use crate::{
+        backend::{
+            CursorControl, MockedBackend, MockedStyle, RenderStats, ScreenControl, StyleExt,
+            TextPrint,
+        },
+        layout::Rect,
+        widgets::State,
+    };
+
+    #[test]
+    fn single_changed_cell() {
+        let mut backend = DiffBackend::new(MockedBackend::init(), 80, 24);
+        backend.print_at(3, 5, "hello");
+        backend.print_styled_at(10, 0, "ab", MockedStyle::bold());
+        backend.flush_buf();
+        assert_eq!(
+            backend.inner_mut().drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 3 col: 5>>".to_owned()),
+                (MockedStyle::default(), "hello".to_owned()),
+                (
+                    MockedStyle::default(),
+                    "<<go to row: 10 col: 0>>".to_owned()
+                ),
+                (MockedStyle::bold(), "ab".to_owned()),
+            ]
+        );
+
+        backend.print_at(3, 5, "hallo");
+        backend.flush_buf();
+        assert_eq!(
+            backend.inner_mut().drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 3 col: 6>>".to_owned()),
+                (MockedStyle::default(), "a".to_owned()),
+            ]
+        );
+        backend.flush_buf();
+        assert!(backend.inner_mut().drain().is_empty());
+    }
+
+    #[test]
+    fn cursor_after_changes() {
+        let mut backend = DiffBackend::new(MockedBackend::init(), 20, 2);
+        backend.print_at(0, 0, "hello");
+        backend.render_cursor_at(0, 2);
+        backend.flush_buf();
+        assert_eq!(
+            backend.inner_mut().drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), "hello".to_owned()),
+                (
+                    MockedStyle::default(),
+                    "<<draw cursor row: 0 col: 2>>".to_owned()
+                ),
+            ]
+        );
+        // cursor is rendered once
+        backend.flush_buf();
+        assert!(backend.inner_mut().drain().is_empty());
+        // hidden cursor is not rendered
+        backend.render_cursor_at(1, 1);
+        backend.hide_cursor();
+        backend.flush_buf();
+        assert!(backend.inner_mut().drain().is_empty());
+    }
+
+    #[test]
+    fn requested_and_emitted_stats() {
+        let mut backend = DiffBackend::new(MockedBackend::init(), 20, 2);
+        for _ in 0..2 {
+            backend.freeze();
+            backend.print_at(0, 0, "hello");
+            backend.print_styled_at(1, 0, "ab", MockedStyle::bold());
+            backend.pad(3);
+            backend.unfreeze();
+        }
+        let requested = RenderStats {
+            prints: 1,
+            styled_prints: 1,
+            go_tos: 2,
+            padded_cells: 3,
+            bytes: 10,
+        };
+        assert_eq!(backend.requested_stats(), &requested);
+        // nothing changed in the second frame
+        assert_eq!(backend.stats(), Some(&RenderStats::default()));
+    }
+
+    #[test]
+    fn wide_and_styled_runs() {
+        let mut backend = DiffBackend::new(MockedBackend::init(), 10, 2);
+        backend.print_at(0, 0, "a字b");
+        backend.flush_buf();
+        backend.inner_mut().drain();
+        backend.print_at(0, 1, "字");
+        backend.print_styled_at(0, 2, "x", MockedStyle::bold());
+        backend.flush_buf();
+        assert_eq!(
+            backend.inner_mut().drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned()),
+                (MockedStyle::default(), " ".to_owned()),
+                (MockedStyle::bold(), "x".to_owned()),
+            ]
+        );
+        assert_eq!(backend.buffer().render_to_string(), "a xb\n");
+    }
+
+    #[test]
+    fn same_list_twice() {
+        let mut backend = DiffBackend::new(MockedBackend::init(), 20, 5);
+        let mut state = State::<DiffBackend<MockedBackend>>::new();
+        let options = ["first", "second", "third"];
+        let rect = Rect::new(0, 0, 20, 5);
+        state.render_list(options.into_iter(), rect, &mut backend);
+        backend.flush_buf();
+        assert!(!backend.inner_mut().drain().is_empty());
+        backend.clear_all();
+        state.render_list(options.into_iter(), rect, &mut backend);
+        backend.flush_buf();
+        assert!(backend.inner_mut().drain().is_empty());
+    }
+}
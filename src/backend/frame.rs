@@ -0,0 +1,206 @@
+use super::ScreenControl;
+use std::{
+    ops::{Deref, DerefMut},
+    time::{Duration, Instant},
+};
+
+/// Source of time for FrameLimiter (allows injecting time in tests)
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Freezes the backend for the lifetime of the guard, on drop unfreezes and flushes the buffer
+pub struct FrameGuard<'a, B: ScreenControl> {
+    backend: &'a mut B,
+}
+
+impl<'a, B: ScreenControl> FrameGuard<'a, B> {
+    pub fn new(backend: &'a mut B) -> Self {
+        backend.freeze();
+        Self { backend }
+    }
+}
+
+impl<B: ScreenControl> Deref for FrameGuard<'_, B> {
+    type Target = B;
+
+    fn deref(&self) -> &Self::Target {
+        self.backend
+    }
+}
+
+impl<B: ScreenControl> DerefMut for FrameGuard<'_, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.backend
+    }
+}
+
+impl<B: ScreenControl> Drop for FrameGuard<'_, B> {
+    fn drop(&mut self) {
+        self.backend.unfreeze();
+        self.backend.flush_buf();
+    }
+}
+
+/// Throttles rendering to minimum interval between frames - never sleeps,
+/// only answers if the frame should be rendered now.
+#[derive(Debug, Clone)]
+pub struct FrameLimiter<C: Clock = SystemClock> {
+    clock: C,
+    min_interval: Duration,
+    last_render: Option<Instant>,
+    forced: bool,
+}
+
+impl FrameLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        Self::with_clock(min_interval, SystemClock)
+    }
+}
+
+impl<C: Clock> FrameLimiter<C> {
+    pub fn with_clock(min_interval: Duration, clock: C) -> Self {
+        Self {
+            clock,
+            min_interval,
+            last_render: None,
+            forced: false,
+        }
+    }
+
+    #[inline]
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    #[inline]
+    pub fn set_min_interval(&mut self, min_interval: Duration) {
+        self.min_interval = min_interval;
+    }
+
+    /// next should_render will return true regardless of the interval (resize)
+    #[inline]
+    pub fn force(&mut self) {
+        self.forced = true;
+    }
+
+    /// returns true if the frame should be rendered (counts as rendered)
+    pub fn should_render(&mut self) -> bool {
+        let now = self.clock.now();
+        let elapsed = match self.last_render {
+            Some(last_render) => now.saturating_duration_since(last_render) >= self.min_interval,
+            None => true,
+        };
+        if !self.forced && !elapsed {
+            return false;
+        }
+        self.forced = false;
+        self.last_render = Some(now);
+        true
+    }
+
+    /// returns frame guard if the frame should be rendered
+    pub fn frame<'a, B: ScreenControl>(&mut self, backend: &'a mut B) -> Option<FrameGuard<'a, B>> {
+        if !self.should_render() {
+            return None;
+        }
+        Some(FrameGuard::new(backend))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock, FrameLimiter};
+    use crate::backend::{CursorControl, MockedBackend, MockedStyle, ScreenControl};
+    use std::{
+        cell::Cell,
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+
+    #[derive(Clone)]
+    struct TestClock {
+        start: Instant,
+        offset: Rc<Cell<Duration>>,
+    }
+
+    impl TestClock {
+        fn new() -> Self {
+            Self {
+                start: Instant::now(),
+                offset: Rc::default(),
+            }
+        }
+
+        fn advance(&self, millis: u64) {
+            self.offset
+                .set(self.offset.get() + Duration::from_millis(millis));
+        }
+    }
+
+    impl Clock for TestClock {
+        fn now(&self) -> Instant {
+            self.start + self.offset.get()
+        }
+    }
+
+    #[test]
+    fn throttling() {
+        let clock = TestClock::new();
+        let mut limiter = FrameLimiter::with_clock(Duration::from_millis(16), clock.clone());
+        assert!(limiter.should_render());
+        assert!(!limiter.should_render());
+        clock.advance(10);
+        assert!(!limiter.should_render());
+        clock.advance(6);
+        assert!(limiter.should_render());
+        clock.advance(15);
+        assert!(!limiter.should_render());
+        // interval counts from the last render
+        clock.advance(15);
+        assert!(limiter.should_render());
+    }
+
+    #[test]
+    fn force() {
+        let clock = TestClock::new();
+        let mut limiter = FrameLimiter::with_clock(Duration::from_millis(16), clock.clone());
+        assert!(limiter.should_render());
+        limiter.force();
+        assert!(limiter.should_render());
+        assert!(!limiter.should_render());
+        clock.advance(16);
+        limiter.force();
+        assert!(limiter.should_render());
+        assert!(!limiter.should_render());
+    }
+
+    #[test]
+    fn frame_guard() {
+        let clock = TestClock::new();
+        let mut limiter = FrameLimiter::with_clock(Duration::from_millis(16), clock.clone());
+        let mut backend = MockedBackend::init();
+        if let Some(mut guard) = limiter.frame(&mut backend) {
+            guard.go_to(1, 1);
+        }
+        assert!(limiter.frame(&mut backend).is_none());
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<freeze>>".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 1>>".to_owned()),
+                (MockedStyle::default(), "<<unfreeze>>".to_owned()),
+            ]
+        );
+    }
+}
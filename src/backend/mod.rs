@@ -1,41 +1,89 @@
+mod buffer;
 #[cfg(feature = "crossterm_backend")]
 mod crossterm_backend;
+mod diff;
+mod frame;
+mod stats;
 mod style;
+#[cfg(feature = "termion_backend")]
+mod termion_backend;
 use super::layout::Rect;
+pub use buffer::{BufferBackend, Cell};
 #[cfg(feature = "crossterm_backend")]
-pub use crossterm_backend::{background_rgb, parse_raw_rgb, pull_color, serialize_rgb, CrossTerm};
+pub use crossterm_backend::{
+    background_rgb, parse_raw_rgb, pull_color, serialize_rgb, ColorMode, CrossTerm,
+};
+pub use diff::DiffBackend;
+pub use frame::{Clock, FrameGuard, FrameLimiter, SystemClock};
+pub use stats::RenderStats;
 use std::{
     fmt::{Debug, Display},
     io::{Result, Write},
 };
-pub use style::StyleExt;
+pub use style::{StyleExt, StyleKey};
+#[cfg(feature = "termion_backend")]
+pub use termion_backend::{Termion, TermionAttributes, TermionColor, TermionStyle};
 
 pub const ERR_MSG: &str = "Rendering (Stdout) Err:";
 
-/// If stdout is returning errors the program should crash -> use expect
-// impl all utilities although not all are used
-pub trait Backend: Write + Sized + Debug + PartialEq + Default {
+/// Backend is a blanket supertrait - `B: Backend` bounds bring the capability methods in scope,
+/// on concrete backends the traits have to be imported: `use idiom_tui::backend::prelude::*;`
+pub mod prelude {
+    pub use super::{Backend, CursorControl, ScreenControl, StyleControl, StyleExt, TextPrint};
+}
+
+/// Printing text at the current cursor location - the only trait required for pure rendering
+pub trait TextPrint {
     type Style: Sized + PartialEq + Debug + Clone;
-    type Color: Sized + PartialEq + Debug + Clone;
 
-    fn init() -> Self;
-    fn exit() -> std::io::Result<()>;
-    /// get whole screen as rect
-    fn screen() -> Result<Rect>;
-    /// stop updates allowing to build buffer
-    fn freeze(&mut self);
-    /// restore updates allowing to render buffer
-    fn unfreeze(&mut self);
-    fn flush_buf(&mut self);
-    /// clears from cursor until the End Of Line
-    fn clear_to_eol(&mut self);
-    /// clears current cursor line
-    fn clear_line(&mut self);
-    fn clear_all(&mut self);
+    /// print text at current location - default styling
+    fn print<D: Display>(&mut self, text: D);
+    /// prints styled text without affecting the writer set style
+    fn print_styled<D: Display>(&mut self, text: D, style: Self::Style);
+    /// padding with empty space
+    fn pad(&mut self, width: usize) {
+        self.print(format!("{:width$}", ""));
+    }
+    /// padding with empty space styled
+    fn pad_styled(&mut self, width: usize, style: Self::Style) {
+        self.print_styled(format!("{:width$}", ""), style);
+    }
+    /// render counters for the current frame (reset on freeze) if the backend collects them
+    fn stats(&self) -> Option<&RenderStats> {
+        None
+    }
+}
+
+/// Cursor movement and positioned printing
+pub trait CursorControl: TextPrint {
+    /// sends the cursor to location
+    fn go_to(&mut self, row: u16, col: u16);
+    /// goes to location and prints text
+    fn print_at<D: Display>(&mut self, row: u16, col: u16, text: D) {
+        self.go_to(row, col);
+        self.print(text);
+    }
+    /// goes to location and prints styled text without affecting the writer set style
+    fn print_styled_at<D: Display>(&mut self, row: u16, col: u16, text: D, style: Self::Style) {
+        self.go_to(row, col);
+        self.print_styled(text, style);
+    }
     /// stores the cursor
     fn save_cursor(&mut self);
     /// restores cursor position
     fn restore_cursor(&mut self);
+    /// direct adding cursor at location - no buffer queing
+    fn render_cursor_at(&mut self, row: u16, col: u16);
+    /// direct showing cursor - no buffer queing
+    fn show_cursor(&mut self);
+    /// direct hiding cursor - no buffer queing
+    fn hide_cursor(&mut self);
+}
+
+/// Writer style state and style constructors
+pub trait StyleControl: TextPrint {
+    type Color: Sized + PartialEq + Debug + Clone;
+
     /// sets the style for the print/print at
     fn set_style(&mut self, style: Self::Style);
     fn get_style(&mut self) -> Self::Style;
@@ -49,30 +97,14 @@ pub trait Backend: Write + Sized + Debug + PartialEq + Default {
     fn set_bg(&mut self, color: Option<Self::Color>);
     /// restores the style of the writer to default
     fn reset_style(&mut self);
-    /// sends the cursor to location
-    fn go_to(&mut self, row: u16, col: u16);
-    /// direct adding cursor at location - no buffer queing
-    fn render_cursor_at(&mut self, row: u16, col: u16);
-    /// direct showing cursor - no buffer queing
-    fn show_cursor(&mut self);
-    /// direct hiding cursor - no buffer queing
-    fn hide_cursor(&mut self);
-    /// print text at current location - default styling
-    fn print<D: Display>(&mut self, text: D);
-    /// goes to location and prints text
-    fn print_at<D: Display>(&mut self, row: u16, col: u16, text: D);
-    /// prints styled text without affecting the writer set style
-    fn print_styled<D: Display>(&mut self, text: D, style: Self::Style);
-    /// goes to location and prints styled text without affecting the writer set style
-    fn print_styled_at<D: Display>(&mut self, row: u16, col: u16, text: D, style: Self::Style);
-    /// padding with empty space
-    fn pad(&mut self, width: usize);
-    /// padding with empty space styled
-    fn pad_styled(&mut self, width: usize, style: Self::Style);
     /// merge styles
     fn merge_style(left: Self::Style, right: Self::Style) -> Self::Style;
     /// Self::Style with revers attr
     fn reversed_style() -> Self::Style;
+    /// Self::Style with dim attr (ital by default for backends without dim support)
+    fn dim_style() -> Self::Style {
+        Self::ital_style()
+    }
     /// Self::Style with bold attr
     fn bold_style() -> Self::Style;
     /// Self::Style with ital attr
@@ -89,6 +121,61 @@ pub trait Backend: Write + Sized + Debug + PartialEq + Default {
     fn bg_style(color: Self::Color) -> Self::Style;
 }
 
+/// Terminal lifecycle, buffering and clearing
+pub trait ScreenControl: CursorControl {
+    fn init() -> Self;
+    fn exit() -> std::io::Result<()>;
+    /// get whole screen as rect
+    fn screen() -> Result<Rect>;
+    /// stop updates allowing to build buffer
+    fn freeze(&mut self);
+    /// restore updates allowing to render buffer
+    fn unfreeze(&mut self);
+    fn flush_buf(&mut self);
+    /// clears from cursor until the End Of Line
+    fn clear_to_eol(&mut self);
+    /// clears current cursor line
+    fn clear_line(&mut self);
+    fn clear_all(&mut self);
+    /// audible attention signal
+    fn bell(&mut self) {}
+    /// paints flash frame over the rect (no waiting) - the caller should re-render the rect on next frame
+    fn visual_bell(&mut self, rect: Rect, style: Self::Style) {
+        for line in rect {
+            self.go_to(line.row, line.col);
+            self.pad_styled(line.width, style.clone());
+        }
+    }
+}
+
+/// If stdout is returning errors the program should crash -> use expect
+/// Implemented for every type providing all capabilities
+pub trait Backend:
+    Write
+    + Sized
+    + Debug
+    + PartialEq
+    + Default
+    + TextPrint
+    + CursorControl
+    + StyleControl
+    + ScreenControl
+{
+}
+
+impl<T> Backend for T where
+    T: Write
+        + Sized
+        + Debug
+        + PartialEq
+        + Default
+        + TextPrint
+        + CursorControl
+        + StyleControl
+        + ScreenControl
+{
+}
+
 #[cfg(test)]
 mod test;
 
@@ -0,0 +1,17 @@
+/// Counters of rendering calls since the last freeze (start of frame)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RenderStats {
+    pub prints: usize,
+    pub styled_prints: usize,
+    pub go_tos: usize,
+    pub padded_cells: usize,
+    /// bytes emitted by the backend
+    pub bytes: usize,
+}
+
+impl RenderStats {
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
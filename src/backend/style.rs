@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, hash::Hasher};
 
 #[allow(dead_code)]
 pub trait StyleExt: Sized + PartialEq + Debug {
@@ -22,9 +22,56 @@ pub trait StyleExt: Sized + PartialEq + Debug {
     fn ital() -> Self;
     fn add_reverse(&mut self);
     fn reversed() -> Self;
+    /// no-op by default - styles without dim attr render the text as is
+    fn add_dim(&mut self) {}
+    fn dim() -> Self {
+        // reversed is the only constructor without colors / args
+        let mut style = Self::reversed();
+        style.reset_mods();
+        style.add_dim();
+        style
+    }
     fn reset_mods(&mut self);
     fn undercurle(&mut self, color: Option<Self::Color>);
     fn undercurled(color: Option<Self::Color>) -> Self;
     fn underline(&mut self, color: Option<Self::Color>);
     fn underlined(color: Option<Self::Color>) -> Self;
 }
+
+/// Stable hashing of backend style - allows Hash on Text/StyledLine for backends with non Hash styles
+pub trait StyleKey {
+    fn hash_key<H: Hasher>(&self, state: &mut H);
+}
+
+impl StyleKey for () {
+    fn hash_key<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// no styling - used by text only BufferBackend
+impl StyleExt for () {
+    type Color = ();
+    type Attribute = ();
+    fn update(&mut self, _: Self) {}
+    fn set_attr(&mut self, _: ()) {}
+    fn unset_attr(&mut self, _: ()) {}
+    fn with_fg(self, _: ()) {}
+    fn set_fg(&mut self, _: Option<()>) {}
+    fn fg(_: ()) {}
+    fn with_bg(self, _: ()) {}
+    fn set_bg(&mut self, _: Option<()>) {}
+    fn bg(_: ()) {}
+    fn drop_bg(&mut self) {}
+    fn add_slowblink(&mut self) {}
+    fn slowblink() {}
+    fn add_bold(&mut self) {}
+    fn bold() {}
+    fn add_ital(&mut self) {}
+    fn ital() {}
+    fn add_reverse(&mut self) {}
+    fn reversed() {}
+    fn reset_mods(&mut self) {}
+    fn undercurle(&mut self, _: Option<()>) {}
+    fn undercurled(_: Option<()>) {}
+    fn underline(&mut self, _: Option<()>) {}
+    fn underlined(_: Option<()>) {}
+}
@@ -0,0 +1,809 @@
+use super::{
+    style::{StyleExt, StyleKey},
+    ERR_MSG,
+};
+use bitflags::bitflags;
+use std::{
+    fmt::{Debug, Display},
+    io::{Stdout, Write},
+};
+use termion::{
+    clear, color, cursor,
+    raw::{IntoRawMode, RawTerminal},
+    screen::{AlternateScreen, IntoAlternateScreen, ToMainScreen},
+    style,
+};
+
+use super::super::layout::Rect;
+
+use super::{CursorControl, RenderStats, ScreenControl, StyleControl, TextPrint};
+
+const BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026h";
+const END_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026l";
+const DISABLE_LINE_WRAP: &str = "\x1b[?7l";
+const ENABLE_LINE_WRAP: &str = "\x1b[?7h";
+const ENABLE_MOUSE_CAPTURE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+const DISABLE_MOUSE_CAPTURE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+const ENABLE_BRACKETED_PASTE: &str = "\x1b[?2004h";
+const DISABLE_BRACKETED_PASTE: &str = "\x1b[?2004l";
+
+/// Termion implementation of the backend - drop in replacement for CrossTerm (unix only)
+/// Raw mode and alternate screen are held by the writer and restored when the backend is dropped
+pub struct Termion {
+    writer: AlternateScreen<RawTerminal<Stdout>>,
+    default_styled: Option<TermionStyle>,
+    stats: RenderStats,
+}
+
+impl Debug for Termion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Termion")
+            .field("default_styled", &self.default_styled)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl Default for Termion {
+    fn default() -> Self {
+        Self::init()
+    }
+}
+
+impl PartialEq for Termion {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Write for Termion {
+    #[inline(always)]
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.stats.bytes += written;
+        Ok(written)
+    }
+
+    #[inline(always)]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(buf)?;
+        self.stats.bytes += buf.len();
+        Ok(())
+    }
+}
+
+impl Termion {
+    pub fn detached_hide_cursor() {
+        write!(std::io::stdout(), "{}", cursor::Hide).expect(ERR_MSG);
+    }
+
+    pub fn detached_show_cursor() {
+        write!(std::io::stdout(), "{}", cursor::Show).expect(ERR_MSG);
+    }
+
+    /// writes text in style and restores the writer set style
+    #[inline]
+    fn write_styled<D: Display>(&mut self, text: D, style: TermionStyle) {
+        match self.default_styled {
+            Some(restore_style) => {
+                write!(self, "{style}{text}{}{restore_style}", style::Reset)
+            }
+            None => write!(self, "{style}{text}{}", style::Reset),
+        }
+        .expect(ERR_MSG);
+    }
+}
+
+impl TextPrint for Termion {
+    type Style = TermionStyle;
+
+    #[inline]
+    fn print<D: Display>(&mut self, text: D) {
+        self.stats.prints += 1;
+        write!(self, "{text}").expect(ERR_MSG);
+    }
+
+    /// prints styled text without affecting the writer set style
+    #[inline]
+    fn print_styled<D: Display>(&mut self, text: D, style: TermionStyle) {
+        self.stats.styled_prints += 1;
+        self.write_styled(text, style);
+    }
+
+    #[inline]
+    fn pad(&mut self, width: usize) {
+        self.stats.padded_cells += width;
+        write!(self, "{:width$}", "").expect(ERR_MSG);
+    }
+
+    #[inline]
+    fn pad_styled(&mut self, width: usize, style: TermionStyle) {
+        self.stats.padded_cells += width;
+        self.write_styled(format!("{:width$}", ""), style);
+    }
+
+    #[inline]
+    fn stats(&self) -> Option<&RenderStats> {
+        Some(&self.stats)
+    }
+}
+
+impl CursorControl for Termion {
+    /// stores the cursor
+    #[inline]
+    fn save_cursor(&mut self) {
+        write!(self, "{}", cursor::Save).expect(ERR_MSG);
+        self.flush().expect(ERR_MSG);
+    }
+
+    /// restores cursor position
+    #[inline]
+    fn restore_cursor(&mut self) {
+        write!(self, "{}", cursor::Restore).expect(ERR_MSG);
+    }
+
+    /// sends the cursor to location (termion is 1-based)
+    #[inline]
+    fn go_to(&mut self, row: u16, col: u16) {
+        self.stats.go_tos += 1;
+        write!(self, "{}", cursor::Goto(col + 1, row + 1)).expect(ERR_MSG);
+    }
+
+    /// direct adding cursor at location - no buffer queing
+    #[inline]
+    fn render_cursor_at(&mut self, row: u16, col: u16) {
+        write!(self, "{}{}", cursor::Goto(col + 1, row + 1), cursor::Show).expect(ERR_MSG);
+    }
+
+    /// direct showing cursor - no buffer queing
+    #[inline]
+    fn show_cursor(&mut self) {
+        write!(self, "{}", cursor::Show).expect(ERR_MSG);
+    }
+
+    /// direct hiding cursor - no buffer queing
+    #[inline]
+    fn hide_cursor(&mut self) {
+        write!(self, "{}", cursor::Hide).expect(ERR_MSG);
+    }
+}
+
+impl StyleControl for Termion {
+    type Color = TermionColor;
+
+    /// sets the style for the print/print at
+    #[inline]
+    fn set_style(&mut self, style: TermionStyle) {
+        self.default_styled.replace(style);
+        write!(self, "{}{style}", style::Reset).expect(ERR_MSG);
+    }
+
+    #[inline]
+    fn get_style(&mut self) -> TermionStyle {
+        self.default_styled.unwrap_or_default()
+    }
+
+    #[inline]
+    fn to_set_style(&mut self) {
+        match self.default_styled {
+            Some(style) => write!(self, "{}{style}", style::Reset),
+            None => write!(self, "{}", style::Reset),
+        }
+        .expect(ERR_MSG);
+    }
+
+    /// update existing style if exists otherwise sets it to the new one
+    /// mods will be taken from updating and will replace fg and bg if present
+    #[inline]
+    fn update_style(&mut self, style: TermionStyle) {
+        if let Some(current) = self.default_styled.as_mut() {
+            current.update(style);
+        } else {
+            self.default_styled.replace(style);
+        };
+        self.to_set_style();
+    }
+
+    /// adds foreground to the already set style
+    #[inline]
+    fn set_fg(&mut self, color: Option<TermionColor>) {
+        if let Some(current) = self.default_styled.as_mut() {
+            current.set_fg(color);
+        } else if let Some(color) = color {
+            self.default_styled.replace(TermionStyle::fg(color));
+        };
+        self.to_set_style()
+    }
+
+    /// adds background to the already set style
+    #[inline]
+    fn set_bg(&mut self, color: Option<TermionColor>) {
+        if let Some(current) = self.default_styled.as_mut() {
+            current.set_bg(color);
+        } else if let Some(color) = color {
+            self.default_styled.replace(TermionStyle::bg(color));
+        }
+        self.to_set_style();
+    }
+
+    /// restores the style of the writer to default
+    #[inline]
+    fn reset_style(&mut self) {
+        self.default_styled = None;
+        write!(self, "{}", style::Reset).expect(ERR_MSG);
+    }
+
+    #[inline]
+    fn merge_style(mut left: TermionStyle, right: TermionStyle) -> TermionStyle {
+        left.update(right);
+        left
+    }
+
+    #[inline]
+    fn reversed_style() -> Self::Style {
+        Self::Style::reversed()
+    }
+
+    #[inline]
+    fn dim_style() -> Self::Style {
+        Self::Style::dim()
+    }
+
+    #[inline]
+    fn bold_style() -> Self::Style {
+        Self::Style::bold()
+    }
+
+    #[inline]
+    fn slow_blink_style() -> Self::Style {
+        Self::Style::slowblink()
+    }
+
+    #[inline]
+    fn ital_style() -> Self::Style {
+        Self::Style::ital()
+    }
+
+    #[inline]
+    fn undercurle_style(color: Option<Self::Color>) -> Self::Style {
+        Self::Style::undercurled(color)
+    }
+
+    #[inline]
+    fn underline_style(color: Option<Self::Color>) -> Self::Style {
+        Self::Style::underlined(color)
+    }
+
+    fn fg_style(color: Self::Color) -> Self::Style {
+        Self::Style::fg(color)
+    }
+
+    fn bg_style(color: Self::Color) -> Self::Style {
+        Self::Style::bg(color)
+    }
+}
+
+impl ScreenControl for Termion {
+    #[inline]
+    fn init() -> Self {
+        let writer = init_terminal().expect(ERR_MSG);
+        Self {
+            writer,
+            default_styled: None,
+            stats: RenderStats::default(),
+        }
+    }
+
+    /// restores terminal modes - raw mode is restored when the writer is dropped
+    #[inline]
+    fn exit() -> std::io::Result<()> {
+        graceful_exit()
+    }
+
+    /// get whole screen as rect
+    #[inline]
+    fn screen() -> std::io::Result<Rect> {
+        termion::terminal_size().map(Rect::from)
+    }
+
+    /// freeze screen allowing to build buffer
+    #[inline]
+    fn freeze(&mut self) {
+        self.stats.reset();
+        self.write_all(BEGIN_SYNCHRONIZED_UPDATE.as_bytes())
+            .expect(ERR_MSG);
+        self.flush().expect(ERR_MSG);
+    }
+
+    /// unfreeze allowing the buffer to render
+    #[inline]
+    fn unfreeze(&mut self) {
+        self.write_all(END_SYNCHRONIZED_UPDATE.as_bytes())
+            .expect(ERR_MSG);
+        self.flush().expect(ERR_MSG);
+    }
+
+    /// flushs buffer with panic on error
+    #[inline]
+    fn flush_buf(&mut self) {
+        self.writer.flush().expect(ERR_MSG);
+    }
+
+    /// clears from cursor until the End Of Line
+    #[inline]
+    fn clear_to_eol(&mut self) {
+        write!(self, "{}", clear::UntilNewline).expect(ERR_MSG);
+    }
+
+    /// clears current cursor line
+    #[inline]
+    fn clear_line(&mut self) {
+        write!(self, "{}", clear::CurrentLine).expect(ERR_MSG);
+    }
+
+    #[inline]
+    fn clear_all(&mut self) {
+        write!(self, "{}", clear::All).expect(ERR_MSG);
+    }
+
+    /// emits BEL char
+    #[inline]
+    fn bell(&mut self) {
+        write!(self, "\x07").expect(ERR_MSG);
+    }
+}
+
+impl Drop for Termion {
+    fn drop(&mut self) {
+        let _ = self.flush();
+        let _ = Termion::exit();
+    }
+}
+
+fn init_terminal() -> std::io::Result<AlternateScreen<RawTerminal<Stdout>>> {
+    // Ensures panics are retported
+    std::panic::set_hook(Box::new(|info| {
+        let _ = graceful_exit();
+        eprintln!("{info}");
+    }));
+    // Init terminal
+    let mut writer = std::io::stdout().into_raw_mode()?.into_alternate_screen()?;
+    write!(
+        writer,
+        "{DISABLE_LINE_WRAP}{}{ENABLE_MOUSE_CAPTURE}{ENABLE_BRACKETED_PASTE}{}",
+        style::Reset,
+        cursor::Hide,
+    )?;
+    writer.flush()?;
+    Ok(writer)
+}
+
+fn graceful_exit() -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(
+        stdout,
+        "{DISABLE_MOUSE_CAPTURE}{DISABLE_BRACKETED_PASTE}{ToMainScreen}{ENABLE_LINE_WRAP}{}{}",
+        style::Reset,
+        cursor::Show,
+    )?;
+    stdout.flush()
+}
+
+/// termion has a type per color - the enum is mapped to the matching termion color on print
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TermionColor {
+    #[default]
+    Reset,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    LightBlack,
+    LightRed,
+    LightGreen,
+    LightYellow,
+    LightBlue,
+    LightMagenta,
+    LightCyan,
+    LightWhite,
+    AnsiValue(u8),
+    Rgb {
+        r: u8,
+        g: u8,
+        b: u8,
+    },
+}
+
+impl TermionColor {
+    fn as_termion(&self) -> &dyn color::Color {
+        match self {
+            Self::Reset => &color::Reset,
+            Self::Black => &color::Black,
+            Self::Red => &color::Red,
+            Self::Green => &color::Green,
+            Self::Yellow => &color::Yellow,
+            Self::Blue => &color::Blue,
+            Self::Magenta => &color::Magenta,
+            Self::Cyan => &color::Cyan,
+            Self::White => &color::White,
+            Self::LightBlack => &color::LightBlack,
+            Self::LightRed => &color::LightRed,
+            Self::LightGreen => &color::LightGreen,
+            Self::LightYellow => &color::LightYellow,
+            Self::LightBlue => &color::LightBlue,
+            Self::LightMagenta => &color::LightMagenta,
+            Self::LightCyan => &color::LightCyan,
+            Self::LightWhite => &color::LightWhite,
+            Self::AnsiValue(..) | Self::Rgb { .. } => &color::Reset,
+        }
+    }
+
+    fn write_fg(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::AnsiValue(value) => write!(f, "{}", color::Fg(color::AnsiValue(value))),
+            Self::Rgb { r, g, b } => write!(f, "{}", color::Fg(color::Rgb(r, g, b))),
+            _ => write!(f, "{}", color::Fg(self.as_termion())),
+        }
+    }
+
+    fn write_bg(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::AnsiValue(value) => write!(f, "{}", color::Bg(color::AnsiValue(value))),
+            Self::Rgb { r, g, b } => write!(f, "{}", color::Bg(color::Rgb(r, g, b))),
+            _ => write!(f, "{}", color::Bg(self.as_termion())),
+        }
+    }
+
+    /// termion has no underline color - written as raw SGR 58 / 59
+    fn write_underline(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Reset => f.write_str("\x1b[59m"),
+            Self::AnsiValue(value) => write!(f, "\x1b[58;5;{value}m"),
+            Self::Rgb { r, g, b } => write!(f, "\x1b[58;2;{r};{g};{b}m"),
+            named => write!(f, "\x1b[58;5;{}m", named.ansi_index()),
+        }
+    }
+
+    fn ansi_index(&self) -> u8 {
+        match self {
+            Self::Reset | Self::Black => 0,
+            Self::Red => 1,
+            Self::Green => 2,
+            Self::Yellow => 3,
+            Self::Blue => 4,
+            Self::Magenta => 5,
+            Self::Cyan => 6,
+            Self::White => 7,
+            Self::LightBlack => 8,
+            Self::LightRed => 9,
+            Self::LightGreen => 10,
+            Self::LightYellow => 11,
+            Self::LightBlue => 12,
+            Self::LightMagenta => 13,
+            Self::LightCyan => 14,
+            Self::LightWhite => 15,
+            Self::AnsiValue(value) => *value,
+            Self::Rgb { .. } => 0,
+        }
+    }
+}
+
+bitflags! {
+    /// Text attributes of TermionStyle
+    #[derive(Default, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+    pub struct TermionAttributes: u8 {
+        const BOLD        = 0b0000_0001;
+        const DIM         = 0b0000_0010;
+        const ITALIC      = 0b0000_0100;
+        const UNDERLINED  = 0b0000_1000;
+        const UNDERCURLED = 0b0001_0000;
+        const SLOW_BLINK  = 0b0010_0000;
+        const REVERSE     = 0b0100_0000;
+    }
+}
+
+/// Style for Termion backend - written as SGR sequences (fg, bg, attributes, underline color)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TermionStyle {
+    pub foreground_color: Option<TermionColor>,
+    pub background_color: Option<TermionColor>,
+    pub underline_color: Option<TermionColor>,
+    pub attributes: TermionAttributes,
+}
+
+impl Display for TermionStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(color) = self.foreground_color {
+            color.write_fg(f)?;
+        }
+        if let Some(color) = self.background_color {
+            color.write_bg(f)?;
+        }
+        if self.attributes.contains(TermionAttributes::BOLD) {
+            write!(f, "{}", style::Bold)?;
+        }
+        if self.attributes.contains(TermionAttributes::DIM) {
+            write!(f, "{}", style::Faint)?;
+        }
+        if self.attributes.contains(TermionAttributes::ITALIC) {
+            write!(f, "{}", style::Italic)?;
+        }
+        if self.attributes.contains(TermionAttributes::UNDERLINED) {
+            write!(f, "{}", style::Underline)?;
+        }
+        if self.attributes.contains(TermionAttributes::UNDERCURLED) {
+            // termion has no undercurl
+            f.write_str("\x1b[4:3m")?;
+        }
+        if self.attributes.contains(TermionAttributes::SLOW_BLINK) {
+            write!(f, "{}", style::Blink)?;
+        }
+        if self.attributes.contains(TermionAttributes::REVERSE) {
+            write!(f, "{}", style::Invert)?;
+        }
+        if let Some(color) = self.underline_color {
+            color.write_underline(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl StyleKey for TermionStyle {
+    fn hash_key<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(self, state);
+    }
+}
+
+impl TermionStyle {
+    #[inline]
+    fn with_attributes(attributes: TermionAttributes) -> Self {
+        Self {
+            attributes,
+            ..Default::default()
+        }
+    }
+}
+
+impl StyleExt for TermionStyle {
+    type Attribute = TermionAttributes;
+    type Color = TermionColor;
+
+    #[inline]
+    fn update(&mut self, rhs: Self) {
+        if let Some(c) = rhs.foreground_color {
+            self.foreground_color.replace(c);
+        }
+        if let Some(c) = rhs.background_color {
+            self.background_color.replace(c);
+        }
+        if let Some(c) = rhs.underline_color {
+            self.underline_color.replace(c);
+        }
+        self.attributes = rhs.attributes;
+    }
+
+    fn set_attr(&mut self, attr: TermionAttributes) {
+        self.attributes.insert(attr);
+    }
+
+    fn unset_attr(&mut self, attr: TermionAttributes) {
+        self.attributes.remove(attr);
+    }
+
+    #[inline]
+    fn with_fg(mut self, color: TermionColor) -> Self {
+        self.foreground_color = Some(color);
+        self
+    }
+
+    #[inline]
+    fn set_fg(&mut self, color: Option<TermionColor>) {
+        self.foreground_color = color;
+    }
+
+    #[inline]
+    fn fg(color: TermionColor) -> Self {
+        Self {
+            foreground_color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    fn with_bg(mut self, color: TermionColor) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    #[inline]
+    fn set_bg(&mut self, color: Option<TermionColor>) {
+        self.background_color = color;
+    }
+
+    #[inline]
+    fn bg(color: TermionColor) -> Self {
+        Self {
+            background_color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    fn drop_bg(&mut self) {
+        self.background_color = None;
+    }
+
+    #[inline]
+    fn add_slowblink(&mut self) {
+        self.attributes.insert(TermionAttributes::SLOW_BLINK);
+    }
+
+    #[inline]
+    fn slowblink() -> Self {
+        Self::with_attributes(TermionAttributes::SLOW_BLINK)
+    }
+
+    #[inline]
+    fn add_bold(&mut self) {
+        self.attributes.insert(TermionAttributes::BOLD);
+    }
+
+    #[inline]
+    fn bold() -> Self {
+        Self::with_attributes(TermionAttributes::BOLD)
+    }
+
+    #[inline]
+    fn add_ital(&mut self) {
+        self.attributes.insert(TermionAttributes::ITALIC);
+    }
+
+    #[inline]
+    fn ital() -> Self {
+        Self::with_attributes(TermionAttributes::ITALIC)
+    }
+
+    #[inline]
+    fn add_reverse(&mut self) {
+        self.attributes.insert(TermionAttributes::REVERSE);
+    }
+
+    #[inline]
+    fn reversed() -> Self {
+        Self::with_attributes(TermionAttributes::REVERSE)
+    }
+
+    #[inline]
+    fn add_dim(&mut self) {
+        self.attributes.insert(TermionAttributes::DIM);
+    }
+
+    #[inline]
+    fn dim() -> Self {
+        Self::with_attributes(TermionAttributes::DIM)
+    }
+
+    #[inline]
+    fn reset_mods(&mut self) {
+        self.attributes = TermionAttributes::default();
+        self.underline_color = None;
+    }
+
+    #[inline]
+    fn undercurle(&mut self, color: Option<TermionColor>) {
+        self.attributes.insert(TermionAttributes::UNDERCURLED);
+        self.underline_color = color;
+    }
+
+    #[inline]
+    fn undercurled(color: Option<TermionColor>) -> Self {
+        Self {
+            underline_color: color,
+            ..Self::with_attributes(TermionAttributes::UNDERCURLED)
+        }
+    }
+
+    #[inline]
+    fn underline(&mut self, color: Option<TermionColor>) {
+        self.attributes.insert(TermionAttributes::UNDERLINED);
+        self.underline_color = color;
+    }
+
+    #[inline]
+    fn underlined(color: Option<TermionColor>) -> Self {
+        Self {
+            underline_color: color,
+            ..Self::with_attributes(TermionAttributes::UNDERLINED)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Termion, TermionAttributes, TermionColor, TermionStyle};
+    use crate::backend::{StyleControl, StyleExt};
+
+    #[test]
+    fn style_constructors() {
+        assert_eq!(Termion::bold_style().attributes, TermionAttributes::BOLD);
+        assert_eq!(Termion::dim_style().attributes, TermionAttributes::DIM);
+        assert_eq!(Termion::ital_style().attributes, TermionAttributes::ITALIC);
+        assert_eq!(
+            Termion::reversed_style().attributes,
+            TermionAttributes::REVERSE
+        );
+        assert_eq!(
+            Termion::slow_blink_style().attributes,
+            TermionAttributes::SLOW_BLINK
+        );
+        let curled = Termion::undercurle_style(Some(TermionColor::Red));
+        assert_eq!(curled.attributes, TermionAttributes::UNDERCURLED);
+        assert_eq!(curled.underline_color, Some(TermionColor::Red));
+        let underlined = Termion::underline_style(None);
+        assert_eq!(underlined.attributes, TermionAttributes::UNDERLINED);
+        assert_eq!(underlined.underline_color, None);
+        assert_eq!(
+            Termion::fg_style(TermionColor::Blue).foreground_color,
+            Some(TermionColor::Blue)
+        );
+        assert_eq!(
+            Termion::bg_style(TermionColor::AnsiValue(42)).background_color,
+            Some(TermionColor::AnsiValue(42))
+        );
+    }
+
+    #[test]
+    fn merge_style() {
+        let left = TermionStyle::fg(TermionColor::Red).with_bg(TermionColor::Black);
+        let merged = Termion::merge_style(left, TermionStyle::bold().with_fg(TermionColor::Green));
+        assert_eq!(merged.foreground_color, Some(TermionColor::Green));
+        assert_eq!(merged.background_color, Some(TermionColor::Black));
+        assert_eq!(merged.attributes, TermionAttributes::BOLD);
+
+        let mut style = merged;
+        style.add_ital();
+        style.undercurle(Some(TermionColor::Rgb { r: 1, g: 2, b: 3 }));
+        style.reset_mods();
+        assert_eq!(style.attributes, TermionAttributes::empty());
+        assert_eq!(style.underline_color, None);
+        style.drop_bg();
+        assert_eq!(style, TermionStyle::fg(TermionColor::Green));
+    }
+
+    #[test]
+    fn style_sequences() {
+        assert_eq!(TermionStyle::default().to_string(), "");
+        assert_eq!(
+            TermionStyle::fg(TermionColor::Red).to_string(),
+            "\x1b[38;5;1m"
+        );
+        assert_eq!(
+            TermionStyle::bg(TermionColor::Rgb { r: 1, g: 2, b: 3 }).to_string(),
+            "\x1b[48;2;1;2;3m"
+        );
+        let mut style = TermionStyle::bold().with_fg(TermionColor::AnsiValue(200));
+        style.add_reverse();
+        assert_eq!(style.to_string(), "\x1b[38;5;200m\x1b[1m\x1b[7m");
+        assert_eq!(
+            TermionStyle::undercurled(Some(TermionColor::LightBlue)).to_string(),
+            "\x1b[4:3m\x1b[58;5;12m"
+        );
+    }
+}
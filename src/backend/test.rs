@@ -1,14 +1,24 @@
 use std::io::Write;
 
-use super::{style::StyleExt, Backend};
+use super::{
+    style::{StyleExt, StyleKey},
+    CursorControl, RenderStats, ScreenControl, StyleControl, TextPrint,
+};
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MockedStyle {
     fg: Option<usize>,
     bg: Option<usize>,
+    underline_color: Option<usize>,
     attrs: Vec<isize>,
 }
 
+impl StyleKey for MockedStyle {
+    fn hash_key<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(self, state);
+    }
+}
+
 impl StyleExt for MockedStyle {
     type Attribute = isize;
     type Color = usize;
@@ -63,6 +73,7 @@ impl StyleExt for MockedStyle {
 
     fn reset_mods(&mut self) {
         self.attrs.clear();
+        self.underline_color = None;
     }
 
     fn reversed() -> Self {
@@ -72,6 +83,17 @@ impl StyleExt for MockedStyle {
         }
     }
 
+    fn add_dim(&mut self) {
+        self.attrs.push(7);
+    }
+
+    fn dim() -> Self {
+        Self {
+            attrs: vec![7],
+            ..Default::default()
+        }
+    }
+
     fn set_attr(&mut self, attr: Self::Attribute) {
         self.attrs.push(attr);
     }
@@ -91,24 +113,28 @@ impl StyleExt for MockedStyle {
         }
     }
 
-    fn undercurle(&mut self, _: Option<Self::Color>) {
+    fn undercurle(&mut self, color: Option<Self::Color>) {
         self.attrs.push(5);
+        self.underline_color = color;
     }
 
-    fn undercurled(_: Option<Self::Color>) -> Self {
+    fn undercurled(color: Option<Self::Color>) -> Self {
         Self {
             attrs: vec![5],
+            underline_color: color,
             ..Default::default()
         }
     }
 
-    fn underline(&mut self, _: Option<Self::Color>) {
+    fn underline(&mut self, color: Option<Self::Color>) {
         self.attrs.push(6);
+        self.underline_color = color;
     }
 
-    fn underlined(_: Option<Self::Color>) -> Self {
+    fn underlined(color: Option<Self::Color>) -> Self {
         Self {
             attrs: vec![6],
+            underline_color: color,
             ..Default::default()
         }
     }
@@ -118,8 +144,15 @@ impl StyleExt for MockedStyle {
     }
 
     fn update(&mut self, rhs: Self) {
-        self.bg = rhs.bg;
-        self.fg = rhs.fg;
+        if rhs.bg.is_some() {
+            self.bg = rhs.bg;
+        }
+        if rhs.fg.is_some() {
+            self.fg = rhs.fg;
+        }
+        if rhs.underline_color.is_some() {
+            self.underline_color = rhs.underline_color;
+        }
         self.attrs.extend(rhs.attrs);
     }
 
@@ -142,6 +175,7 @@ impl StyleExt for MockedStyle {
 pub struct MockedBackend {
     pub data: Vec<(MockedStyle, String)>,
     pub default_style: MockedStyle,
+    stats: RenderStats,
 }
 
 impl MockedBackend {
@@ -156,52 +190,49 @@ impl PartialEq for MockedBackend {
     }
 }
 
-impl Backend for MockedBackend {
+impl TextPrint for MockedBackend {
     type Style = MockedStyle;
-    type Color = usize;
-
-    fn init() -> Self {
-        Self {
-            data: Vec::new(),
-            default_style: MockedStyle::default(),
-        }
-    }
 
-    fn exit() -> std::io::Result<()> {
-        Ok(())
+    fn print<D: std::fmt::Display>(&mut self, text: D) {
+        let text = text.to_string();
+        self.stats.prints += 1;
+        self.stats.bytes += text.len();
+        self.data.push((self.default_style.clone(), text));
     }
 
-    fn freeze(&mut self) {
-        self.data
-            .push((MockedStyle::default(), String::from("<<freeze>>")));
+    fn print_styled<D: std::fmt::Display>(&mut self, text: D, style: Self::Style) {
+        let text = text.to_string();
+        self.stats.styled_prints += 1;
+        self.stats.bytes += text.len();
+        self.data.push((style, text));
     }
 
-    fn unfreeze(&mut self) {
-        self.data
-            .push((MockedStyle::default(), String::from("<<unfreeze>>")));
+    fn pad(&mut self, width: usize) {
+        self.stats.padded_cells += width;
+        self.stats.bytes += width;
+        self.data.push((
+            self.default_style.clone(),
+            format!("<<padding: {:?}>>", width),
+        ))
     }
 
-    /// force flush buffer if writing small amount of data
-    fn flush_buf(&mut self) {}
-
-    fn clear_all(&mut self) {
-        self.data
-            .push((MockedStyle::default(), String::from("<<clear all>>")));
-    }
-    fn clear_line(&mut self) {
-        self.data
-            .push((MockedStyle::default(), String::from("<<clear line>>")));
-    }
-    fn clear_to_eol(&mut self) {
-        self.data
-            .push((MockedStyle::default(), String::from("<<clear EOL>>")));
+    fn pad_styled(&mut self, width: usize, style: MockedStyle) {
+        self.stats.padded_cells += width;
+        self.stats.bytes += width;
+        self.data.push((
+            self.default_style.clone(),
+            format!("<<padding: {:?}, styled: {:?}>>", width, style),
+        ))
     }
 
-    fn get_style(&mut self) -> Self::Style {
-        self.default_style.clone()
+    fn stats(&self) -> Option<&RenderStats> {
+        Some(&self.stats)
     }
+}
 
+impl CursorControl for MockedBackend {
     fn go_to(&mut self, row: u16, col: u16) {
+        self.stats.go_tos += 1;
         self.data.push((
             MockedStyle::default(),
             format!("<<go to row: {row} col: {col}>>"),
@@ -210,30 +241,6 @@ impl Backend for MockedBackend {
 
     fn hide_cursor(&mut self) {}
 
-    fn print<D: std::fmt::Display>(&mut self, text: D) {
-        self.data
-            .push((self.default_style.clone(), text.to_string()));
-    }
-
-    fn print_at<D: std::fmt::Display>(&mut self, row: u16, col: u16, text: D) {
-        self.go_to(row, col);
-        self.print(text)
-    }
-    fn print_styled<D: std::fmt::Display>(&mut self, text: D, style: Self::Style) {
-        self.data.push((style, text.to_string()));
-    }
-
-    fn print_styled_at<D: std::fmt::Display>(
-        &mut self,
-        row: u16,
-        col: u16,
-        text: D,
-        style: MockedStyle,
-    ) {
-        self.go_to(row, col);
-        self.print_styled(text, style);
-    }
-
     fn render_cursor_at(&mut self, row: u16, col: u16) {
         self.data.push((
             self.default_style.clone(),
@@ -241,12 +248,6 @@ impl Backend for MockedBackend {
         ));
     }
 
-    fn reset_style(&mut self) {
-        self.default_style = MockedStyle::default();
-        self.data
-            .push((self.default_style.clone(), String::from("<<reset style>>")));
-    }
-
     fn restore_cursor(&mut self) {
         self.data.push((
             self.default_style.clone(),
@@ -259,8 +260,21 @@ impl Backend for MockedBackend {
             .push((self.default_style.clone(), String::from("<<saved cursor>>")));
     }
 
-    fn screen() -> std::io::Result<crate::layout::Rect> {
-        Ok(crate::layout::Rect::new(0, 0, 120, 60))
+    fn show_cursor(&mut self) {}
+    // self.data.push((self.default_style, String::from("<<show cursor>>")));
+}
+
+impl StyleControl for MockedBackend {
+    type Color = usize;
+
+    fn get_style(&mut self) -> Self::Style {
+        self.default_style.clone()
+    }
+
+    fn reset_style(&mut self) {
+        self.default_style = MockedStyle::default();
+        self.data
+            .push((self.default_style.clone(), String::from("<<reset style>>")));
     }
 
     fn set_bg(&mut self, color: Option<Self::Color>) {
@@ -285,9 +299,6 @@ impl Backend for MockedBackend {
             .push((self.default_style.clone(), "<<set style>>".to_string()))
     }
 
-    fn show_cursor(&mut self) {}
-    // self.data.push((self.default_style, String::from("<<show cursor>>")));
-
     fn to_set_style(&mut self) {
         self.data
             .push((self.default_style.clone(), String::from("<<set style>>")));
@@ -301,20 +312,6 @@ impl Backend for MockedBackend {
         ))
     }
 
-    fn pad(&mut self, width: usize) {
-        self.data.push((
-            self.default_style.clone(),
-            format!("<<padding: {:?}>>", width),
-        ))
-    }
-
-    fn pad_styled(&mut self, width: usize, style: MockedStyle) {
-        self.data.push((
-            self.default_style.clone(),
-            format!("<<padding: {:?}, styled: {:?}>>", width, style),
-        ))
-    }
-
     fn merge_style(mut left: Self::Style, right: Self::Style) -> Self::Style {
         left.update(right);
         left
@@ -324,6 +321,10 @@ impl Backend for MockedBackend {
         Self::Style::reversed()
     }
 
+    fn dim_style() -> Self::Style {
+        Self::Style::dim()
+    }
+
     fn bold_style() -> Self::Style {
         Self::Style::bold()
     }
@@ -353,6 +354,68 @@ impl Backend for MockedBackend {
     }
 }
 
+impl ScreenControl for MockedBackend {
+    fn init() -> Self {
+        Self {
+            data: Vec::new(),
+            default_style: MockedStyle::default(),
+            stats: RenderStats::default(),
+        }
+    }
+
+    fn exit() -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn freeze(&mut self) {
+        self.stats.reset();
+        self.data
+            .push((MockedStyle::default(), String::from("<<freeze>>")));
+    }
+
+    fn unfreeze(&mut self) {
+        self.data
+            .push((MockedStyle::default(), String::from("<<unfreeze>>")));
+    }
+
+    /// force flush buffer if writing small amount of data
+    fn flush_buf(&mut self) {}
+
+    fn clear_all(&mut self) {
+        self.data
+            .push((MockedStyle::default(), String::from("<<clear all>>")));
+    }
+
+    fn clear_line(&mut self) {
+        self.data
+            .push((MockedStyle::default(), String::from("<<clear line>>")));
+    }
+
+    fn clear_to_eol(&mut self) {
+        self.data
+            .push((MockedStyle::default(), String::from("<<clear EOL>>")));
+    }
+
+    fn screen() -> std::io::Result<crate::layout::Rect> {
+        Ok(crate::layout::Rect::new(0, 0, 120, 60))
+    }
+
+    fn bell(&mut self) {
+        self.data
+            .push((self.default_style.clone(), String::from("<<bell>>")));
+    }
+
+    fn visual_bell(&mut self, rect: crate::layout::Rect, style: MockedStyle) {
+        self.data.push((
+            style,
+            format!(
+                "<<visual bell row: {} col: {} width: {} height: {}>>",
+                rect.row, rect.col, rect.width, rect.height
+            ),
+        ));
+    }
+}
+
 impl Write for MockedBackend {
     fn by_ref(&mut self) -> &mut Self
     where
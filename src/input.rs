@@ -0,0 +1,130 @@
+use bitflags::bitflags;
+
+#[cfg(feature = "crossterm_backend")]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Backend agnostic key - covers the keys used by the widgets keymaps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Tab,
+    BackTab,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Left,
+    Right,
+    Up,
+    Down,
+    F(u8),
+}
+
+bitflags! {
+    /// Modifiers held with the Key
+    #[derive(Default, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+    pub struct KeyMod: u8 {
+        const SHIFT   = 0b0000_0001;
+        const CONTROL = 0b0000_0010;
+        const ALT     = 0b0000_0100;
+    }
+}
+
+/// Mouse click classified by the caller (timing of repeated clicks is not tracked)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClickKind {
+    Single,
+    Double,
+    Triple,
+    /// move with pressed button
+    Drag,
+}
+
+/// Backend agnostic mouse event (screen cell)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    pub kind: ClickKind,
+    pub row: u16,
+    pub column: u16,
+}
+
+/// converts crossterm key event - None for keys without Key equivalent
+#[cfg(feature = "crossterm_backend")]
+pub fn from_crossterm(event: KeyEvent) -> Option<(Key, KeyMod)> {
+    let key = match event.code {
+        KeyCode::Char(ch) => Key::Char(ch),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::F(n) => Key::F(n),
+        _ => return None,
+    };
+    let mut mods = KeyMod::empty();
+    mods.set(KeyMod::SHIFT, event.modifiers.contains(KeyModifiers::SHIFT));
+    mods.set(
+        KeyMod::CONTROL,
+        event.modifiers.contains(KeyModifiers::CONTROL),
+    );
+    mods.set(KeyMod::ALT, event.modifiers.contains(KeyModifiers::ALT));
+    Some((key, mods))
+}
+
+/// converts termion key (new line and tab chars are mapped to Enter / Tab)
+#[cfg(feature = "termion_backend")]
+pub fn from_termion(key: termion::event::Key) -> Option<(Key, KeyMod)> {
+    use termion::event::Key as TermionKey;
+    let none = KeyMod::empty();
+    Some(match key {
+        TermionKey::Char('\n') => (Key::Enter, none),
+        TermionKey::Char('\t') => (Key::Tab, none),
+        TermionKey::Char(ch) => (Key::Char(ch), none),
+        TermionKey::Alt(ch) => (Key::Char(ch), KeyMod::ALT),
+        TermionKey::Ctrl(ch) => (Key::Char(ch), KeyMod::CONTROL),
+        TermionKey::Esc => (Key::Esc, none),
+        TermionKey::BackTab => (Key::BackTab, KeyMod::SHIFT),
+        TermionKey::Backspace => (Key::Backspace, none),
+        TermionKey::Delete => (Key::Delete, none),
+        TermionKey::Insert => (Key::Insert, none),
+        TermionKey::Home => (Key::Home, none),
+        TermionKey::CtrlHome => (Key::Home, KeyMod::CONTROL),
+        TermionKey::End => (Key::End, none),
+        TermionKey::CtrlEnd => (Key::End, KeyMod::CONTROL),
+        TermionKey::PageUp => (Key::PageUp, none),
+        TermionKey::PageDown => (Key::PageDown, none),
+        TermionKey::Left => (Key::Left, none),
+        TermionKey::ShiftLeft => (Key::Left, KeyMod::SHIFT),
+        TermionKey::AltLeft => (Key::Left, KeyMod::ALT),
+        TermionKey::CtrlLeft => (Key::Left, KeyMod::CONTROL),
+        TermionKey::Right => (Key::Right, none),
+        TermionKey::ShiftRight => (Key::Right, KeyMod::SHIFT),
+        TermionKey::AltRight => (Key::Right, KeyMod::ALT),
+        TermionKey::CtrlRight => (Key::Right, KeyMod::CONTROL),
+        TermionKey::Up => (Key::Up, none),
+        TermionKey::ShiftUp => (Key::Up, KeyMod::SHIFT),
+        TermionKey::AltUp => (Key::Up, KeyMod::ALT),
+        TermionKey::CtrlUp => (Key::Up, KeyMod::CONTROL),
+        TermionKey::Down => (Key::Down, none),
+        TermionKey::ShiftDown => (Key::Down, KeyMod::SHIFT),
+        TermionKey::AltDown => (Key::Down, KeyMod::ALT),
+        TermionKey::CtrlDown => (Key::Down, KeyMod::CONTROL),
+        TermionKey::F(n) => (Key::F(n), none),
+        _ => return None,
+    })
+}
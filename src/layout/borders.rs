@@ -33,6 +33,29 @@ pub const THICK_BORDERS: BorderSet = BorderSet {
     horizontal_bot: '━',
 };
 
+pub const ROUNDED_BORDERS: BorderSet = BorderSet {
+    top_left_qorner: '╭',
+    top_right_qorner: '╮',
+    bot_left_qorner: '╰',
+    bot_right_qorner: '╯',
+    vertical_left: '│',
+    vertical_right: '│',
+    horizontal_top: '─',
+    horizontal_bot: '─',
+};
+
+/// for terminals / fonts without box drawing chars
+pub const ASCII_BORDERS: BorderSet = BorderSet {
+    top_left_qorner: '+',
+    top_right_qorner: '+',
+    bot_left_qorner: '+',
+    bot_right_qorner: '+',
+    vertical_left: '|',
+    vertical_right: '|',
+    horizontal_top: '-',
+    horizontal_bot: '-',
+};
+
 pub const HAVED_WIDE_BORDERS: BorderSet = BorderSet {
     top_left_qorner: '▛',
     top_right_qorner: '▜',
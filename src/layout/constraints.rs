@@ -0,0 +1,70 @@
+/// Size rule for a single cell of Rect::split_with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// fixed size - allocated first
+    Length(usize),
+    /// percentage of space left after fixed sizes
+    Percentage(u16),
+    /// takes at least the size and grows as Fill(1)
+    Min(usize),
+    /// weighted share of the leftover space
+    Fill(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// cells are placed next to each other (split width)
+    Horizontal,
+    /// cells are placed one under another (split height)
+    Vertical,
+}
+
+/// sizes of cells - the sum never exceeds total, the last growing cell (or the last cell if none grows)
+/// takes the remainder
+pub fn allocate(total: usize, constraints: &[Constraint]) -> Vec<usize> {
+    let mut sizes = vec![0; constraints.len()];
+    let mut remaining = total;
+    for (size, constraint) in sizes.iter_mut().zip(constraints) {
+        if let Constraint::Length(len) | Constraint::Min(len) = constraint {
+            *size = std::cmp::min(*len, remaining);
+            remaining -= *size;
+        }
+    }
+    let base = remaining;
+    for (size, constraint) in sizes.iter_mut().zip(constraints) {
+        if let Constraint::Percentage(percent) = constraint {
+            *size = std::cmp::min(
+                base * std::cmp::min(*percent, 100) as usize / 100,
+                remaining,
+            );
+            remaining -= *size;
+        }
+    }
+    let weight = |constraint: &Constraint| match constraint {
+        Constraint::Fill(weight) => *weight as usize,
+        Constraint::Min(..) => 1,
+        Constraint::Length(..) | Constraint::Percentage(..) => 0,
+    };
+    let total_weight = constraints.iter().map(weight).sum::<usize>();
+    if total_weight == 0 {
+        // nothing grows - the last cell takes the remainder
+        if let Some(last) = sizes.last_mut() {
+            *last += remaining;
+        }
+        return sizes;
+    }
+    let leftover = remaining;
+    let mut last_growing = 0;
+    for (idx, (size, constraint)) in sizes.iter_mut().zip(constraints).enumerate() {
+        let weight = weight(constraint);
+        if weight == 0 {
+            continue;
+        }
+        let share = leftover * weight / total_weight;
+        *size += share;
+        remaining -= share;
+        last_growing = idx;
+    }
+    sizes[last_growing] += remaining;
+    sizes
+}
@@ -1,5 +1,10 @@
-use crate::{backend::Backend, utils::UTFSafe, widgets::Writable};
+use crate::{
+    backend::{CursorControl, StyleControl, TextPrint},
+    utils::{truncate_middle, UTFSafe},
+    widgets::{Spinner, Writable},
+};
 use std::ops::{AddAssign, SubAssign};
+use unicode_width::UnicodeWidthChar;
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Line {
@@ -18,24 +23,33 @@ impl Line {
     }
 
     #[inline]
-    pub fn fill(self, symbol: char, backend: &mut impl Backend) {
+    pub fn fill(self, symbol: char, backend: &mut impl CursorControl) {
+        if self.width == 0 {
+            return;
+        }
         let text = (0..self.width).map(|_| symbol).collect::<String>();
         backend.print_at(self.row, self.col, text)
     }
 
     #[inline]
-    pub fn fill_styled<B: Backend>(
+    pub fn fill_styled<B: CursorControl>(
         self,
         symbol: char,
-        style: <B as Backend>::Style,
+        style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
+        if self.width == 0 {
+            return;
+        }
         let text = (0..self.width).map(|_| symbol).collect::<String>();
         backend.print_styled_at(self.row, self.col, text, style)
     }
 
     #[inline]
-    pub fn render_centered(self, text: &str, backend: &mut impl Backend) {
+    pub fn render_centered(self, text: &str, backend: &mut impl CursorControl) {
+        if self.width == 0 {
+            return;
+        }
         let (remaining_width, text) = text.truncate_width(self.width);
         backend.go_to(self.row, self.col);
         match remaining_width {
@@ -54,12 +68,15 @@ impl Line {
     }
 
     #[inline]
-    pub fn render_centered_styled<B: Backend>(
+    pub fn render_centered_styled<B: CursorControl + StyleControl>(
         self,
         text: &str,
-        style: <B as Backend>::Style,
+        style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
+        if self.width == 0 {
+            return;
+        }
         let (remaining_width, text) = text.truncate_width(self.width);
         let restore_style = backend.get_style();
         backend.set_style(style);
@@ -81,7 +98,10 @@ impl Line {
     }
 
     #[inline]
-    pub fn render_left(self, text: &str, backend: &mut impl Backend) {
+    pub fn render_left(self, text: &str, backend: &mut impl CursorControl) {
+        if self.width == 0 {
+            return;
+        }
         let (pad_width, text) = text.truncate_width_start(self.width);
         backend.go_to(self.row, self.col);
         if pad_width != 0 {
@@ -91,12 +111,15 @@ impl Line {
     }
 
     #[inline]
-    pub fn render_left_styled<B: Backend>(
+    pub fn render_left_styled<B: CursorControl>(
         self,
         text: &str,
-        style: <B as Backend>::Style,
+        style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
+        if self.width == 0 {
+            return;
+        }
         let (pad_width, text) = text.truncate_width_start(self.width);
         backend.go_to(self.row, self.col);
         if pad_width != 0 {
@@ -105,14 +128,58 @@ impl Line {
         backend.print_styled(text, style);
     }
 
+    /// renders text truncated in the middle with ellipsis if wider than line
     #[inline]
-    pub fn render_empty(self, backend: &mut impl Backend) {
+    pub fn render_middle_ellipsis(self, text: &str, backend: &mut impl CursorControl) {
+        if self.width == 0 {
+            return;
+        }
+        let Line { width, row, col } = self;
+        let text = truncate_middle(text, width);
+        backend.go_to(row, col);
+        backend.print(&text);
+        let pad_width = width - text.width();
+        if pad_width != 0 {
+            backend.pad(pad_width);
+        }
+    }
+
+    /// renders text cut at the end with ellipsis char if wider than line
+    /// (falls back to plain truncation if the ellipsis does not fit)
+    pub fn render_truncated_ellipsis(
+        self,
+        text: &str,
+        ellipsis: char,
+        backend: &mut impl CursorControl,
+    ) {
+        let ellipsis_width = UnicodeWidthChar::width(ellipsis).unwrap_or_default();
+        if text.width() <= self.width || ellipsis_width > self.width {
+            return self.render(text, backend);
+        }
+        let Line { width, row, col } = self;
+        let (pad_width, text) = text.truncate_width(width - ellipsis_width);
+        backend.go_to(row, col);
+        backend.print(text);
+        backend.print(ellipsis);
+        if pad_width != 0 {
+            backend.pad(pad_width);
+        }
+    }
+
+    #[inline]
+    pub fn render_empty(self, backend: &mut impl CursorControl) {
+        if self.width == 0 {
+            return;
+        }
         backend.go_to(self.row, self.col);
         backend.pad(self.width);
     }
 
     #[inline]
-    pub fn render(self, text: &str, backend: &mut impl Backend) {
+    pub fn render(self, text: &str, backend: &mut impl CursorControl) {
+        if self.width == 0 {
+            return;
+        }
         let Line { width, row, col } = self;
         let (pad_width, text) = text.truncate_width(width);
         backend.go_to(row, col);
@@ -123,12 +190,15 @@ impl Line {
     }
 
     #[inline]
-    pub fn render_styled<B: Backend>(
+    pub fn render_styled<B: CursorControl + StyleControl>(
         self,
         text: &str,
-        style: <B as Backend>::Style,
+        style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
+        if self.width == 0 {
+            return;
+        }
         let Line { width, row, col } = self;
         let (pad_width, text) = text.truncate_width(width);
         let reset_style = backend.get_style();
@@ -165,11 +235,23 @@ impl Line {
         self.row == row && self.col <= column && column < self.col + self.width as u16
     }
 
+    /// shifts line by deltas, None if the line would end up outside of u16 range
+    pub fn translate(&self, d_row: i32, d_col: i32) -> Option<Self> {
+        let row = u16::try_from((self.row as i32).checked_add(d_row)?).ok()?;
+        let col = u16::try_from((self.col as i32).checked_add(d_col)?).ok()?;
+        col.checked_add(u16::try_from(self.width).ok()?)?;
+        Some(Self {
+            row,
+            col,
+            width: self.width,
+        })
+    }
+
     /// creates line builder from Line
     /// push/push_styled can be used to add to line
     /// on drop pads the line to end
     #[inline]
-    pub fn unsafe_builder<T: Backend>(self, backend: &mut T) -> LineBuilder<'_, T> {
+    pub fn unsafe_builder<T: CursorControl>(self, backend: &mut T) -> LineBuilder<'_, T> {
         backend.go_to(self.row, self.col);
         LineBuilder {
             row: self.row,
@@ -183,7 +265,7 @@ impl Line {
     /// push/push_styled can be used to add to line
     /// on drop pads the line to end
     #[inline]
-    pub fn unsafe_builder_rev<T: Backend>(self, backend: &mut T) -> LineBuilderRev<'_, T> {
+    pub fn unsafe_builder_rev<T: CursorControl>(self, backend: &mut T) -> LineBuilderRev<'_, T> {
         let remaining = self.width;
         let col = self.col;
         let row = self.row;
@@ -229,20 +311,20 @@ impl SubAssign<u16> for Line {
     }
 }
 
-pub struct LineBuilder<'a, B: Backend> {
+pub struct LineBuilder<'a, B: CursorControl> {
     row: u16,
     col: u16,
     remaining: usize,
     backend: &'a mut B,
 }
 
-impl<B: Backend> LineBuilder<'_, B> {
+impl<B: CursorControl> LineBuilder<'_, B> {
     /// returns Ok(bool) -> if true line is not full, false the line is finished
     pub fn push(&mut self, text: &str) -> bool {
         match text.truncate_if_wider(self.remaining) {
             Ok(truncated_text) => {
                 self.backend.print(truncated_text);
-                self.remaining = 0;
+                self.pad_gap(truncated_text);
                 false
             }
             Err(width) => {
@@ -254,11 +336,11 @@ impl<B: Backend> LineBuilder<'_, B> {
     }
 
     /// push with style
-    pub fn push_styled(&mut self, text: &str, style: <B as Backend>::Style) -> bool {
+    pub fn push_styled(&mut self, text: &str, style: <B as TextPrint>::Style) -> bool {
         match text.truncate_if_wider(self.remaining) {
             Ok(truncated_text) => {
                 self.backend.print_styled(truncated_text, style);
-                self.remaining = 0;
+                self.pad_gap(truncated_text);
                 false
             }
             Err(width) => {
@@ -269,6 +351,22 @@ impl<B: Backend> LineBuilder<'_, B> {
         }
     }
 
+    /// wide char that does not fit the last cell leaves a gap - padded to keep the line clean
+    #[inline]
+    fn pad_gap(&mut self, truncated_text: &str) {
+        let gap = self.remaining - truncated_text.width();
+        if gap != 0 {
+            self.backend.pad(gap);
+        }
+        self.remaining = 0;
+    }
+
+    /// push current frame of the spinner
+    pub fn push_spinner(&mut self, spinner: &Spinner) -> bool {
+        let mut buffer = [0; 4];
+        self.push(spinner.frame().encode_utf8(&mut buffer))
+    }
+
     pub fn pad(&mut self) {
         if self.remaining == 0 {
             return;
@@ -277,7 +375,7 @@ impl<B: Backend> LineBuilder<'_, B> {
         self.remaining = 0;
     }
 
-    pub fn pad_styled(&mut self, style: <B as Backend>::Style) {
+    pub fn pad_styled(&mut self, style: <B as TextPrint>::Style) {
         if self.remaining == 0 {
             return;
         }
@@ -299,7 +397,7 @@ impl<B: Backend> LineBuilder<'_, B> {
     }
 }
 
-impl<T: Backend> Drop for LineBuilder<'_, T> {
+impl<T: CursorControl> Drop for LineBuilder<'_, T> {
     /// ensure line is rendered and padded till end;
     fn drop(&mut self) {
         if self.remaining != 0 {
@@ -308,20 +406,23 @@ impl<T: Backend> Drop for LineBuilder<'_, T> {
     }
 }
 
-pub struct LineBuilderRev<'a, B: Backend> {
+pub struct LineBuilderRev<'a, B: CursorControl> {
     row: u16,
     col: u16,
     remaining: usize,
     backend: &'a mut B,
 }
 
-impl<B: Backend> LineBuilderRev<'_, B> {
+impl<B: CursorControl> LineBuilderRev<'_, B> {
     /// returns Ok(bool) -> if true line is not full, false the line is finished
     pub fn push(&mut self, text: &str) -> bool {
         match text.truncate_if_wider_start(self.remaining) {
             Ok(truncated_text) => {
+                // gap left by wide char stays at the start (already cleared)
+                let gap = self.remaining - truncated_text.width();
                 self.remaining = 0;
-                self.backend.print_at(self.row, self.col, truncated_text);
+                self.backend
+                    .print_at(self.row, self.col + gap as u16, truncated_text);
                 false
             }
             Err(width) => {
@@ -334,12 +435,17 @@ impl<B: Backend> LineBuilderRev<'_, B> {
     }
 
     /// push with style
-    pub fn push_styled(&mut self, text: &str, style: <B as Backend>::Style) -> bool {
+    pub fn push_styled(&mut self, text: &str, style: <B as TextPrint>::Style) -> bool {
         match text.truncate_if_wider_start(self.remaining) {
             Ok(truncated_text) => {
+                let gap = self.remaining - truncated_text.width();
                 self.remaining = 0;
-                self.backend
-                    .print_styled_at(self.row, self.col, truncated_text, style);
+                self.backend.print_styled_at(
+                    self.row,
+                    self.col + gap as u16,
+                    truncated_text,
+                    style,
+                );
                 false
             }
             Err(width) => {
@@ -386,7 +492,7 @@ impl<B: Backend> LineBuilderRev<'_, B> {
     }
 }
 
-impl<T: Backend> Drop for LineBuilderRev<'_, T> {
+impl<T: CursorControl> Drop for LineBuilderRev<'_, T> {
     /// ensure line is rendered and padded till end;
     fn drop(&mut self) {
         if self.remaining != 0 {
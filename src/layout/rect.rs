@@ -1,8 +1,8 @@
 use crate::{
     Position,
     {
-        backend::Backend,
-        layout::{BorderSet, Borders, Line, BORDERS},
+        backend::{CursorControl, StyleControl, TextPrint},
+        layout::{constraints::allocate, BorderSet, Borders, Constraint, Direction, Line, BORDERS},
         utils::UTFSafe,
     },
 };
@@ -46,11 +46,93 @@ impl Rect {
         }
     }
 
+    /// width * height
+    #[inline]
+    pub fn area(&self) -> usize {
+        self.width * self.height as usize
+    }
+
+    /// (width, height)
+    #[inline]
+    pub fn size(&self) -> (usize, u16) {
+        (self.width, self.height)
+    }
+
+    /// first column after the rect (col + width)
+    #[inline]
+    pub fn right_edge(&self) -> u16 {
+        self.col + self.width as u16
+    }
+
+    /// first row after the rect (row + height)
+    #[inline]
+    pub fn bottom_edge(&self) -> u16 {
+        self.row + self.height
+    }
+
     pub fn contains_position(&self, row: u16, column: u16) -> bool {
         self.col <= column
             && self.row <= row
-            && row < self.row + self.height
-            && column < self.col + self.width as u16
+            && row < self.bottom_edge()
+            && column < self.right_edge()
+    }
+
+    /// shifts rect by deltas, None if the rect would end up outside of u16 range
+    pub fn translate(&self, d_row: i32, d_col: i32) -> Option<Self> {
+        let row = u16::try_from((self.row as i32).checked_add(d_row)?).ok()?;
+        let col = u16::try_from((self.col as i32).checked_add(d_col)?).ok()?;
+        row.checked_add(self.height)?;
+        col.checked_add(u16::try_from(self.width).ok()?)?;
+        Some(Self { row, col, ..*self })
+    }
+
+    /// moves rect to new origin (top left corner) keeping dimensions
+    pub fn reanchor(&self, new_origin: Position) -> Self {
+        Self {
+            row: new_origin.row,
+            col: new_origin.col,
+            ..*self
+        }
+    }
+
+    /// true if other rect is fully within self
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        self.row <= other.row
+            && self.col <= other.col
+            && other.bottom_edge() <= self.bottom_edge()
+            && other.col as usize + other.width <= self.col as usize + self.width
+    }
+
+    /// overlapping region (geometry only - borders are not kept), None if rects do not overlap
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let row = std::cmp::max(self.row, other.row);
+        let col = std::cmp::max(self.col, other.col);
+        let bottom_edge = std::cmp::min(self.bottom_edge(), other.bottom_edge());
+        let right_edge = std::cmp::min(
+            self.col as usize + self.width,
+            other.col as usize + other.width,
+        );
+        if bottom_edge <= row || right_edge <= col as usize {
+            return None;
+        }
+        Some(Self::new(
+            row,
+            col,
+            right_edge - col as usize,
+            bottom_edge - row,
+        ))
+    }
+
+    /// bounding box of both rects (geometry only - borders are not kept)
+    pub fn union(&self, other: &Self) -> Self {
+        let row = std::cmp::min(self.row, other.row);
+        let col = std::cmp::min(self.col, other.col);
+        let bottom_edge = std::cmp::max(self.bottom_edge(), other.bottom_edge());
+        let right_edge = std::cmp::max(
+            self.col as usize + self.width,
+            other.col as usize + other.width,
+        );
+        Self::new(row, col, right_edge - col as usize, bottom_edge - row)
     }
 
     pub fn relative_position(&self, row: u16, column: u16) -> Option<Position> {
@@ -95,7 +177,7 @@ impl Rect {
             if self.width > 30 + col_offset as usize {
                 width = self.width - col_offset as usize;
             } else if self.width > 30 {
-                col = (self.col + self.width as u16) - 30;
+                col = self.right_edge() - 30;
                 width = 30;
             } else {
                 width = 0;
@@ -152,6 +234,120 @@ impl Rect {
         )
     }
 
+    /// splits rect into count columns of even width - remainder goes to the first columns
+    /// (columns are new rects - borders are not kept)
+    pub fn split_even_horizontal(self, count: usize) -> Vec<Self> {
+        self.even_columns(count)
+            .into_iter()
+            .map(|column| Self::new(column.row, column.col, column.width, column.height))
+            .collect()
+    }
+
+    /// count columns of even width keeping the rect settings - remainder goes to the first columns
+    fn even_columns(self, count: usize) -> Vec<Self> {
+        let mut col = self.col;
+        even_parts(self.width, count)
+            .map(|width| {
+                let column = Self { col, width, ..self };
+                col += width as u16;
+                column
+            })
+            .collect()
+    }
+
+    /// splits rect into n columns of even width - remainder goes to the leftmost columns
+    pub fn split_cols(self, n: usize) -> Vec<Self> {
+        if self.width == 0 || self.height == 0 {
+            return Vec::new();
+        }
+        self.even_columns(n)
+    }
+
+    /// splits rect into n rows of even height - remainder goes to the topmost rows
+    pub fn split_rows(self, n: usize) -> Vec<Self> {
+        if self.width == 0 || self.height == 0 {
+            return Vec::new();
+        }
+        let mut row = self.row;
+        even_parts(self.height as usize, n)
+            .map(|height| {
+                let height = height as u16;
+                let cell = Self {
+                    row,
+                    height,
+                    ..self
+                };
+                row += height;
+                cell
+            })
+            .collect()
+    }
+
+    /// splits rect by constraints along direction
+    /// lengths are allocated first, then percentages of the remaining space, then fills
+    pub fn split_with(&self, direction: Direction, constraints: &[Constraint]) -> Vec<Self> {
+        match direction {
+            Direction::Horizontal => {
+                let mut col = self.col;
+                allocate(self.width, constraints)
+                    .into_iter()
+                    .map(|width| {
+                        let cell = Self {
+                            col,
+                            width,
+                            ..*self
+                        };
+                        col += width as u16;
+                        cell
+                    })
+                    .collect()
+            }
+            Direction::Vertical => {
+                let mut row = self.row;
+                allocate(self.height as usize, constraints)
+                    .into_iter()
+                    .map(|height| {
+                        let cell = Self {
+                            row,
+                            height: height as u16,
+                            ..*self
+                        };
+                        row += height as u16;
+                        cell
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// splits rect into rows of columns (grid[row][col])
+    pub fn grid(self, rows: usize, cols: usize) -> Vec<Vec<Self>> {
+        if cols == 0 {
+            return Vec::new();
+        }
+        self.split_rows(rows)
+            .into_iter()
+            .map(|row| row.split_cols(cols))
+            .collect()
+    }
+
+    /// splits rect into header, body and footer - body takes the remaining height
+    /// if rect is too short body shrinks first, then footer, then header
+    pub fn header_body_footer(self, header_rows: u16, footer_rows: u16) -> (Self, Self, Self) {
+        let header_height = std::cmp::min(header_rows, self.height);
+        let footer_height = std::cmp::min(footer_rows, self.height - header_height);
+        let body_height = self.height - header_height - footer_height;
+        let header = Self::new(self.row, self.col, self.width, header_height);
+        let body = Self::new(self.row + header_height, self.col, self.width, body_height);
+        let footer = Self::new(
+            self.row + header_height + body_height,
+            self.col,
+            self.width,
+            footer_height,
+        );
+        (header, body, footer)
+    }
+
     /// Pops last line from rect
     pub fn pop_line(&mut self) -> Line {
         if self.height == 0 {
@@ -224,6 +420,18 @@ impl Rect {
         }
     }
 
+    /// full width rect with height of the content centered vertically (height is clamped)
+    pub fn center_vertical(&self, content_height: u16) -> Self {
+        let height = std::cmp::min(self.height, content_height);
+        Self {
+            row: self.row + ((self.height - height) / 2),
+            col: self.col,
+            width: self.width,
+            height,
+            ..Default::default()
+        }
+    }
+
     pub fn vcenter(self, mut width: usize) -> Self {
         width = std::cmp::min(self.width, width);
         let col = (self.width - width) as u16 / 2 + self.col;
@@ -338,6 +546,25 @@ impl Rect {
         }
     }
 
+    /// shrinks rect by vertical rows on top and bottom and by horizontal cols on both sides
+    /// (saturates to empty rect within self) - borders are not changed
+    pub fn margin(&self, vertical: u16, horizontal: usize) -> Self {
+        let row_shift = std::cmp::min(vertical, self.height / 2);
+        let col_shift = std::cmp::min(horizontal, self.width / 2);
+        Self {
+            row: self.row + row_shift,
+            col: self.col + col_shift as u16,
+            width: self.width.saturating_sub(horizontal.saturating_mul(2)),
+            height: self.height.saturating_sub(vertical.saturating_mul(2)),
+            borders: self.borders,
+        }
+    }
+
+    /// same margin on all sides
+    pub fn inner(&self, amount: u16) -> Self {
+        self.margin(amount, amount as usize)
+    }
+
     #[inline]
     pub fn bordered(&mut self) {
         self.col += 1;
@@ -383,7 +610,48 @@ impl Rect {
         self
     }
 
-    pub fn clear(&self, writer: &mut impl Backend) {
+    /// sets border flags only - geometry is not changed
+    /// useful when the space for the border is already taken (by parent)
+    #[inline]
+    pub fn set_borders(&mut self, borders: Borders) {
+        self.borders = borders;
+    }
+
+    #[inline]
+    pub fn has_border(&self, border: Borders) -> bool {
+        self.borders.contains(border)
+    }
+
+    /// removes border flags and restores the geometry taken by them
+    /// inverse of top_border / bot_border / right_border / left_border
+    #[inline]
+    pub fn without_border(mut self, border: Borders) -> Self {
+        let removed = self.borders & border;
+        // rect at the screen edge has no col / row to restore
+        if removed.contains(Borders::TOP) {
+            let row = self.row.saturating_sub(1);
+            self.height = self.height.saturating_add(self.row - row);
+            self.row = row;
+        }
+        if removed.contains(Borders::BOTTOM) {
+            self.height = self.height.saturating_add(1);
+        }
+        if removed.contains(Borders::LEFT) {
+            let col = self.col.saturating_sub(1);
+            self.width += (self.col - col) as usize;
+            self.col = col;
+        }
+        if removed.contains(Borders::RIGHT) {
+            self.width += 1;
+        }
+        self.borders.remove(removed);
+        self
+    }
+
+    pub fn clear(&self, writer: &mut impl CursorControl) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
         for line in self.into_iter() {
             line.render_empty(writer);
         }
@@ -392,7 +660,7 @@ impl Rect {
     /// renders title if top border exists
     /// !!! this needs to happen after border rendering
     #[inline]
-    pub fn border_title(&self, text: &str, backend: &mut impl Backend) {
+    pub fn border_title(&self, text: &str, backend: &mut impl CursorControl) {
         if !self.borders.contains(Borders::TOP) {
             return;
         };
@@ -400,7 +668,12 @@ impl Rect {
     }
 
     #[inline]
-    pub fn border_title_prefixed(&self, prefix: &str, suffix: &str, backend: &mut impl Backend) {
+    pub fn border_title_prefixed(
+        &self,
+        prefix: &str,
+        suffix: &str,
+        backend: &mut impl CursorControl,
+    ) {
         if !self.borders.contains(Borders::TOP) {
             return;
         }
@@ -415,12 +688,30 @@ impl Rect {
         };
     }
 
+    /// border_title truncated in the middle (keeps the end of paths visible)
+    #[inline]
+    pub fn border_title_middle(
+        &self,
+        text: &str,
+        ellipsis: &str,
+        backend: &mut impl CursorControl,
+    ) {
+        if !self.borders.contains(Borders::TOP) {
+            return;
+        };
+        backend.print_at(
+            self.row - 1,
+            self.col,
+            text.truncate_width_middle(self.width, ellipsis),
+        );
+    }
+
     /// border_title with style
     #[inline]
-    pub fn border_title_styled<B: Backend>(
+    pub fn border_title_styled<B: CursorControl>(
         &self,
         text: &str,
-        style: <B as Backend>::Style,
+        style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
         if self.borders.contains(Borders::TOP) {
@@ -436,10 +727,10 @@ impl Rect {
     /// renders title if bottom border exists
     /// !!! this needs to happen after border rendering
     #[inline]
-    pub fn border_title_bot(&self, text: &str, backend: &mut impl Backend) {
+    pub fn border_title_bot(&self, text: &str, backend: &mut impl CursorControl) {
         if self.borders.contains(Borders::BOTTOM) {
             backend.print_at(
-                self.row + self.height + 1,
+                self.bottom_edge() + 1,
                 self.col,
                 text.truncate_width(self.width).1,
             );
@@ -448,15 +739,15 @@ impl Rect {
 
     /// border_title_bot with style
     #[inline]
-    pub fn border_title_bot_styled<B: Backend>(
+    pub fn border_title_bot_styled<B: CursorControl>(
         &self,
         text: &str,
-        style: <B as Backend>::Style,
+        style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
         if self.borders.contains(Borders::BOTTOM) {
             backend.print_styled_at(
-                self.row + self.height + 1,
+                self.bottom_edge() + 1,
                 self.col,
                 text.truncate_width(self.width).1,
                 style,
@@ -464,21 +755,26 @@ impl Rect {
         }
     }
 
-    pub fn draw_borders<B: Backend>(
+    pub fn draw_borders<B: CursorControl + StyleControl>(
         &self,
         set: Option<BorderSet>,
-        fg: Option<<B as Backend>::Color>,
+        fg: Option<<B as StyleControl>::Color>,
         backend: &mut B,
     ) {
         let top = self.borders.contains(Borders::TOP);
         let bot = self.borders.contains(Borders::BOTTOM);
         let left = self.borders.contains(Borders::LEFT);
         let right = self.borders.contains(Borders::RIGHT);
+        // nothing is enclosed or borders would be out of the screen
+        if self.width == 0 || self.height == 0 || (top && self.row == 0) || (left && self.col == 0)
+        {
+            return;
+        }
 
         let mut row = self.row;
         let mut col = self.col;
-        let last_row = self.row + self.height;
-        let last_col = self.col + self.width as u16;
+        let last_row = self.bottom_edge();
+        let last_col = self.right_edge();
 
         if top {
             row -= 1;
@@ -538,6 +834,13 @@ impl Rect {
     }
 }
 
+/// sizes of count even parts of total - remainder goes to the first parts
+fn even_parts(total: usize, count: usize) -> impl Iterator<Item = usize> {
+    let base = total.checked_div(count).unwrap_or_default();
+    let remainder = total.checked_rem(count).unwrap_or_default();
+    (0..count).map(move |idx| base + usize::from(idx < remainder))
+}
+
 impl From<(u16, u16)> for Rect {
     fn from((width, height): (u16, u16)) -> Self {
         Self {
@@ -1,5 +1,5 @@
 use crate::{
-    backend::Backend,
+    backend::CursorControl,
     layout::{Line, Rect},
 };
 use std::ops::Range;
@@ -7,12 +7,12 @@ use std::ops::Range;
 pub trait IterLines: Iterator<Item = Line> {
     fn len(&self) -> usize;
     fn width(&self) -> usize;
-    fn move_cursor(&mut self, backend: &mut impl Backend) -> Option<usize>;
+    fn move_cursor(&mut self, backend: &mut impl CursorControl) -> Option<usize>;
     fn into_rect(self) -> Option<Rect>;
     fn forward(&mut self, steps: usize);
     fn is_finished(&self) -> bool;
     fn next_line_idx(&self) -> u16;
-    fn clear_to_end(&mut self, backend: &mut impl Backend);
+    fn clear_to_end(&mut self, backend: &mut impl CursorControl);
 
     fn is_empty(&self) -> bool {
         self.len() == 0
@@ -40,7 +40,7 @@ impl IntoIterator for Rect {
     type Item = Line;
     fn into_iter(self) -> Self::IntoIter {
         RectIter {
-            row_range: self.row..self.row + self.height,
+            row_range: self.row..self.bottom_edge(),
             rect: self,
         }
     }
@@ -61,7 +61,7 @@ impl IterLines for RectIter {
 
     /// moves to next line and returns width if success
     #[inline]
-    fn move_cursor(&mut self, backend: &mut impl Backend) -> Option<usize> {
+    fn move_cursor(&mut self, backend: &mut impl CursorControl) -> Option<usize> {
         self.next().map(|Line { row, col, width }| {
             backend.go_to(row, col);
             width
@@ -100,7 +100,7 @@ impl IterLines for RectIter {
     }
 
     #[inline]
-    fn clear_to_end(&mut self, backend: &mut impl Backend) {
+    fn clear_to_end(&mut self, backend: &mut impl CursorControl) {
         for remaining_line in self {
             remaining_line.render_empty(backend);
         }
@@ -159,7 +159,7 @@ impl IterLines for DoublePaddedRectIter {
     }
 
     #[inline]
-    fn move_cursor(&mut self, backend: &mut impl Backend) -> Option<usize> {
+    fn move_cursor(&mut self, backend: &mut impl CursorControl) -> Option<usize> {
         let row = self.row_range.next()?;
         backend.go_to(row, self.padded_col + self.padded_width as u16);
         backend.pad(self.padding);
@@ -181,7 +181,7 @@ impl IterLines for DoublePaddedRectIter {
     }
 
     #[inline]
-    fn clear_to_end(&mut self, backend: &mut impl Backend) {
+    fn clear_to_end(&mut self, backend: &mut impl CursorControl) {
         for row in self.row_range.by_ref() {
             Line {
                 row,
@@ -214,7 +214,7 @@ impl DoublePaddedRectIter {
         }
     }
 
-    pub fn next_padded(&mut self, backend: &mut impl Backend) -> Option<Line> {
+    pub fn next_padded(&mut self, backend: &mut impl CursorControl) -> Option<Line> {
         let row = self.row_range.next()?;
         backend.go_to(row, self.padded_col + self.padded_width as u16);
         backend.pad(self.padding);
@@ -233,3 +233,205 @@ impl Rect {
         DoublePaddedRectIter::new(self, padding)
     }
 }
+
+/// Lines avoiding range of columns (relative to the rect) - used when rendering around vertical elements
+/// Policy: lines are limited to the columns left of the exclusion,
+/// if the exclusion starts at the first column, lines are limited to the columns right of it.
+pub struct ExcludingColsRectIter {
+    row_range: Range<u16>,
+    col: u16,
+    width: usize,
+}
+
+impl Iterator for ExcludingColsRectIter {
+    type Item = Line;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.row_range.next().map(|row| Line {
+            col: self.col,
+            row,
+            width: self.width,
+        })
+    }
+}
+
+impl IterLines for ExcludingColsRectIter {
+    #[inline]
+    fn len(&self) -> usize {
+        self.row_range.len()
+    }
+
+    #[inline]
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    fn move_cursor(&mut self, backend: &mut impl CursorControl) -> Option<usize> {
+        let row = self.row_range.next()?;
+        backend.go_to(row, self.col);
+        Some(self.width)
+    }
+
+    #[inline]
+    fn into_rect(mut self) -> Option<Rect> {
+        let height = self.row_range.len() as u16;
+        self.row_range.next().map(|row| Rect {
+            row,
+            col: self.col,
+            width: self.width,
+            height,
+            ..Default::default()
+        })
+    }
+
+    #[inline]
+    fn forward(&mut self, mut steps: usize) {
+        while steps != 0 {
+            steps -= 1;
+            self.row_range.next();
+        }
+    }
+
+    #[inline]
+    fn is_finished(&self) -> bool {
+        self.row_range.is_empty()
+    }
+
+    #[inline]
+    fn next_line_idx(&self) -> u16 {
+        self.row_range.start
+    }
+
+    #[inline]
+    fn clear_to_end(&mut self, backend: &mut impl CursorControl) {
+        for remaining_line in self {
+            remaining_line.render_empty(backend);
+        }
+    }
+}
+
+impl RectIter {
+    /// remaining lines narrowed to avoid the columns in range (relative to rect col)
+    pub fn excluding_cols(self, range: Range<usize>) -> ExcludingColsRectIter {
+        let (col, width) = match range.start {
+            0 => (
+                self.rect.col + std::cmp::min(range.end, self.rect.width) as u16,
+                self.rect.width.saturating_sub(range.end),
+            ),
+            start => (self.rect.col, std::cmp::min(start, self.rect.width)),
+        };
+        ExcludingColsRectIter {
+            row_range: self.row_range,
+            col,
+            width,
+        }
+    }
+}
+
+/// Lines of content centered vertically within the remaining rows -
+/// rows above the content are cleared on the first move_cursor (or on clear_to_end), rows below on clear_to_end
+pub struct CenteredRectIter {
+    col: u16,
+    width: usize,
+    top_range: Range<u16>,
+    row_range: Range<u16>,
+    bottom_range: Range<u16>,
+}
+
+impl CenteredRectIter {
+    fn clear_top(&mut self, backend: &mut impl CursorControl) {
+        let (col, width) = (self.col, self.width);
+        for row in self.top_range.by_ref() {
+            Line { row, col, width }.render_empty(backend);
+        }
+    }
+}
+
+impl Iterator for CenteredRectIter {
+    type Item = Line;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.row_range.next().map(|row| Line {
+            col: self.col,
+            row,
+            width: self.width,
+        })
+    }
+}
+
+impl IterLines for CenteredRectIter {
+    #[inline]
+    fn len(&self) -> usize {
+        self.row_range.len()
+    }
+
+    #[inline]
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    fn move_cursor(&mut self, backend: &mut impl CursorControl) -> Option<usize> {
+        self.clear_top(backend);
+        let Line { row, col, width } = self.next()?;
+        backend.go_to(row, col);
+        Some(width)
+    }
+
+    #[inline]
+    fn into_rect(mut self) -> Option<Rect> {
+        let height = self.row_range.len() as u16;
+        self.row_range.next().map(|row| Rect {
+            row,
+            col: self.col,
+            width: self.width,
+            height,
+            ..Default::default()
+        })
+    }
+
+    #[inline]
+    fn forward(&mut self, mut steps: usize) {
+        while steps != 0 {
+            steps -= 1;
+            self.row_range.next();
+        }
+    }
+
+    #[inline]
+    fn is_finished(&self) -> bool {
+        self.row_range.is_empty()
+    }
+
+    #[inline]
+    fn next_line_idx(&self) -> u16 {
+        self.row_range.start
+    }
+
+    #[inline]
+    fn clear_to_end(&mut self, backend: &mut impl CursorControl) {
+        self.clear_top(backend);
+        let (col, width) = (self.col, self.width);
+        for row in self.row_range.by_ref().chain(self.bottom_range.by_ref()) {
+            Line { row, col, width }.render_empty(backend);
+        }
+    }
+}
+
+impl RectIter {
+    /// remaining lines with content lines centered vertically (content is clamped to the remaining lines)
+    /// blank rows around the content are rendered by move_cursor / clear_to_end
+    pub fn centered(self, content_lines: usize) -> CenteredRectIter {
+        let Range { start, end } = self.row_range;
+        let remaining = end - start;
+        let content = std::cmp::min(content_lines, remaining as usize) as u16;
+        let content_start = start + (remaining - content) / 2;
+        let content_end = content_start + content;
+        CenteredRectIter {
+            col: self.rect.col,
+            width: self.rect.width,
+            top_range: start..content_start,
+            row_range: content_start..content_end,
+            bottom_range: content_end..end,
+        }
+    }
+}
@@ -1,8 +1,10 @@
-use super::{Line, Rect};
+use super::{Constraint, Direction, IterLines, Line, Rect};
 use crate::{
-    backend::{Backend, StyleExt},
-    backend::{MockedBackend, MockedStyle},
-    layout::Borders,
+    backend::{prelude::*, MockedBackend, MockedStyle},
+    layout::{BorderSet, Borders, ASCII_BORDERS, DOUBLE_BORDERS, ROUNDED_BORDERS, THICK_BORDERS},
+    utils::UTFSafe,
+    widgets::Spinner,
+    Position,
 };
 
 #[test]
@@ -135,6 +137,85 @@ fn rect_next_line_back() {
     assert_eq!(Some(rect.clone().pop_line()), rect.next_line_back());
 }
 
+#[test]
+fn center_vertical() {
+    let rect = Rect::new(2, 4, 10, 9);
+    assert_eq!(rect.center_vertical(3), Rect::new(5, 4, 10, 3));
+    assert_eq!(rect.center_vertical(4), Rect::new(4, 4, 10, 4));
+    assert_eq!(rect.center_vertical(20), Rect::new(2, 4, 10, 9));
+}
+
+#[test]
+fn centered_lines() {
+    let mut backend = MockedBackend::init();
+    let rect = Rect::new(0, 0, 10, 9);
+    let mut lines = rect.into_iter().centered(3);
+    assert_eq!(lines.len(), 3);
+    assert!(backend.drain().is_empty());
+    for item in ["first", "second", "third"] {
+        let width = lines.move_cursor(&mut backend).unwrap();
+        backend.print(item);
+        backend.pad(width - item.len());
+    }
+    assert!(lines.move_cursor(&mut backend).is_none());
+    lines.clear_to_end(&mut backend);
+    let mut expected = vec![];
+    let empty_row = |expected: &mut Vec<(MockedStyle, String)>, row: u16| {
+        expected.push((
+            MockedStyle::default(),
+            format!("<<go to row: {row} col: 0>>"),
+        ));
+        expected.push((MockedStyle::default(), "<<padding: 10>>".to_owned()));
+    };
+    for row in 0..3 {
+        empty_row(&mut expected, row);
+    }
+    for (row, item) in [(3, "first"), (4, "second"), (5, "third")] {
+        expected.push((
+            MockedStyle::default(),
+            format!("<<go to row: {row} col: 0>>"),
+        ));
+        expected.push((MockedStyle::default(), item.to_owned()));
+        expected.push((
+            MockedStyle::default(),
+            format!("<<padding: {}>>", 10 - item.len()),
+        ));
+    }
+    for row in 6..9 {
+        empty_row(&mut expected, row);
+    }
+    assert_eq!(backend.drain(), expected);
+
+    // rows above are cleared on clear_to_end if lines are taken with next
+    let mut lines = rect.into_iter().centered(3);
+    for (line, item) in lines.by_ref().zip(["first", "second", "third"]) {
+        line.render(item, &mut backend);
+    }
+    backend.drain();
+    lines.clear_to_end(&mut backend);
+    let mut expected = vec![];
+    for row in (0..3).chain(6..9) {
+        empty_row(&mut expected, row);
+    }
+    assert_eq!(backend.drain(), expected);
+
+    // skipped content lines are not rendered
+    let mut lines = rect.into_iter().centered(3);
+    lines.forward(1);
+    assert_eq!(lines.next_line_idx(), 4);
+    assert_eq!(lines.into_rect(), Some(Rect::new(4, 0, 10, 2)));
+
+    // content taller than the rect fills it
+    let mut lines = rect.into_iter().centered(20);
+    assert_eq!(lines.next_line_idx(), 0);
+    assert_eq!(lines.len(), 9);
+    lines.move_cursor(&mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![(MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned())]
+    );
+}
+
 #[test]
 fn render_centered() {
     let width = 50;
@@ -483,3 +564,763 @@ fn left_bot_cornet() {
     let rect = Rect::new(0, 0, 100, 20).left_bot_corner(5, 60);
     assert_eq!(Rect::new(15, 0, 60, 5), rect);
 }
+
+#[test]
+fn border_round_trip() {
+    let base = Rect::new(5, 5, 40, 20);
+
+    let mut rect = base;
+    rect.top_border();
+    assert!(rect.has_border(Borders::TOP));
+    assert_eq!(rect.without_border(Borders::TOP), base);
+
+    let mut rect = base;
+    rect.bot_border();
+    assert!(rect.has_border(Borders::BOTTOM));
+    assert_eq!(rect.without_border(Borders::BOTTOM), base);
+
+    let mut rect = base;
+    rect.left_border();
+    assert!(rect.has_border(Borders::LEFT));
+    assert_eq!(rect.without_border(Borders::LEFT), base);
+
+    let mut rect = base;
+    rect.right_border();
+    assert!(rect.has_border(Borders::RIGHT));
+    assert_eq!(rect.without_border(Borders::RIGHT), base);
+
+    assert_eq!(base.with_borders().without_border(Borders::ALL), base);
+}
+
+#[test]
+fn border_partial_removal() {
+    let base = Rect::new(5, 5, 40, 20);
+    let rect = base.with_borders().without_border(Borders::LEFT);
+    assert_eq!(
+        rect.borders,
+        Borders::TOP | Borders::RIGHT | Borders::BOTTOM
+    );
+    assert_eq!(
+        rect,
+        Rect {
+            row: 6,
+            col: 5,
+            width: 39,
+            height: 18,
+            borders: rect.borders
+        }
+    );
+    // not set flags do not affect geometry
+    assert_eq!(rect.without_border(Borders::LEFT), rect);
+}
+
+#[test]
+fn border_title_middle() {
+    let mut backend = MockedBackend::init();
+    let rect = Rect::new(0, 0, 10, 4).with_borders();
+    rect.border_title_middle("/home/user/file.rs", "…", &mut backend);
+    assert_eq!(
+        backend.drain(),
+        [
+            (MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned()),
+            (MockedStyle::default(), "/hom….rs".to_owned()),
+        ]
+    );
+    rect.border_title_middle("short", "…", &mut backend);
+    assert_eq!(
+        backend.drain()[1],
+        (MockedStyle::default(), "short".to_owned())
+    );
+    // no top border - nothing to render on
+    Rect::new(0, 0, 10, 4).border_title_middle("title", "…", &mut backend);
+    assert!(backend.drain().is_empty());
+}
+
+#[test]
+fn without_border_at_edge() {
+    let mut rect = Rect::new(0, 0, 40, 20);
+    rect.set_borders(Borders::ALL);
+    let rect = rect.without_border(Borders::ALL);
+    assert_eq!(rect, Rect::new(0, 0, 41, 21));
+    assert_eq!(rect.borders, Borders::NONE);
+}
+
+#[test]
+fn set_borders_keeps_geometry() {
+    let mut rect = Rect::new(1, 1, 40, 20);
+    rect.set_borders(Borders::TOP);
+    assert_eq!(rect.row, 1);
+    assert_eq!(rect.height, 20);
+    assert!(rect.has_border(Borders::TOP));
+    assert!(!rect.has_border(Borders::LEFT));
+    let mut backend = MockedBackend::init();
+    rect.draw_borders(None, None, &mut backend);
+    let data = backend.drain();
+    assert!(data.contains(&(MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned())));
+    assert!(!data.iter().any(|(_, text)| text == "│"));
+}
+
+#[test]
+fn border_sets() {
+    let sets: [(BorderSet, [&str; 4]); 4] = [
+        (ROUNDED_BORDERS, ["╭", "╮", "╰", "╯"]),
+        (DOUBLE_BORDERS, ["╔", "╗", "╚", "╝"]),
+        (THICK_BORDERS, ["┏", "┓", "┗", "┛"]),
+        (ASCII_BORDERS, ["+", "+", "+", "+"]),
+    ];
+    let rect = Rect::new(0, 0, 5, 4).with_borders();
+    let mut backend = MockedBackend::init();
+    for (set, [top_left, top_right, bot_left, bot_right]) in sets {
+        let horizontal = set.horizontal_top.to_string();
+        rect.draw_borders(Some(set), None, &mut backend);
+        let data = backend.drain();
+        assert!(data.contains(&(MockedStyle::default(), horizontal)));
+        let corners: Vec<_> = data[data.len() - 8..]
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect();
+        assert_eq!(
+            corners,
+            [
+                "<<go to row: 0 col: 0>>",
+                top_left,
+                "<<go to row: 0 col: 4>>",
+                top_right,
+                "<<go to row: 3 col: 0>>",
+                bot_left,
+                "<<go to row: 3 col: 4>>",
+                bot_right,
+            ]
+        );
+    }
+}
+
+#[test]
+fn header_body_footer() {
+    let rect = Rect::new(2, 3, 40, 20);
+    let (header, body, footer) = rect.header_body_footer(1, 2);
+    assert_eq!(header, Rect::new(2, 3, 40, 1));
+    assert_eq!(body, Rect::new(3, 3, 40, 17));
+    assert_eq!(footer, Rect::new(20, 3, 40, 2));
+}
+
+#[test]
+fn header_body_footer_short() {
+    let rect = Rect::new(2, 3, 40, 4);
+    let (header, body, footer) = rect.header_body_footer(3, 3);
+    assert_eq!(header, Rect::new(2, 3, 40, 3));
+    assert_eq!(body, Rect::new(5, 3, 40, 0));
+    assert_eq!(footer, Rect::new(5, 3, 40, 1));
+    assert_eq!(header.height + body.height + footer.height, rect.height);
+
+    let (header, body, footer) = rect.header_body_footer(6, 3);
+    assert_eq!(header, Rect::new(2, 3, 40, 4));
+    assert_eq!(body, Rect::new(6, 3, 40, 0));
+    assert_eq!(footer, Rect::new(6, 3, 40, 0));
+
+    let (header, body, footer) = rect.header_body_footer(1, 3);
+    assert_eq!(header, Rect::new(2, 3, 40, 1));
+    assert_eq!(body, Rect::new(3, 3, 40, 0));
+    assert_eq!(footer, Rect::new(3, 3, 40, 3));
+}
+
+#[test]
+fn contains_rect() {
+    let rect = Rect::new(2, 2, 10, 10);
+    assert!(rect.contains_rect(&rect));
+    assert!(rect.contains_rect(&Rect::new(3, 3, 9, 9)));
+    assert!(!rect.contains_rect(&Rect::new(3, 3, 10, 9)));
+    assert!(!rect.contains_rect(&Rect::new(1, 3, 2, 2)));
+    assert!(!rect.contains_rect(&Rect::new(11, 3, 2, 2)));
+}
+
+#[test]
+fn builder_push_spinner() {
+    let mut backend = MockedBackend::init();
+    let mut spinner = Spinner::new();
+    let line = Line {
+        row: 1,
+        col: 3,
+        width: 12,
+    };
+    {
+        let mut builder = line.clone().unsafe_builder(&mut backend);
+        assert!(builder.push_spinner(&spinner));
+        assert!(builder.push(" Loading"));
+        assert_eq!(builder.width(), 3);
+    }
+    spinner.tick();
+    {
+        let mut builder = line.unsafe_builder(&mut backend);
+        assert!(builder.push("Loading 42%"));
+        assert!(builder.push_spinner(&spinner));
+        assert!(!builder.push_spinner(&spinner));
+    }
+    assert_eq!(
+        backend.drain(),
+        [
+            (MockedStyle::default(), "<<go to row: 1 col: 3>>".to_owned()),
+            (MockedStyle::default(), "⠋".to_owned()),
+            (MockedStyle::default(), " Loading".to_owned()),
+            (MockedStyle::default(), "<<padding: 3>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 3>>".to_owned()),
+            (MockedStyle::default(), "Loading 42%".to_owned()),
+            (MockedStyle::default(), "⠙".to_owned()),
+            (MockedStyle::default(), "".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn rect_translate() {
+    let rect = Rect::new(2, 3, 10, 5).with_borders();
+    assert_eq!(
+        rect.translate(4, -4),
+        Some(Rect {
+            row: 7,
+            col: 0,
+            ..rect
+        })
+    );
+    assert_eq!(rect.translate(-3, 0).map(|r| r.row), Some(0));
+    assert_eq!(rect.translate(-4, 0), None);
+    assert_eq!(rect.translate(0, -5), None);
+    let max_row = (u16::MAX - rect.height - rect.row) as i32;
+    assert_eq!(
+        rect.translate(max_row, 0).map(|r| r.row),
+        Some(u16::MAX - rect.height)
+    );
+    assert_eq!(rect.translate(max_row + 1, 0), None);
+    let max_col = u16::MAX as i32 - rect.width as i32 - rect.col as i32;
+    assert!(rect.translate(0, max_col).is_some());
+    assert_eq!(rect.translate(0, max_col + 1), None);
+    assert_eq!(rect.translate(i32::MAX, 0), None);
+}
+
+#[test]
+fn line_translate() {
+    let line = Line {
+        row: 1,
+        col: 2,
+        width: 10,
+    };
+    assert_eq!(
+        line.translate(-1, -2),
+        Some(Line {
+            row: 0,
+            col: 0,
+            width: 10
+        })
+    );
+    assert_eq!(line.translate(-2, 0), None);
+    assert_eq!(line.translate(0, -3), None);
+    assert_eq!(
+        line.translate(u16::MAX as i32 - 1, 0).map(|l| l.row),
+        Some(u16::MAX)
+    );
+    assert_eq!(line.translate(u16::MAX as i32, 0), None);
+    assert!(line.translate(0, u16::MAX as i32 - 12).is_some());
+    assert_eq!(line.translate(0, u16::MAX as i32 - 11), None);
+}
+
+#[test]
+fn rect_reanchor() {
+    let rect = Rect::new(2, 3, 10, 5).with_borders();
+    let moved = rect.reanchor(Position { row: 20, col: 30 });
+    assert_eq!((moved.row, moved.col), (20, 30));
+    assert_eq!(
+        (moved.width, moved.height, moved.borders),
+        (rect.width, rect.height, rect.borders)
+    );
+}
+
+#[test]
+fn visual_bell() {
+    let mut backend = MockedBackend::init();
+    backend.visual_bell(Rect::new(1, 2, 10, 3), MockedStyle::reversed());
+    assert_eq!(
+        backend.drain(),
+        [(
+            MockedStyle::reversed(),
+            "<<visual bell row: 1 col: 2 width: 10 height: 3>>".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn rect_accessors() {
+    let rect = Rect::new(2, 3, 10, 5);
+    assert_eq!(rect.area(), 50);
+    assert_eq!(rect.size(), (10, 5));
+    assert_eq!(rect.right_edge(), 13);
+    assert_eq!(rect.bottom_edge(), 7);
+    assert!(rect.contains_position(6, 12));
+    assert!(!rect.contains_position(7, 12));
+    assert!(!rect.contains_position(6, 13));
+    assert_eq!(Rect::default().area(), 0);
+}
+
+/// minimal backend with only printing and cursor capabilities
+#[derive(Default)]
+struct PrintOnly {
+    data: Vec<String>,
+}
+
+impl TextPrint for PrintOnly {
+    type Style = ();
+
+    fn print<D: std::fmt::Display>(&mut self, text: D) {
+        self.data.push(text.to_string());
+    }
+
+    fn print_styled<D: std::fmt::Display>(&mut self, text: D, _: ()) {
+        self.print(text);
+    }
+}
+
+impl CursorControl for PrintOnly {
+    fn go_to(&mut self, row: u16, col: u16) {
+        self.data.push(format!("<<go to row: {row} col: {col}>>"));
+    }
+
+    fn save_cursor(&mut self) {}
+
+    fn restore_cursor(&mut self) {}
+
+    fn render_cursor_at(&mut self, row: u16, col: u16) {
+        self.go_to(row, col);
+    }
+
+    fn show_cursor(&mut self) {}
+
+    fn hide_cursor(&mut self) {}
+}
+
+#[test]
+fn print_only_backend() {
+    let mut backend = PrintOnly::default();
+    let mut rect = Rect::new(1, 2, 6, 2);
+    let mut lines = rect.into_iter();
+    lines.next().unwrap().render("abc", &mut backend);
+    let mut builder = lines.next().unwrap().unsafe_builder(&mut backend);
+    assert!(builder.push_styled("de", ()));
+    drop(builder);
+    rect.border_title("ignored", &mut backend);
+    rect.borders = Borders::TOP;
+    rect.row = 2;
+    rect.border_title("title", &mut backend);
+    assert_eq!(
+        backend.data,
+        [
+            "<<go to row: 1 col: 2>>",
+            "abc",
+            "   ",
+            "<<go to row: 2 col: 2>>",
+            "de",
+            "    ",
+            "<<go to row: 1 col: 2>>",
+            "title",
+        ]
+    );
+}
+
+/// generic code bounded only by Backend (pre split) - supertrait methods are in scope through the bound
+mod backend_bound {
+    use crate::backend::{Backend, MockedBackend, MockedStyle, StyleExt};
+
+    fn render_title<B: Backend>(title: &str, backend: &mut B) {
+        backend.go_to(0, 1);
+        backend.print_styled(title, B::bold_style());
+        backend.pad(2);
+    }
+
+    #[test]
+    fn only_backend_in_scope() {
+        let mut backend = MockedBackend::default();
+        render_title("title", &mut backend);
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned()),
+                (MockedStyle::bold(), "title".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            ]
+        );
+    }
+}
+
+#[test]
+fn render_middle_ellipsis() {
+    let mut backend = MockedBackend::init();
+    let line = Line {
+        row: 1,
+        col: 2,
+        width: 10,
+    };
+    line.clone()
+        .render_middle_ellipsis("/home/user/file.rs", &mut backend);
+    line.render_middle_ellipsis("/tmp/a", &mut backend);
+    assert_eq!(
+        backend.drain(),
+        [
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "/home…e.rs".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "/tmp/a".to_owned()),
+            (MockedStyle::default(), "<<padding: 4>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn render_truncated_ellipsis() {
+    let mut backend = MockedBackend::init();
+    let line = Line {
+        row: 1,
+        col: 2,
+        width: 6,
+    };
+    // "テ" would fit, but has to make space for the ellipsis
+    line.clone()
+        .render_truncated_ellipsis("漢字テスト", '…', &mut backend);
+    line.clone()
+        .render_truncated_ellipsis("abcdefg", '…', &mut backend);
+    line.clone()
+        .render_truncated_ellipsis("漢字テ", '…', &mut backend);
+    Line { width: 1, ..line }.render_truncated_ellipsis("漢字", '＿', &mut backend);
+    assert_eq!(
+        backend.drain(),
+        [
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "漢字".to_owned()),
+            (MockedStyle::default(), "…".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "abcde".to_owned()),
+            (MockedStyle::default(), "…".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "漢字テ".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn zero_width_line_render() {
+    let mut backend = MockedBackend::init();
+    let line = Line {
+        row: 1,
+        col: 1,
+        width: 0,
+    };
+    line.clone().fill('x', &mut backend);
+    line.clone()
+        .fill_styled('x', MockedStyle::bold(), &mut backend);
+    line.clone().render_centered("abc", &mut backend);
+    line.clone()
+        .render_centered_styled("abc", MockedStyle::bold(), &mut backend);
+    line.clone().render_left("abc", &mut backend);
+    line.clone()
+        .render_left_styled("abc", MockedStyle::bold(), &mut backend);
+    line.clone().render_middle_ellipsis("abc", &mut backend);
+    line.clone()
+        .render_truncated_ellipsis("abc", '…', &mut backend);
+    line.clone().render_empty(&mut backend);
+    line.clone().render("abc", &mut backend);
+    line.render_styled("abc", MockedStyle::bold(), &mut backend);
+    assert!(backend.drain().is_empty());
+}
+
+#[test]
+fn zero_size_rect_borders_and_clear() {
+    let mut backend = MockedBackend::init();
+    let rect = Rect {
+        borders: Borders::ALL,
+        ..Default::default()
+    };
+    rect.draw_borders(None, None, &mut backend);
+    rect.clear(&mut backend);
+    // top left corner would wrap out of the screen
+    let mut rect = Rect::new(0, 0, 5, 5);
+    rect.borders = Borders::ALL;
+    rect.draw_borders(None, None, &mut backend);
+    Rect::new(2, 2, 0, 3).clear(&mut backend);
+    Rect::new(2, 2, 3, 0).clear(&mut backend);
+    assert!(backend.drain().is_empty());
+}
+
+#[test]
+fn split_even_horizontal() {
+    let rect = Rect::new(1, 2, 11, 3);
+    let columns = rect.split_even_horizontal(3);
+    assert_eq!(
+        columns,
+        [
+            Rect::new(1, 2, 4, 3),
+            Rect::new(1, 6, 4, 3),
+            Rect::new(1, 10, 3, 3),
+        ]
+    );
+    assert!(rect.split_even_horizontal(0).is_empty());
+    assert_eq!(rect.split_even_horizontal(1), [rect]);
+    // unlike split_cols borders are dropped and empty rects are still split
+    let bordered = Rect::new(0, 0, 10, 10).with_borders();
+    assert!(bordered
+        .split_even_horizontal(2)
+        .iter()
+        .all(|r| r.borders == Borders::empty()));
+    assert_eq!(
+        Rect::new(0, 0, 4, 0).split_even_horizontal(2),
+        [Rect::new(0, 0, 2, 0), Rect::new(0, 2, 2, 0)]
+    );
+}
+
+#[test]
+fn split_cols_rows() {
+    let rect = Rect::new(1, 2, 10, 3);
+    let columns = rect.split_cols(3);
+    assert_eq!(
+        columns.iter().map(|r| (r.col, r.width)).collect::<Vec<_>>(),
+        [(2, 4), (6, 3), (9, 3)]
+    );
+    assert!(columns.iter().all(|r| r.row == 1 && r.height == 3));
+    let bordered = Rect::new(0, 0, 10, 10).with_borders();
+    assert!(bordered
+        .split_cols(2)
+        .iter()
+        .all(|r| r.borders == Borders::ALL));
+    assert!(bordered
+        .split_rows(2)
+        .iter()
+        .all(|r| r.borders == Borders::ALL));
+    let rows = Rect::new(1, 2, 10, 5).split_rows(3);
+    assert_eq!(
+        rows.iter().map(|r| (r.row, r.height)).collect::<Vec<_>>(),
+        [(1, 2), (3, 2), (5, 1)]
+    );
+    assert!(rect.split_cols(0).is_empty());
+    assert!(rect.split_rows(0).is_empty());
+    assert!(Rect::new(0, 0, 0, 5).split_cols(2).is_empty());
+    assert!(Rect::new(0, 0, 5, 0).split_rows(2).is_empty());
+}
+
+#[test]
+fn grid() {
+    let grid = Rect::new(0, 0, 10, 5).grid(2, 3);
+    assert_eq!(grid.len(), 2);
+    assert_eq!(
+        grid[0],
+        [
+            Rect::new(0, 0, 4, 3),
+            Rect::new(0, 4, 3, 3),
+            Rect::new(0, 7, 3, 3),
+        ]
+    );
+    assert_eq!(
+        grid[1],
+        [
+            Rect::new(3, 0, 4, 2),
+            Rect::new(3, 4, 3, 2),
+            Rect::new(3, 7, 3, 2),
+        ]
+    );
+    assert!(Rect::new(0, 0, 10, 5).grid(0, 3).is_empty());
+    assert!(Rect::new(0, 0, 10, 5).grid(2, 0).is_empty());
+    assert!(Rect::new(0, 0, 0, 0).grid(2, 2).is_empty());
+}
+
+/// (last printed col + 1, printed width) from mocked output
+fn printed_span(data: &[(MockedStyle, String)]) -> (usize, usize) {
+    let mut col = 0;
+    let mut end = 0;
+    let mut total = 0;
+    for (_, text) in data {
+        let width = if let Some(go_to) = text.strip_prefix("<<go to row: ") {
+            col = go_to
+                .split("col: ")
+                .nth(1)
+                .unwrap()
+                .trim_end_matches(">>")
+                .parse()
+                .unwrap();
+            continue;
+        } else if let Some(padding) = text.strip_prefix("<<padding: ") {
+            padding.trim_end_matches(">>").parse().unwrap()
+        } else {
+            UTFSafe::width(text.as_str())
+        };
+        col += width;
+        total += width;
+        end = std::cmp::max(end, col);
+    }
+    (end, total)
+}
+
+#[test]
+fn builder_wide_char_boundary() {
+    let segments = ["a", "🚀", "b", "🚀🚀", "cd", "🚀"];
+    for width in 3..=6 {
+        for skip in 0..2 {
+            let mut backend = MockedBackend::init();
+            let line = Line {
+                row: 0,
+                col: 0,
+                width,
+            };
+            let mut builder = line.unsafe_builder(&mut backend);
+            for segment in segments.iter().skip(skip) {
+                if !builder.push(segment) {
+                    break;
+                }
+            }
+            drop(builder);
+            assert_eq!(
+                printed_span(&backend.drain()),
+                (width, width),
+                "width {width} skip {skip}"
+            );
+        }
+    }
+}
+
+#[test]
+fn builder_rev_wide_char_boundary() {
+    let segments = ["a", "🚀", "b", "🚀🚀", "cd", "🚀"];
+    for width in 3..=6 {
+        for skip in 0..2 {
+            let mut backend = MockedBackend::init();
+            let line = Line {
+                row: 0,
+                col: 0,
+                width,
+            };
+            let mut builder = line.unsafe_builder_rev(&mut backend);
+            for segment in segments.iter().skip(skip) {
+                if !builder.push_styled(segment, MockedStyle::bold()) {
+                    break;
+                }
+            }
+            drop(builder);
+            let data = backend.drain();
+            // text is right aligned and never printed past the line
+            let text_data: Vec<_> = data.into_iter().skip(2).collect();
+            let (end, printed) = printed_span(&text_data);
+            assert_eq!(end, width, "width {width} skip {skip}");
+            assert!(printed + 1 >= width, "width {width} skip {skip}");
+        }
+    }
+}
+
+#[test]
+fn split_with_constraints() {
+    let rect = Rect::new(1, 2, 40, 5);
+    let cells = rect.split_with(
+        Direction::Horizontal,
+        &[
+            Constraint::Length(3),
+            Constraint::Percentage(50),
+            Constraint::Fill(1),
+        ],
+    );
+    assert_eq!(
+        cells,
+        [
+            Rect::new(1, 2, 3, 5),
+            Rect::new(1, 5, 18, 5),
+            Rect::new(1, 23, 19, 5),
+        ]
+    );
+    let cells = rect.split_with(
+        Direction::Horizontal,
+        &[
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Fill(2),
+            Constraint::Min(5),
+        ],
+    );
+    assert_eq!(
+        cells.iter().map(|r| r.width).collect::<Vec<_>>(),
+        [8, 3, 16, 13]
+    );
+
+    // never exceeds parent
+    let cells = rect.split_with(
+        Direction::Vertical,
+        &[
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Percentage(200),
+            Constraint::Fill(1),
+        ],
+    );
+    assert_eq!(
+        cells.iter().map(|r| (r.row, r.height)).collect::<Vec<_>>(),
+        [(1, 3), (4, 2), (6, 0), (6, 0)]
+    );
+    assert!(rect.split_with(Direction::Vertical, &[]).is_empty());
+
+    // nothing grows - percentage rounding goes to the last cell
+    let cells = rect.split_with(
+        Direction::Horizontal,
+        &[
+            Constraint::Length(3),
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+        ],
+    );
+    assert_eq!(
+        cells.iter().map(|r| r.width).collect::<Vec<_>>(),
+        [3, 12, 25]
+    );
+}
+
+#[test]
+fn margin() {
+    let rect = Rect::new(2, 3, 10, 6).with_borders();
+    let inner = rect.margin(1, 2);
+    assert_eq!(
+        (inner.row, inner.col, inner.width, inner.height),
+        (4, 6, 4, 2)
+    );
+    assert_eq!(inner.borders, Borders::ALL);
+    assert_eq!(rect.inner(1), rect.margin(1, 1));
+
+    let rect = Rect::new(5, 5, 1, 1);
+    let inner = rect.margin(1, 1);
+    assert_eq!(
+        (inner.row, inner.col, inner.width, inner.height),
+        (5, 5, 0, 0)
+    );
+    let inner = rect.inner(u16::MAX);
+    assert_eq!((inner.width, inner.height), (0, 0));
+    assert_eq!(Rect::new(0, 0, 5, 3).inner(0), Rect::new(0, 0, 5, 3));
+}
+
+#[test]
+fn intersect_union() {
+    let left = Rect::new(2, 2, 10, 5).with_borders();
+    let right = Rect::new(4, 8, 10, 10);
+    assert_eq!(left.intersect(&right), Some(Rect::new(4, 8, 3, 2)));
+    assert_eq!(right.intersect(&left), left.intersect(&right));
+    assert_eq!(left.union(&right), Rect::new(3, 3, 15, 11));
+
+    // touching edges
+    let top = Rect::new(0, 0, 5, 3);
+    let bot = Rect::new(3, 0, 5, 3);
+    let side = Rect::new(0, 5, 5, 3);
+    assert_eq!(top.intersect(&bot), None);
+    assert_eq!(top.intersect(&side), None);
+    assert_eq!(top.union(&bot), Rect::new(0, 0, 5, 6));
+    assert_eq!(top.union(&side), Rect::new(0, 0, 10, 3));
+
+    // nested
+    let outer = Rect::new(0, 0, 20, 20);
+    let inner = Rect::new(5, 5, 3, 3);
+    assert_eq!(outer.intersect(&inner), Some(inner));
+    assert_eq!(inner.intersect(&outer), Some(inner));
+    assert_eq!(outer.union(&inner), outer);
+    assert_eq!(outer.intersect(&Rect::new(5, 5, 0, 3)), None);
+}
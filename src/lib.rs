@@ -1,12 +1,17 @@
+pub mod text_area;
 pub mod text_field;
 
 pub mod backend;
+pub mod input;
 pub mod layout;
 pub mod utils;
 pub mod widgets;
 
-pub use backend::Backend;
-pub use utils::{ByteChunks, CharLimitedWidths, StrChunks, UTFSafe, UTFSafeStringExt, WriteChunks};
+pub use backend::{prelude, Backend, CursorControl, ScreenControl, StyleControl, TextPrint};
+pub use utils::{
+    ByteChunks, CharLimitedWidths, GraphemeSafe, StrChunks, UTFSafe, UTFSafeStringExt, WordChunks,
+    WriteChunks, WriteChunksRev,
+};
 
 /// This can easily gorow to be a framework itself
 pub fn count_as_string(len: usize) -> String {
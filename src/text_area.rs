@@ -0,0 +1,896 @@
+use super::{
+    backend::{CursorControl, TextPrint},
+    layout::{IterLines, LineBuilder, Rect},
+    text_field::Status,
+    utils::{normalize_paste, Clipboard, PasteOptions, PasteTabs},
+    UTFSafe, UTFSafeStringExt,
+};
+use core::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+#[cfg(feature = "crossterm_backend")]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Location within TextArea - line idx and char idx within the line
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct CursorPosition {
+    pub line: usize,
+    pub char: usize,
+}
+
+impl CursorPosition {
+    pub const fn new(line: usize, char: usize) -> Self {
+        Self { line, char }
+    }
+}
+
+/// Multi line input field
+/// good for commit messages and small config editors
+/// shares Status and selection model with TextField (cursor is based on char idx)
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    cursor: CursorPosition,
+    select: Option<CursorPosition>,
+    at_line: usize,
+    /// column kept by vertical movement - valid while the cursor is where it was left
+    sticky_char: Option<(usize, CursorPosition)>,
+    wrap: bool,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+impl TextArea {
+    pub fn new(text: String) -> Self {
+        let lines: Vec<String> = text.lines().map(ToOwned::to_owned).collect();
+        let mut text_area = Self {
+            lines,
+            cursor: CursorPosition::default(),
+            select: None,
+            at_line: 0,
+            sticky_char: None,
+            wrap: false,
+        };
+        if text.is_empty() || text.ends_with('\n') {
+            text_area.lines.push(String::new());
+        }
+        text_area.cursor = text_area.end_position();
+        text_area
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// lines joined with "\n"
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    pub fn cursor(&self) -> CursorPosition {
+        self.cursor
+    }
+
+    pub fn select(&self) -> Option<(CursorPosition, CursorPosition)> {
+        self.select.map(|f| {
+            if f > self.cursor {
+                (self.cursor, f)
+            } else {
+                (f, self.cursor)
+            }
+        })
+    }
+
+    pub fn select_take(&mut self) -> Option<(CursorPosition, CursorPosition)> {
+        let select = self.select();
+        self.select = None;
+        select
+    }
+
+    pub fn select_drop(&mut self) -> Status {
+        match self.select.take() {
+            Some(..) => Status::UpdatedCursor,
+            None => Status::Skipped,
+        }
+    }
+
+    pub fn text_set(&mut self, text: String) {
+        *self = Self {
+            wrap: self.wrap,
+            ..Self::new(text)
+        };
+    }
+
+    pub fn text_take(&mut self) -> String {
+        let text = self.text();
+        self.text_set(String::new());
+        text
+    }
+
+    /// in wrap mode lines wider than the rect continue on the next row (otherwise clipped)
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    pub fn is_wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// position is clamped to existing text
+    pub fn cursor_set(&mut self, position: CursorPosition) -> Status {
+        let line = std::cmp::min(position.line, self.lines.len() - 1);
+        let char = std::cmp::min(position.char, self.lines[line].char_len());
+        let new_cursor = CursorPosition { line, char };
+        let status = self.select_drop();
+        if new_cursor == self.cursor {
+            return status;
+        }
+        self.cursor = new_cursor;
+        Status::UpdatedCursor
+    }
+
+    pub fn select_all(&mut self) -> Status {
+        let end = self.end_position();
+        let new_select = Some(CursorPosition::default());
+        if self.is_empty() || (self.cursor == end && self.select == new_select) {
+            return Status::Skipped;
+        }
+        self.select = new_select;
+        self.cursor = end;
+        Status::UpdatedCursor
+    }
+
+    // RENDER
+
+    /// renders lines clipped (or wrapped in wrap mode) to the rect width, scrolls to keep the cursor visible
+    pub fn widget<B: CursorControl>(
+        &mut self,
+        rect: Rect,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        let height = rect.height as usize;
+        if height == 0 || rect.width == 0 {
+            return;
+        }
+        if self.wrap {
+            self.widget_wrapped(rect, cursor_style, select_style, backend);
+            return;
+        }
+        if self.at_line > self.cursor.line {
+            self.at_line = self.cursor.line;
+        } else if self.cursor.line - self.at_line >= height {
+            self.at_line = self.cursor.line + 1 - height;
+        }
+        let select = self.select().filter(|(from, to)| from != to);
+        let mut lines = rect.into_iter();
+        for (idx, text) in self.lines.iter().enumerate().skip(self.at_line) {
+            let Some(line) = lines.next() else { break };
+            let select_range = select.and_then(|(from, to)| {
+                if idx < from.line || idx > to.line {
+                    return None;
+                }
+                let start = if idx == from.line { from.char } else { 0 };
+                let end = if idx == to.line { to.char } else { usize::MAX };
+                Some(start..end)
+            });
+            let cursor = match idx == self.cursor.line {
+                true => Some(self.cursor.char),
+                false => None,
+            };
+            let offset = match cursor {
+                Some(cursor) => width_offset(text, cursor, line.width),
+                None => 0,
+            };
+            render_line(
+                text,
+                offset,
+                select_range,
+                cursor,
+                (cursor_style.clone(), select_style.clone()),
+                line.unsafe_builder(backend),
+            );
+        }
+        lines.clear_to_end(backend);
+    }
+
+    fn widget_wrapped<B: CursorControl>(
+        &mut self,
+        rect: Rect,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        let height = rect.height as usize;
+        self.at_line = std::cmp::min(self.at_line, self.cursor.line);
+        let cursor_rows = self.wrap_rows(self.cursor.line, rect.width);
+        let cursor_row = cursor_rows
+            .iter()
+            .rposition(|row| row.start <= self.cursor.char)
+            .unwrap_or_default();
+        let mut rows_to_cursor = cursor_row
+            + 1
+            + (self.at_line..self.cursor.line)
+                .map(|idx| self.wrap_rows(idx, rect.width).len())
+                .sum::<usize>();
+        while rows_to_cursor > height && self.at_line < self.cursor.line {
+            rows_to_cursor -= self.wrap_rows(self.at_line, rect.width).len();
+            self.at_line += 1;
+        }
+        let skip_rows = rows_to_cursor.saturating_sub(height);
+        let select = self.select().filter(|(from, to)| from != to);
+        let mut lines = rect.into_iter();
+        for (idx, text) in self.lines.iter().enumerate().skip(self.at_line) {
+            let rows = self.wrap_rows(idx, rect.width);
+            let skip = if idx == self.at_line { skip_rows } else { 0 };
+            for (row_idx, row) in rows.iter().enumerate().skip(skip) {
+                let Some(line) = lines.next() else {
+                    return;
+                };
+                let select_range = select.and_then(|(from, to)| {
+                    if idx < from.line || idx > to.line {
+                        return None;
+                    }
+                    let start = if idx == from.line { from.char } else { 0 };
+                    let end = if idx == to.line { to.char } else { usize::MAX };
+                    Some(start.saturating_sub(row.start)..end.saturating_sub(row.start))
+                });
+                let is_last = row_idx + 1 == rows.len();
+                let cursor = match idx == self.cursor.line {
+                    true => Some(self.cursor.char)
+                        .filter(|char| row.contains(char) || (is_last && *char == row.end))
+                        .map(|char| char - row.start),
+                    false => None,
+                };
+                render_line(
+                    text.unchecked_get_char_range(row.start, row.end),
+                    0,
+                    select_range,
+                    cursor,
+                    (cursor_style.clone(), select_style.clone()),
+                    line.unsafe_builder(backend),
+                );
+            }
+        }
+        lines.clear_to_end(backend);
+    }
+
+    /// char ranges of rows for wrapped line (extra row for cursor at the end of full row)
+    fn wrap_rows(&self, line_idx: usize, width: usize) -> Vec<Range<usize>> {
+        let text = &self.lines[line_idx];
+        let mut rows = Vec::new();
+        let mut start = 0;
+        let mut row_width = 0;
+        let mut char_len = 0;
+        for (idx, ch) in text.chars().enumerate() {
+            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
+            if row_width + ch_width > width && idx != start {
+                rows.push(start..idx);
+                start = idx;
+                row_width = 0;
+            }
+            row_width += ch_width;
+            char_len += 1;
+        }
+        rows.push(start..char_len);
+        let cursor_at_end = self.cursor == CursorPosition::new(line_idx, char_len);
+        if cursor_at_end && row_width >= width {
+            rows.push(char_len..char_len);
+        }
+        rows
+    }
+
+    // CLIPBOARD LOGIC
+
+    pub fn copy(&self) -> Option<String> {
+        let (from, to) = self.select()?;
+        if from == to {
+            return None;
+        }
+        Some(self.get_text(from, to))
+    }
+
+    pub fn cut(&mut self) -> Option<String> {
+        let (from, to) = self.select_take()?;
+        if from == to {
+            return None;
+        }
+        let clip = self.get_text(from, to);
+        let tail = self.lines[to.line]
+            .unchecked_get_from_char(to.char)
+            .to_owned();
+        self.lines[from.line].replace_from_char(from.char, &tail);
+        self.lines.drain(from.line + 1..=to.line);
+        self.cursor = from;
+        Some(clip)
+    }
+
+    /// copies selection into clipboard (clipboard is not touched if nothing is copied)
+    pub fn copy_to(&self, clip: &mut impl Clipboard) {
+        if let Some(text) = self.copy() {
+            clip.set(&text);
+        }
+    }
+
+    /// pastes clipboard content keeping the lines (same as paste)
+    pub fn paste_from(&mut self, clip: &mut impl Clipboard) -> Status {
+        match clip.get() {
+            Some(text) => self.paste(text),
+            None => Status::Skipped,
+        }
+    }
+
+    /// multi line clips are split into lines ("\r\n" and "\r" are normalized)
+    pub fn paste(&mut self, clip: String) -> Status {
+        let opts = PasteOptions {
+            newline_to_space: false,
+            tabs: PasteTabs::Keep,
+        };
+        let clip = normalize_paste(&clip, opts);
+        self.cut();
+        let CursorPosition { mut line, char } = self.cursor;
+        let tail = self.lines[line].split_off_at_char(char);
+        let mut clip_lines = clip.split('\n');
+        let first = clip_lines.next().unwrap_or_default();
+        self.lines[line].push_str(first);
+        let mut end_char = char + first.char_len();
+        for clip_line in clip_lines {
+            line += 1;
+            self.lines.insert(line, clip_line.to_owned());
+            end_char = clip_line.char_len();
+        }
+        self.lines[line].push_str(&tail);
+        self.cursor = CursorPosition::new(line, end_char);
+        Status::Updated
+    }
+
+    // EDITS
+
+    pub fn push_char(&mut self, ch: char) -> Status {
+        if ch == '\n' {
+            return self.new_line();
+        }
+        self.cut();
+        self.lines[self.cursor.line].insert_at_char(self.cursor.char, ch);
+        self.cursor.char += 1;
+        Status::Updated
+    }
+
+    /// splits the line at cursor
+    pub fn new_line(&mut self) -> Status {
+        self.cut();
+        let CursorPosition { line, char } = self.cursor;
+        let new_line = self.lines[line].split_off_at_char(char);
+        self.lines.insert(line + 1, new_line);
+        self.cursor = CursorPosition::new(line + 1, 0);
+        Status::Updated
+    }
+
+    pub fn del(&mut self) -> Status {
+        if self.cut().is_some() {
+            return Status::Updated;
+        }
+        let CursorPosition { line, char } = self.cursor;
+        if char < self.lines[line].char_len() {
+            self.lines[line].remove_at_char(char);
+            Status::Updated
+        } else if line + 1 < self.lines.len() {
+            let next_line = self.lines.remove(line + 1);
+            self.lines[line].push_str(&next_line);
+            Status::Updated
+        } else {
+            Status::Skipped
+        }
+    }
+
+    pub fn backspace(&mut self) -> Status {
+        if self.cut().is_some() {
+            return Status::Updated;
+        }
+        let CursorPosition { line, char } = self.cursor;
+        if char > 0 {
+            self.cursor.char -= 1;
+            self.lines[line].remove_at_char(char - 1);
+            Status::Updated
+        } else if line > 0 {
+            let removed = self.lines.remove(line);
+            self.cursor = CursorPosition::new(line - 1, self.lines[line - 1].char_len());
+            self.lines[line - 1].push_str(&removed);
+            Status::Updated
+        } else {
+            Status::Skipped
+        }
+    }
+
+    // MOVEMENT
+
+    pub fn start_of_line(&mut self) -> Status {
+        if self.cursor.char == 0 && self.select.is_none() {
+            return Status::Skipped;
+        }
+        self.cursor.char = 0;
+        self.select = None;
+        Status::UpdatedCursor
+    }
+
+    pub fn end_of_line(&mut self) -> Status {
+        let line_len = self.lines[self.cursor.line].char_len();
+        if self.cursor.char == line_len && self.select.is_none() {
+            return Status::Skipped;
+        }
+        self.cursor.char = line_len;
+        self.select = None;
+        Status::UpdatedCursor
+    }
+
+    pub fn go_left(&mut self) -> Status {
+        self.select_drop() + self.prev_char()
+    }
+
+    pub fn select_left(&mut self) -> Status {
+        self.init_select() + self.prev_char()
+    }
+
+    pub fn go_right(&mut self) -> Status {
+        self.select_drop() + self.next_char()
+    }
+
+    pub fn select_right(&mut self) -> Status {
+        self.init_select() + self.next_char()
+    }
+
+    pub fn go_up(&mut self) -> Status {
+        self.select_drop() + self.prev_line()
+    }
+
+    pub fn select_up(&mut self) -> Status {
+        self.init_select() + self.prev_line()
+    }
+
+    pub fn go_down(&mut self) -> Status {
+        self.select_drop() + self.next_line()
+    }
+
+    pub fn select_down(&mut self) -> Status {
+        self.init_select() + self.next_line()
+    }
+
+    fn prev_char(&mut self) -> Status {
+        if self.cursor.char > 0 {
+            self.cursor.char -= 1;
+        } else if self.cursor.line > 0 {
+            self.cursor.line -= 1;
+            self.cursor.char = self.lines[self.cursor.line].char_len();
+        } else {
+            return Status::Skipped;
+        }
+        Status::UpdatedCursor
+    }
+
+    fn next_char(&mut self) -> Status {
+        if self.cursor.char < self.lines[self.cursor.line].char_len() {
+            self.cursor.char += 1;
+        } else if self.cursor.line + 1 < self.lines.len() {
+            self.cursor = CursorPosition::new(self.cursor.line + 1, 0);
+        } else {
+            return Status::Skipped;
+        }
+        Status::UpdatedCursor
+    }
+
+    fn prev_line(&mut self) -> Status {
+        if self.cursor.line == 0 {
+            return Status::Skipped;
+        }
+        let sticky_char = self.sticky_char();
+        self.cursor.line -= 1;
+        self.stick_char(sticky_char);
+        Status::UpdatedCursor
+    }
+
+    fn next_line(&mut self) -> Status {
+        if self.cursor.line + 1 >= self.lines.len() {
+            return Status::Skipped;
+        }
+        let sticky_char = self.sticky_char();
+        self.cursor.line += 1;
+        self.stick_char(sticky_char);
+        Status::UpdatedCursor
+    }
+
+    /// desired column - kept only if the cursor was not moved since the last vertical move
+    #[inline]
+    fn sticky_char(&self) -> usize {
+        match self.sticky_char {
+            Some((char, position)) if position == self.cursor => char,
+            _ => self.cursor.char,
+        }
+    }
+
+    #[inline]
+    fn stick_char(&mut self, sticky_char: usize) {
+        self.cursor.char = std::cmp::min(sticky_char, self.lines[self.cursor.line].char_len());
+        self.sticky_char = Some((sticky_char, self.cursor));
+    }
+
+    fn init_select(&mut self) -> Status {
+        if self.select.is_some() {
+            return Status::Skipped;
+        }
+        self.select = Some(self.cursor);
+        Status::UpdatedCursor
+    }
+
+    fn end_position(&self) -> CursorPosition {
+        let line = self.lines.len() - 1;
+        CursorPosition::new(line, self.lines[line].char_len())
+    }
+
+    fn get_text(&self, from: CursorPosition, to: CursorPosition) -> String {
+        if from.line == to.line {
+            return self.lines[from.line]
+                .unchecked_get_char_range(from.char, to.char)
+                .to_owned();
+        }
+        let mut clip = self.lines[from.line]
+            .unchecked_get_from_char(from.char)
+            .to_owned();
+        for line in self.lines[from.line + 1..to.line].iter() {
+            clip.push('\n');
+            clip.push_str(line);
+        }
+        clip.push('\n');
+        clip.push_str(self.lines[to.line].unchecked_get_to_char(to.char));
+        clip
+    }
+}
+
+#[cfg(feature = "crossterm_backend")]
+impl TextArea {
+    /// Maps crossterm key events
+    /// if None is returned the key is not mapped at all
+    /// Copy / Cut / Paste logic is not included -> use copy / cut / paste instead
+    pub fn map(&mut self, key: KeyEvent) -> Option<Status> {
+        let select = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('a' | 'A') if key.modifiers == KeyModifiers::CONTROL => {
+                Some(self.select_all())
+            }
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(self.push_char(ch))
+            }
+            KeyCode::Enter => Some(self.new_line()),
+            KeyCode::Delete => Some(self.del()),
+            KeyCode::Backspace => Some(self.backspace()),
+            KeyCode::Home => Some(self.start_of_line()),
+            KeyCode::End => Some(self.end_of_line()),
+            KeyCode::Left if select => Some(self.select_left()),
+            KeyCode::Left => Some(self.go_left()),
+            KeyCode::Right if select => Some(self.select_right()),
+            KeyCode::Right => Some(self.go_right()),
+            KeyCode::Up if select => Some(self.select_up()),
+            KeyCode::Up => Some(self.go_up()),
+            KeyCode::Down if select => Some(self.select_down()),
+            KeyCode::Down => Some(self.go_down()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum CellKind {
+    Plain,
+    Selected,
+    Cursor,
+}
+
+/// first visible char of the cursor line - keeps the cursor cell within width
+fn width_offset(text: &str, cursor: usize, max_width: usize) -> usize {
+    let mut width = text
+        .chars()
+        .nth(cursor)
+        .and_then(UnicodeWidthChar::width)
+        .unwrap_or(1);
+    let mut offset = cursor;
+    for ch in text.unchecked_get_to_char(cursor).chars().rev() {
+        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if width + ch_width > max_width {
+            break;
+        }
+        width += ch_width;
+        offset -= 1;
+    }
+    offset
+}
+
+fn render_line<B: CursorControl>(
+    text: &str,
+    offset: usize,
+    select: Option<Range<usize>>,
+    cursor: Option<usize>,
+    styles: (<B as TextPrint>::Style, <B as TextPrint>::Style),
+    mut builder: LineBuilder<B>,
+) {
+    let (cursor_style, select_style) = styles;
+    let kind = |idx: usize| {
+        if Some(idx) == cursor {
+            CellKind::Cursor
+        } else if select.as_ref().is_some_and(|select| select.contains(&idx)) {
+            CellKind::Selected
+        } else {
+            CellKind::Plain
+        }
+    };
+    let visible = text.unchecked_get_from_char(offset);
+    let mut push = |text: &str, kind: CellKind| match kind {
+        CellKind::Plain => builder.push(text),
+        CellKind::Selected => builder.push_styled(text, select_style.clone()),
+        CellKind::Cursor => builder.push_styled(text, cursor_style.clone()),
+    };
+    let mut run: Option<(usize, CellKind)> = None;
+    for (char_idx, (byte_idx, _)) in (offset..).zip(visible.char_indices()) {
+        let char_kind = kind(char_idx);
+        if let Some((run_start, run_kind)) = run {
+            if run_kind == char_kind {
+                continue;
+            }
+            if !push(&visible[run_start..byte_idx], run_kind) {
+                return;
+            }
+        }
+        run = Some((byte_idx, char_kind));
+    }
+    if let Some((run_start, run_kind)) = run {
+        if !push(&visible[run_start..], run_kind) {
+            return;
+        }
+    }
+    if cursor == Some(offset + visible.char_len()) {
+        push(" ", CellKind::Cursor);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CursorPosition, TextArea};
+    use crate::backend::{MockedBackend, MockedStyle, ScreenControl, StyleExt};
+    use crate::layout::Rect;
+    use crate::text_field::Status;
+
+    #[cfg(feature = "crossterm_backend")]
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn edits() {
+        let mut area = TextArea::new("first\nsecond".to_owned());
+        assert_eq!(area.cursor(), CursorPosition::new(1, 6));
+        area.cursor_set(CursorPosition::new(0, 2));
+        assert_eq!(area.new_line(), Status::Updated);
+        assert_eq!(area.lines(), ["fi", "rst", "second"]);
+        assert_eq!(area.cursor(), CursorPosition::new(1, 0));
+        assert_eq!(area.backspace(), Status::Updated);
+        assert_eq!(area.lines(), ["first", "second"]);
+        assert_eq!(area.cursor(), CursorPosition::new(0, 2));
+        area.end_of_line();
+        assert_eq!(area.del(), Status::Updated);
+        assert_eq!(area.text(), "firstsecond");
+        area.push_char('\n');
+        area.push_char('🦀');
+        assert_eq!(area.lines(), ["first", "🦀second"]);
+        area.text_set(String::new());
+        assert!(area.is_empty());
+        assert_eq!(area.backspace(), Status::Skipped);
+        assert_eq!(area.del(), Status::Skipped);
+        assert_eq!(TextArea::new("a\n".to_owned()).lines(), ["a", ""]);
+    }
+
+    #[test]
+    fn navigation() {
+        let mut area = TextArea::new("long line\nab\nlonger line".to_owned());
+        area.cursor_set(CursorPosition::new(0, 7));
+        assert_eq!(area.go_down(), Status::UpdatedCursor);
+        assert_eq!(area.cursor(), CursorPosition::new(1, 2));
+        area.go_down();
+        assert_eq!(area.cursor(), CursorPosition::new(2, 7));
+        assert_eq!(area.go_down(), Status::Skipped);
+        area.start_of_line();
+        area.go_left();
+        assert_eq!(area.cursor(), CursorPosition::new(1, 2));
+        area.go_right();
+        assert_eq!(area.cursor(), CursorPosition::new(2, 0));
+        area.cursor_set(CursorPosition::new(10, 50));
+        assert_eq!(area.cursor(), CursorPosition::new(2, 11));
+    }
+
+    #[test]
+    fn select_copy_cut_paste() {
+        let mut area = TextArea::new("one\ntwo\nthree".to_owned());
+        area.cursor_set(CursorPosition::new(0, 1));
+        area.select_down();
+        area.select_down();
+        area.select_left();
+        assert_eq!(
+            area.select(),
+            Some((CursorPosition::new(0, 1), CursorPosition::new(2, 0)))
+        );
+        assert_eq!(area.copy().as_deref(), Some("ne\ntwo\n"));
+        assert_eq!(area.cut().as_deref(), Some("ne\ntwo\n"));
+        assert_eq!(area.lines(), ["othree"]);
+        assert_eq!(area.cursor(), CursorPosition::new(0, 1));
+
+        assert_eq!(area.paste("1\r\n22\n333".to_owned()), Status::Updated);
+        assert_eq!(area.lines(), ["o1", "22", "333three"]);
+        assert_eq!(area.cursor(), CursorPosition::new(2, 3));
+
+        // paste replaces selection
+        area.select_all();
+        area.paste("x".to_owned());
+        assert_eq!(area.lines(), ["x"]);
+        assert_eq!(area.copy(), None);
+    }
+
+    #[test]
+    fn widget() {
+        let mut area = TextArea::new("ab\ncd\nef".to_owned());
+        let mut backend = MockedBackend::init();
+        area.cursor_set(CursorPosition::new(0, 1));
+        area.select_down();
+        area.widget(
+            Rect::new(0, 0, 4, 2),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), "a".to_owned()),
+                (MockedStyle::bold(), "b".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+                (MockedStyle::bold(), "c".to_owned()),
+                (MockedStyle::reversed(), "d".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            ]
+        );
+
+        // scrolls to the cursor, cursor at the end of line
+        area.go_down();
+        area.end_of_line();
+        area.widget(
+            Rect::new(0, 0, 4, 2),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), "cd".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+                (MockedStyle::default(), "ef".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn widget_clips_cursor_line() {
+        let mut area = TextArea::new("0123456789\n0123456789".to_owned());
+        let mut backend = MockedBackend::init();
+        area.widget(
+            Rect::new(0, 0, 4, 2),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), "0123".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+                (MockedStyle::default(), "789".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map() {
+        let mut area = TextArea::default();
+        for ch in "ab".chars() {
+            area.map(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        area.map(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        area.map(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
+        area.map(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT));
+        assert_eq!(area.copy().as_deref(), Some("b\nc"));
+        area.map(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+        assert_eq!(area.cursor(), CursorPosition::new(0, 0));
+        assert_eq!(area.text(), "ab\nc");
+    }
+
+    #[test]
+    fn sticky_column() {
+        let mut area = TextArea::new("long line\nab\n\nlonger line".to_owned());
+        area.cursor_set(CursorPosition::new(0, 7));
+        area.go_down();
+        assert_eq!(area.cursor(), CursorPosition::new(1, 2));
+        area.go_down();
+        assert_eq!(area.cursor(), CursorPosition::new(2, 0));
+        area.go_down();
+        assert_eq!(area.cursor(), CursorPosition::new(3, 7));
+        area.select_up();
+        area.select_up();
+        assert_eq!(area.cursor(), CursorPosition::new(1, 2));
+        // horizontal move resets the column
+        area.go_left();
+        area.go_up();
+        assert_eq!(area.cursor(), CursorPosition::new(0, 1));
+    }
+
+    #[test]
+    fn widget_wrapped() {
+        let mut area = TextArea::new("abcdefg\nhi".to_owned());
+        area.set_wrap(true);
+        area.cursor_set(CursorPosition::new(0, 4));
+        let mut backend = MockedBackend::init();
+        area.widget(
+            Rect::new(0, 0, 3, 4),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), "abc".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+                (MockedStyle::default(), "d".to_owned()),
+                (MockedStyle::reversed(), "e".to_owned()),
+                (MockedStyle::default(), "f".to_owned()),
+                (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+                (MockedStyle::default(), "g".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+                (MockedStyle::default(), "<<go to row: 3 col: 0>>".to_owned()),
+                (MockedStyle::default(), "hi".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+
+        // scrolls by rows - cursor at the end of full row gets own row
+        area.text_set("abcdef\nhi\nxyz".to_owned());
+        area.widget(
+            Rect::new(0, 0, 3, 2),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), "xyz".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            ]
+        );
+    }
+}
@@ -1,13 +1,20 @@
-use super::{backend::Backend, UTFSafe};
+use super::{
+    backend::{CursorControl, StyleControl, TextPrint},
+    utils::{normalize_paste, Clipboard, GraphemeSafe, PasteOptions},
+    UTFSafe, UTFSafeStringExt,
+};
 use core::ops::{Add, AddAssign, Range};
+use std::{borrow::Cow, cell::Cell};
 use unicode_width::UnicodeWidthChar;
 
 #[cfg(feature = "crossterm_backend")]
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
 use super::{
     count_as_string,
+    input::{self, ClickKind, Key, KeyMod},
     layout::{Line, LineBuilder},
+    Position,
 };
 
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -51,13 +58,231 @@ impl AddAssign for Status {
     }
 }
 
+/// Last frame rendered by TextField::widget_incremental
+/// should be reset if the screen was cleared or overwritten
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct FieldRenderState {
+    text: String,
+    line: Option<Line>,
+    offset: usize,
+    /// None if the cursor was not painted as single cell (selection / real cursor)
+    cursor: Option<usize>,
+}
+
+impl FieldRenderState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// next render will be full
+    pub fn reset(&mut self) {
+        self.line = None;
+        self.cursor = None;
+    }
+}
+
+const HISTORY_LIMIT: usize = 100;
+
+#[derive(Default, Debug, PartialEq, Clone)]
+struct Snapshot {
+    text: String,
+    char: usize,
+    select: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum EditKind {
+    Insert,
+    Backspace,
+    Other,
+}
+
+/// bounded undo / redo stacks of the field state before each edit
+#[derive(Default, Debug, PartialEq, Clone)]
+struct EditHistory {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// last edit kind and cursor after it - next edit of the same kind at the cursor is grouped
+    last_edit: Option<(EditKind, usize)>,
+}
+
+impl EditHistory {
+    fn push(&mut self, snapshot: Snapshot) {
+        if self.undo.len() == HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push(snapshot);
+    }
+}
+
+/// committed inputs recalled with history_prev / history_next
+#[derive(Default, Debug, PartialEq, Clone)]
+struct InputHistory {
+    entries: Vec<String>,
+    capacity: usize,
+    /// idx of displayed entry - None if the in-progress text is displayed
+    position: Option<usize>,
+    /// in-progress text while browsing entries
+    stash: String,
+}
+
+/// last byte idx -> char idx conversion (valid until text is changed)
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct CharIdxCache {
+    char: usize,
+    select: Option<usize>,
+    char_idx: usize,
+    select_idx: Option<usize>,
+}
+
+/// prefix rendered by widget
+const PREFIX: &str = " >> ";
+
+/// geometry of the line last rendered by the field widgets -
+/// cursor and click helpers map text idxs to screen cols with it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct LineLayout {
+    prefix_width: usize,
+    /// rendered by widget_with_markers - left marker takes a col before the text
+    markers: bool,
+}
+
+impl Default for LineLayout {
+    fn default() -> Self {
+        Self {
+            prefix_width: PREFIX.width(),
+            markers: false,
+        }
+    }
+}
+
+/// holder of cached / render derived values - equal regardless of the content
+/// (cache state is not part of the field state)
+#[derive(Debug, Default, Clone)]
+struct CacheCell<T: Copy>(Cell<T>);
+
+impl<T: Copy> PartialEq for CacheCell<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T: Copy> std::ops::Deref for CacheCell<T> {
+    type Target = Cell<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// chars considered part of a word by jumps (Ctrl+Left / Ctrl+Right) and word deletes
+#[derive(Default, Debug, Clone, Copy)]
+pub enum WordBoundary {
+    /// letters and digits
+    #[default]
+    Alphanumeric,
+    /// letters, digits and underscore
+    Identifier,
+    /// everything except white space
+    NonWhitespace,
+    /// user defined - returns true for word chars
+    Custom(fn(char) -> bool),
+}
+
+impl PartialEq for WordBoundary {
+    /// compares only the variant - custom predicates are all equal (fn addresses are not reliable)
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl Eq for WordBoundary {}
+
+impl WordBoundary {
+    #[inline]
+    pub fn is_word_char(&self, ch: char) -> bool {
+        match self {
+            Self::Alphanumeric => should_jump(ch),
+            Self::Identifier => ch == '_' || should_jump(ch),
+            Self::NonWhitespace => !ch.is_whitespace(),
+            Self::Custom(is_word_char) => is_word_char(ch),
+        }
+    }
+}
+
+/// chars rendered by TextField::widget_with_markers at the edges of clipped text
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OverflowMarkers {
+    pub left: char,
+    pub right: char,
+}
+
+impl Default for OverflowMarkers {
+    fn default() -> Self {
+        Self {
+            left: '<',
+            right: '>',
+        }
+    }
+}
+
+/// key layout used by TextField::apply / map
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum KeyMap {
+    #[default]
+    Default,
+    /// readline style - see TextField::apply_emacs
+    Emacs,
+}
+
+/// input filter and validator of TextField (only presence is compared - fn addresses are not reliable)
+#[derive(Debug, Default, Clone, Copy)]
+struct InputRules {
+    char_filter: Option<fn(char) -> bool>,
+    validator: Option<fn(&str) -> bool>,
+}
+
+impl PartialEq for InputRules {
+    fn eq(&self, other: &Self) -> bool {
+        self.char_filter.is_some() == other.char_filter.is_some()
+            && self.validator.is_some() == other.validator.is_some()
+    }
+}
+
 /// Single line input field
 /// good for search boxes and filters
-#[derive(Default, Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct TextField {
     text: String,
     char: usize,
     select: Option<usize>,
+    overwrite: bool,
+    real_cursor: bool,
+    /// rendered instead of each grapheme (text is kept as is)
+    mask: Option<char>,
+    allow_copy: bool,
+    /// max chars count accepted by input
+    max_chars: Option<usize>,
+    /// user input is ignored (text_set still works)
+    readonly: bool,
+    history: EditHistory,
+    input_history: Option<InputHistory>,
+    char_idx_cache: CacheCell<Option<CharIdxCache>>,
+    /// recorded on render - see LineLayout
+    layout: CacheCell<LineLayout>,
+    word_boundary: WordBoundary,
+    rules: InputRules,
+    /// bumped once per text mutation
+    revision: u64,
+    keymap: KeyMap,
+    /// last text removed by kill_to_end / kill_to_start
+    kill_buffer: String,
+}
+
+impl Default for TextField {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
 }
 
 impl TextField {
@@ -66,9 +291,174 @@ impl TextField {
             char: text.len(),
             text,
             select: None,
+            overwrite: false,
+            real_cursor: false,
+            mask: None,
+            allow_copy: true,
+            max_chars: None,
+            readonly: false,
+            history: EditHistory::default(),
+            input_history: None,
+            char_idx_cache: CacheCell::default(),
+            layout: CacheCell::default(),
+            word_boundary: WordBoundary::default(),
+            rules: InputRules::default(),
+            revision: 0,
+            keymap: KeyMap::default(),
+            kill_buffer: String::new(),
+        }
+    }
+
+    /// restores field from (text, cursor, select) in char idxs (see as_parts),
+    /// idxs are clamped to text end, empty select is dropped and
+    /// cursor not on select edge is moved to select end
+    pub fn from_parts(text: String, cursor_char: usize, select: Option<(usize, usize)>) -> Self {
+        let mut field = Self::new(text);
+        field.char = field.char_to_byte(cursor_char);
+        if let Some((from_char, to_char)) = select {
+            let from = field.char_to_byte(from_char.min(to_char));
+            let to = field.char_to_byte(from_char.max(to_char));
+            if from != to {
+                if field.char == from {
+                    field.select = Some(to);
+                } else {
+                    field.char = to;
+                    field.select = Some(from);
+                }
+            }
+        }
+        field
+    }
+
+    /// (text, cursor, select) in char idxs - select is ordered and None if empty
+    pub fn as_parts(&self) -> (&str, usize, Option<(usize, usize)>) {
+        let select = self.select_chars().filter(|(from, to)| from != to);
+        (&self.text, self.cursor_char(), select)
+    }
+
+    pub fn into_parts(self) -> (String, usize, Option<(usize, usize)>) {
+        let (_, cursor, select) = self.as_parts();
+        (self.text, cursor, select)
+    }
+
+    /// password field - renders mask per grapheme, copy / cut are disabled
+    pub fn masked(mask: char) -> Self {
+        Self {
+            mask: Some(mask),
+            allow_copy: false,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_mask(&mut self, mask: Option<char>) {
+        self.mask = mask;
+    }
+
+    pub fn mask(&self) -> Option<char> {
+        self.mask
+    }
+
+    /// if false copy / cut return None and the text is not changed
+    pub fn set_allow_copy(&mut self, allow_copy: bool) {
+        self.allow_copy = allow_copy;
+    }
+
+    pub fn is_copy_allowed(&self) -> bool {
+        self.allow_copy
+    }
+
+    /// input exceeding the limit is refused (or truncated on paste / token replace),
+    /// text already in the field is not changed
+    pub fn set_max_chars(&mut self, max_chars: Option<usize>) {
+        self.max_chars = max_chars;
+    }
+
+    pub fn max_chars(&self) -> Option<usize> {
+        self.max_chars
+    }
+
+    /// same as set_max_chars(Some(limit))
+    pub fn set_char_limit(&mut self, limit: usize) {
+        self.set_max_chars(Some(limit));
+    }
+
+    /// in readonly mode edits (typing, deletes, paste, cut, undo) are skipped,
+    /// navigation, select and copy still work
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// typed chars not passing the filter are skipped, pasted text is stripped of them
+    pub fn set_char_filter(&mut self, char_filter: Option<fn(char) -> bool>) {
+        self.rules.char_filter = char_filter;
+    }
+
+    pub fn char_filter(&self) -> Option<fn(char) -> bool> {
+        self.rules.char_filter
+    }
+
+    /// used by is_valid / widget_validated - does not block the input
+    pub fn set_validator(&mut self, validator: Option<fn(&str) -> bool>) {
+        self.rules.validator = validator;
+    }
+
+    pub fn validator(&self) -> Option<fn(&str) -> bool> {
+        self.rules.validator
+    }
+
+    /// true if there is no validator or the text passes it
+    pub fn is_valid(&self) -> bool {
+        match self.rules.validator {
+            Some(validator) => validator(&self.text),
+            None => true,
         }
     }
 
+    /// layout used by apply / map
+    pub fn set_keymap(&mut self, keymap: KeyMap) {
+        self.keymap = keymap;
+    }
+
+    pub fn keymap(&self) -> KeyMap {
+        self.keymap
+    }
+
+    pub fn set_word_boundary(&mut self, word_boundary: WordBoundary) {
+        self.word_boundary = word_boundary;
+    }
+
+    pub fn word_boundary(&self) -> WordBoundary {
+        self.word_boundary
+    }
+
+    /// in overwrite mode typing replaces the char under the cursor
+    pub fn set_overwrite(&mut self, overwrite: bool) {
+        self.overwrite = overwrite;
+    }
+
+    pub fn is_overwrite(&self) -> bool {
+        self.overwrite
+    }
+
+    pub fn toggle_overwrite(&mut self) -> Status {
+        self.overwrite = !self.overwrite;
+        Status::UpdatedCursor
+    }
+
+    /// in real cursor mode the widget does not paint the cursor cell,
+    /// the caller should place the terminal cursor at cursor_screen_position
+    pub fn set_real_cursor(&mut self, real_cursor: bool) {
+        self.real_cursor = real_cursor;
+    }
+
+    pub fn is_real_cursor(&self) -> bool {
+        self.real_cursor
+    }
+
     pub fn cursor(&self) -> usize {
         self.char
     }
@@ -100,6 +490,51 @@ impl TextField {
         }
     }
 
+    /// cursor as char idx (cursor returns byte idx)
+    pub fn cursor_char(&self) -> usize {
+        self.char_indices().char_idx
+    }
+
+    /// select as char idxs (select returns byte idxs)
+    pub fn select_chars(&self) -> Option<(usize, usize)> {
+        let cache = self.char_indices();
+        let select_idx = cache.select_idx?;
+        Some(match select_idx > cache.char_idx {
+            true => (cache.char_idx, select_idx),
+            false => (select_idx, cache.char_idx),
+        })
+    }
+
+    /// converts cursor / select byte idxs, rescans only text between the old and new positions
+    fn char_indices(&self) -> CharIdxCache {
+        let prev = self.char_idx_cache.get();
+        if let Some(cache) = prev {
+            if cache.char == self.char && cache.select == self.select {
+                return cache;
+            }
+        }
+        let to_char_idx = |byte_idx: usize| match prev {
+            Some(cache) if cache.char <= byte_idx => {
+                cache.char_idx + self.text[cache.char..byte_idx].char_len()
+            }
+            _ => self.text[..byte_idx].char_len(),
+        };
+        let cache = CharIdxCache {
+            char: self.char,
+            select: self.select,
+            char_idx: to_char_idx(self.char),
+            select_idx: self.select.map(to_char_idx),
+        };
+        self.char_idx_cache.set(Some(cache));
+        cache
+    }
+
+    /// counter bumped once on each call changing the text (including undo / redo) -
+    /// cheap way to detect changes between frames
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
     pub fn as_str(&self) -> &str {
         self.text.as_str()
     }
@@ -117,33 +552,77 @@ impl TextField {
     }
 
     pub fn text_set(&mut self, text: String) {
-        self.select = None;
-        self.text = text;
-        self.char = self.text.len();
+        self.edit(EditKind::Other, |field| {
+            field.select = None;
+            field.text = text;
+            field.char = field.text.len();
+            Status::Updated
+        });
     }
 
+    /// sets cursor by byte idx, clamped to text len and snapped back to char boundary
     pub fn cursor_set(&mut self, new_char: usize) -> Status {
+        let mut new_char = new_char.min(self.text.len());
+        while !self.text.is_char_boundary(new_char) {
+            new_char -= 1;
+        }
         self.select_drop()
-            + if self.text.len() < new_char {
-                match self.char == self.text.len() {
-                    true => Status::Skipped,
-                    false => {
-                        self.char = self.text.len();
-                        Status::UpdatedCursor
-                    }
+            + match new_char == self.char {
+                true => Status::Skipped,
+                false => {
+                    self.char = new_char;
+                    Status::UpdatedCursor
                 }
-            } else if new_char == self.char {
-                Status::Skipped
-            } else {
-                self.char = new_char;
-                Status::UpdatedCursor
             }
     }
 
+    /// sets cursor by char idx, clamped to text end
+    pub fn cursor_set_char(&mut self, char_idx: usize) -> Status {
+        self.cursor_set(self.char_to_byte(char_idx))
+    }
+
+    /// selects from char idx to char idx (cursor is placed on `to`, reversed range is allowed),
+    /// indexes are clamped to text end - empty range is skipped
+    pub fn set_select(&mut self, from_char: usize, to_char: usize) -> Status {
+        let from = self.char_to_byte(from_char);
+        let to = self.char_to_byte(to_char);
+        if from == to {
+            return Status::Skipped;
+        }
+        if self.select == Some(from) && self.char == to {
+            return Status::Skipped;
+        }
+        self.select = Some(from);
+        self.char = to;
+        Status::UpdatedCursor
+    }
+
+    /// selected text without cloning (None if nothing is selected)
+    pub fn selection_str(&self) -> Option<&str> {
+        let (from, to) = self.select()?;
+        if from == to {
+            return None;
+        }
+        Some(&self.text[from..to])
+    }
+
+    /// byte idx of char idx, clamped to text end
+    fn char_to_byte(&self, char_idx: usize) -> usize {
+        match self.text.char_indices().nth(char_idx) {
+            Some((byte_idx, _)) => byte_idx,
+            None => self.text.len(),
+        }
+    }
+
     pub fn text_take(&mut self) -> String {
-        self.char = 0;
-        self.select = None;
-        std::mem::take(&mut self.text)
+        let mut text = String::new();
+        self.edit(EditKind::Other, |field| {
+            field.char = 0;
+            field.select = None;
+            text = std::mem::take(&mut field.text);
+            Status::Updated
+        });
+        text
     }
 
     pub fn select_token_at_cursor(&mut self) -> Status {
@@ -160,153 +639,663 @@ impl TextField {
         Status::UpdatedCursor
     }
 
+    /// selects word around the cursor - chars outside the field word boundary split words
+    pub fn select_word_at_cursor(&mut self) -> Status {
+        let word_range = word_range_at(&self.text, self.char, self.word_boundary);
+        if word_range.is_empty() {
+            return Status::Skipped;
+        }
+        let new_select = Some(word_range.start);
+        if self.select == new_select && self.char == word_range.end {
+            return Status::Skipped;
+        }
+        self.select = new_select;
+        self.char = word_range.end;
+        Status::UpdatedCursor
+    }
+
     pub fn get_token_at_cursor(&self) -> Option<&str> {
         let token_range = arg_range_at(&self.text, self.char);
         self.text.get(token_range)
     }
 
     pub fn replace_token(&mut self, new: &str) {
+        if self.readonly {
+            return;
+        }
         let token_range = arg_range_at(&self.text, self.char);
-        self.char = new.len() + token_range.start;
-        self.select = None;
-        self.text.replace_range(token_range, new);
+        let new = self.fitting_prefix(new, token_range);
+        self.edit(EditKind::Other, |field| {
+            let token_range = arg_range_at(&field.text, field.char);
+            field.char = new.len() + token_range.start;
+            field.select = None;
+            field.text.replace_range(token_range, new);
+            Status::Updated
+        });
+    }
+
+    /// replaces text in char range (clamped to text end), cursor is placed after the inserted text
+    /// and select is dropped - replacing text with the same text is skipped
+    pub fn replace_char_range(&mut self, range: Range<usize>, new: &str) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        let char_len = self.char_len();
+        let end = range.end.min(char_len);
+        let start = range.start.min(end);
+        let (from, to) = (self.char_to_byte(start), self.char_to_byte(end));
+        let new = self.fitting_prefix(new, from..to);
+        if self.text[from..to] == *new {
+            return Status::Skipped;
+        }
+        self.edit(EditKind::Other, |field| {
+            field.text.replace_char_range(start..end, new);
+            field.char = from + new.len();
+            field.select = None;
+            Status::Updated
+        })
     }
 
     // RENDER
 
     /// returns blockless paragraph widget " >> inner text"
-    pub fn widget<B: Backend>(
+    pub fn widget<B: CursorControl>(
         &self,
         line: Line,
-        cursor_style: <B as Backend>::Style,
-        select_style: <B as Backend>::Style,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
-        let mut builder = line.unsafe_builder(backend);
-        builder.push(" >> ");
-        self.insert_formatted_text(builder, cursor_style, select_style);
+        self.widget_with_prefix(line, PREFIX, None, cursor_style, select_style, backend);
     }
 
-    /// returns blockless paragraph widget "99+ >> inner text"
-    pub fn widget_with_count<B: Backend>(
+    /// same as widget, but the prefix and text are rendered in text style,
+    /// cursor and select styles are merged over it
+    pub fn widget_styled<B: CursorControl + StyleControl>(
         &self,
         line: Line,
-        count: usize,
-        cursor_style: <B as Backend>::Style,
-        select_style: <B as Backend>::Style,
+        text_style: <B as TextPrint>::Style,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
+        let cursor_style = B::merge_style(text_style.clone(), cursor_style);
+        let select_style = B::merge_style(text_style.clone(), select_style);
+        self.layout.set(LineLayout::default());
         let mut builder = line.unsafe_builder(backend);
-        builder.push(count_as_string(count).as_str());
-        builder.push(" >> ");
-        self.insert_formatted_text(builder, cursor_style, select_style);
+        builder.push_styled(PREFIX, text_style.clone());
+        self.insert_text(builder, Some(text_style), cursor_style, select_style);
     }
 
-    pub fn insert_formatted_text<B: Backend>(
+    /// same as widget, but the prefix is rendered in error style if the text is not valid
+    pub fn widget_validated<B: CursorControl>(
         &self,
-        line_builder: LineBuilder<B>,
-        cursor_style: <B as Backend>::Style,
-        select_style: <B as Backend>::Style,
+        line: Line,
+        error_style: <B as TextPrint>::Style,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
     ) {
-        if line_builder.width() == 0 {
-            return;
-        }
-        match self.select() {
-            Some((from, to)) if from != to => {
-                self.text_cursor_select(from, to, cursor_style, select_style, line_builder)
-            }
-            _ => self.text_cursor(cursor_style, line_builder),
+        let prefix_style = match self.is_valid() {
+            true => None,
+            false => Some(error_style),
         };
+        self.widget_with_prefix(
+            line,
+            PREFIX,
+            prefix_style,
+            cursor_style,
+            select_style,
+            backend,
+        );
     }
 
-    fn text_cursor<B: Backend>(
+    /// returns blockless paragraph widget with custom prefix "{prefix}inner text"
+    /// (prefix width is kept for cursor / click helpers until the next render)
+    pub fn widget_with_prefix<B: CursorControl>(
         &self,
-        cursor_style: <B as Backend>::Style,
-        mut builder: LineBuilder<B>,
+        line: Line,
+        prefix: &str,
+        prefix_style: Option<<B as TextPrint>::Style>,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
     ) {
-        let offset = self.calculate_width_offset(builder.width());
-        match self.get_cursor_range() {
-            Some(cursor) => {
-                let Range { start, end } = cursor;
-                builder.push(&self.text[offset..start]);
-                builder.push_styled(&self.text[cursor], cursor_style);
-                builder.push(&self.text[end..]);
-            }
-            None => {
-                builder.push(&self.text[offset..]);
-                builder.push_styled(" ", cursor_style);
-            }
-        }
+        self.layout.set(LineLayout {
+            prefix_width: prefix.width(),
+            markers: false,
+        });
+        let mut builder = line.unsafe_builder(backend);
+        match prefix_style {
+            Some(style) => builder.push_styled(prefix, style),
+            None => builder.push(prefix),
+        };
+        self.insert_formatted_text(builder, cursor_style, select_style);
     }
 
-    fn text_cursor_select<B: Backend>(
+    /// same as widget, but without cursor and select highlights - for fields that are not focused
+    pub fn widget_unfocused<B: CursorControl>(
         &self,
-        mut from: usize,
-        to: usize,
-        cursor_style: <B as Backend>::Style,
-        select_style: <B as Backend>::Style,
-        mut builder: LineBuilder<B>,
+        line: Line,
+        text_style: <B as TextPrint>::Style,
+        backend: &mut B,
     ) {
-        let offset = self.calculate_width_offset(builder.width());
-        if offset < from {
-            builder.push(self.text[offset..from].as_ref());
-        } else {
-            from = offset;
+        self.layout.set(LineLayout::default());
+        if let Some(view) = self.masked_view() {
+            return view.widget_unfocused(line, text_style, backend);
         }
-        match self.get_cursor_range() {
-            Some(cursor) => {
-                let Range { start, end } = cursor;
-                if from == cursor.start {
-                    builder.push_styled(&self.text[cursor], cursor_style);
-                    builder.push_styled(&self.text[end..to], select_style);
-                    builder.push(&self.text[to..]);
-                } else {
-                    builder.push_styled(&self.text[from..start], select_style);
-                    builder.push_styled(&self.text[cursor], cursor_style);
-                    builder.push(&self.text[end..]);
-                }
-            }
-            None => {
-                builder.push_styled(&self.text[from..], select_style);
-                builder.push_styled(" ", cursor_style);
-            }
+        let mut builder = line.unsafe_builder(backend);
+        builder.push(PREFIX);
+        if builder.width() == 0 {
+            return;
         }
+        let offset = self.calculate_width_offset(builder.width());
+        builder.push_styled(&self.text[offset..], text_style);
     }
 
-    fn calculate_width_offset(&self, max_width: usize) -> usize {
-        // in all cases byte index is greater than column width
-        // so if avail width is bigger it is safe to skip offset
-        // in most cases at least one char after cursor will be visible
-        // in some using very strange chaars (over 3 cols - it could have visual artefacts)
-        if self.char + 1 < max_width {
-            return 0;
-        }
-        let cursor_prefix = &self.text[..self.char];
-        let mut cursor_prefix_w = cursor_prefix.width() + 2;
-        for (offset, ch) in cursor_prefix.char_indices() {
-            if max_width > cursor_prefix_w {
-                return offset;
-            }
-            if let Some(ch_width) = ch.width() {
-                cursor_prefix_w = cursor_prefix_w.saturating_sub(ch_width);
+    /// same output as widget, but if only the cursor moved within the same visible window
+    /// re-prints only the previous and the new cursor cells
+    pub fn widget_incremental<B: CursorControl>(
+        &self,
+        line: Line,
+        prev: &mut FieldRenderState,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        self.layout.set(LineLayout::default());
+        if let Some(view) = self.masked_view() {
+            return view.widget_incremental(line, prev, cursor_style, select_style, backend);
+        }
+        let (offset, _) = self.text_start(line.width);
+        let cursor = match self.select() {
+            Some((from, to)) if from != to => None,
+            _ if self.real_cursor => None,
+            _ => Some(self.char),
+        };
+        if let (Some(prev_cursor), Some(cursor)) = (prev.cursor, cursor) {
+            if prev.line.as_ref() == Some(&line) && prev.offset == offset && prev.text == self.text
+            {
+                if prev_cursor == cursor {
+                    return;
+                }
+                let prev_cell = self.cursor_cell(&line, offset, prev_cursor);
+                let cell = self.cursor_cell(&line, offset, cursor);
+                if let (Some((prev_col, prev_text)), Some((col, text))) = (prev_cell, cell) {
+                    backend.print_at(line.row, prev_col, prev_text);
+                    backend.print_styled_at(line.row, col, text, cursor_style);
+                    prev.cursor = Some(cursor);
+                    return;
+                }
             }
         }
-        self.char
+        self.widget(line.clone(), cursor_style, select_style, backend);
+        prev.text.clear();
+        prev.text.push_str(&self.text);
+        prev.line = Some(line);
+        prev.offset = offset;
+        prev.cursor = cursor;
+    }
+
+    /// screen col and content of cursor cell (full char - wide chars take multiple cols)
+    fn cursor_cell(&self, line: &Line, offset: usize, cursor: usize) -> Option<(u16, &str)> {
+        let prefix_width = self.layout.get().prefix_width;
+        let max_width = line.width.checked_sub(prefix_width)?;
+        let cursor_width = self.text.get(offset..cursor)?.width();
+        let cell = match self.text[cursor..].chars().next() {
+            Some(ch) => &self.text[cursor..cursor + ch.len_utf8()],
+            None => " ",
+        };
+        if cursor_width + cell.width() > max_width {
+            return None;
+        }
+        Some((line.col + (prefix_width + cursor_width) as u16, cell))
+    }
+
+    /// screen cell of the insertion point within line rendered by widget
+    pub fn cursor_screen_position(&self, line: &Line) -> Option<Position> {
+        let col = self.cursor_display_col(line.width);
+        if col >= line.width {
+            return None;
+        }
+        Some(Position {
+            row: line.row,
+            col: line.col + col as u16,
+        })
+    }
+
+    /// col of the cursor relative to the start of line rendered by widget (prefix and scroll included),
+    /// real cursor can be placed with render_cursor_at(line.row, line.col + col)
+    pub fn cursor_display_col(&self, available_width: usize) -> usize {
+        if let Some(view) = self.masked_view() {
+            return view.cursor_display_col(available_width);
+        }
+        let (offset, text_col) = self.text_start(available_width);
+        text_col + self.text[offset..self.char].width()
+    }
+
+    /// byte offset of the first visible char and col (relative to the line start) where it is rendered
+    /// on line of width rendered by the last widget (prefix and left marker are skipped)
+    fn text_start(&self, line_width: usize) -> (usize, usize) {
+        let layout = self.layout.get();
+        let width = line_width.saturating_sub(layout.prefix_width);
+        if layout.markers && width >= 4 {
+            let (offset, _, left, _) = self.marker_window(width);
+            return (offset, layout.prefix_width + left as usize);
+        }
+        (self.calculate_width_offset(width), layout.prefix_width)
+    }
+
+    /// places cursor on the char under the screen position within line rendered by widget
+    /// (clicks past the end go to the end of text, clicks on the prefix to the first visible char)
+    pub fn click_at(&mut self, position: Position, line: &Line) -> Status {
+        let Some(new_char) = self.idx_at_position(position, line) else {
+            return Status::Skipped;
+        };
+        self.select_drop()
+            + match new_char == self.char {
+                true => Status::Skipped,
+                false => {
+                    self.char = new_char;
+                    Status::UpdatedCursor
+                }
+            }
+    }
+
+    /// selects token (as select_token_at_cursor) rendered at col relative to the line start
+    /// (as rendered by widget) - clicks on white space or past the end are skipped
+    pub fn select_token_at_col(&mut self, display_col: usize, line: &Line) -> Status {
+        if display_col >= line.width {
+            return Status::Skipped;
+        }
+        let idx = self.text_idx_at_col(display_col, line.width);
+        match self.text[idx..].chars().next() {
+            Some(ch) if !ch.is_whitespace() => (),
+            _ => return Status::Skipped,
+        }
+        let token_range = arg_range_at(&self.text, idx);
+        self.select = Some(token_range.start);
+        self.char = token_range.end;
+        Status::UpdatedCursor
+    }
+
+    /// handles mouse event (already classified) on the line rendered by widget -
+    /// single click places the cursor (as click_at), double selects token, triple selects all,
+    /// drag extends select
+    pub fn handle_mouse(&mut self, event: input::MouseEvent, line: &Line) -> Status {
+        let position = Position {
+            row: event.row,
+            col: event.column,
+        };
+        let Some(new_char) = self.idx_at_position(position, line) else {
+            return Status::Skipped;
+        };
+        match event.kind {
+            ClickKind::Single => self.click_at(position, line),
+            ClickKind::Double => self.click_at(position, line) + self.select_token_at_cursor(),
+            ClickKind::Triple => self.select_all(),
+            ClickKind::Drag if new_char == self.char => Status::Skipped,
+            ClickKind::Drag => {
+                self.init_select();
+                self.char = new_char;
+                Status::UpdatedCursor
+            }
+        }
+    }
+
+    /// byte idx of char under the screen position (None if outside the line)
+    fn idx_at_position(&self, position: Position, line: &Line) -> Option<usize> {
+        if !line.contains_position(position.row, position.col) {
+            return None;
+        }
+        Some(self.text_idx_at_col((position.col - line.col) as usize, line.width))
+    }
+
+    /// byte idx in text of char rendered at col (handles masked fields)
+    fn text_idx_at_col(&self, rel_col: usize, line_width: usize) -> usize {
+        match self.masked_view() {
+            Some(view) => {
+                let mask_len = self.mask.map(char::len_utf8).unwrap_or(1);
+                let grapheme = view.char_at_col(rel_col, line_width) / mask_len;
+                self.text.split_at_grapheme(grapheme).0.len()
+            }
+            None => self.char_at_col(rel_col, line_width),
+        }
+    }
+
+    /// byte idx of char rendered at col relative to the line start
+    fn char_at_col(&self, rel_col: usize, line_width: usize) -> usize {
+        let (offset, text_col) = self.text_start(line_width);
+        let mut text_col = rel_col.saturating_sub(text_col);
+        for (idx, ch) in self.text[offset..].char_indices() {
+            let ch_width = ch.width().unwrap_or_default();
+            if text_col < ch_width {
+                return offset + idx;
+            }
+            text_col -= ch_width;
+        }
+        self.text.len()
+    }
+
+    /// returns blockless paragraph widget "99+ >> inner text"
+    pub fn widget_with_count<B: CursorControl>(
+        &self,
+        line: Line,
+        count: usize,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        let count = count_as_string(count);
+        self.layout.set(LineLayout {
+            prefix_width: count.width() + PREFIX.width(),
+            markers: false,
+        });
+        let mut builder = line.unsafe_builder(backend);
+        builder.push(count.as_str());
+        builder.push(PREFIX);
+        self.insert_formatted_text(builder, cursor_style, select_style);
+    }
+
+    pub fn insert_formatted_text<B: CursorControl>(
+        &self,
+        line_builder: LineBuilder<B>,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+    ) {
+        self.insert_text(line_builder, None, cursor_style, select_style);
+    }
+
+    /// text not selected and not under cursor is pushed with text style (if any)
+    fn insert_text<B: CursorControl>(
+        &self,
+        mut line_builder: LineBuilder<B>,
+        text_style: Option<<B as TextPrint>::Style>,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+    ) {
+        if line_builder.width() == 0 {
+            return;
+        }
+        if let Some(view) = self.masked_view() {
+            return view.insert_text(line_builder, text_style, cursor_style, select_style);
+        }
+        let offset = self.calculate_width_offset(line_builder.width());
+        let visible = offset..self.text.len();
+        self.insert_visible_text(
+            visible,
+            text_style,
+            cursor_style,
+            select_style,
+            &mut line_builder,
+        );
+    }
+
+    /// same as widget, but clipped text is marked at the edges of the line (in marker style),
+    /// left marker if the text is scrolled and right marker if the text continues past the line end
+    /// markers are expected to be single col chars (narrow lines are rendered without markers)
+    pub fn widget_with_markers<B: CursorControl>(
+        &self,
+        line: Line,
+        markers: OverflowMarkers,
+        marker_style: <B as TextPrint>::Style,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        self.layout.set(LineLayout {
+            prefix_width: PREFIX.width(),
+            markers: true,
+        });
+        if let Some(view) = self.masked_view() {
+            return view.widget_with_markers(
+                line,
+                markers,
+                marker_style,
+                cursor_style,
+                select_style,
+                backend,
+            );
+        }
+        let mut builder = line.unsafe_builder(backend);
+        builder.push(PREFIX);
+        let width = builder.width();
+        if width < 4 {
+            return self.insert_formatted_text(builder, cursor_style, select_style);
+        }
+        let (offset, text_width, left, right) = self.marker_window(width);
+        let mut buffer = [0; 4];
+        if left {
+            builder.push_styled(markers.left.encode_utf8(&mut buffer), marker_style.clone());
+        }
+        let (_, visible_text) = self.text[offset..].truncate_width(text_width);
+        let visible = offset..offset + visible_text.len();
+        self.insert_visible_text(visible, None, cursor_style, select_style, &mut builder);
+        if right {
+            // wide char not fitting the text leaves a gap before the last col
+            let gap = builder.width().saturating_sub(1);
+            if gap != 0 {
+                builder.push(&" ".repeat(gap));
+            }
+            builder.push_styled(markers.right.encode_utf8(&mut buffer), marker_style);
+        }
+    }
+
+    /// (offset, text width, left marker, right marker) of text rendered with markers in width -
+    /// markers take cols from the text, so offset is recalculated until they are stable
+    fn marker_window(&self, width: usize) -> (usize, usize, bool, bool) {
+        let (mut left, mut right) = (false, false);
+        loop {
+            let text_width = width - left as usize - right as usize;
+            let offset = self.calculate_width_offset(text_width);
+            let cursor_cell = (self.char == self.text.len()) as usize;
+            let overflow = self.text[offset..].width() + cursor_cell > text_width;
+            if (left || offset != 0, right || overflow) == (left, right) {
+                return (offset, text_width, left, right);
+            }
+            left |= offset != 0;
+            right |= overflow;
+        }
+    }
+
+    /// renders visible range of the text with cursor and select
+    fn insert_visible_text<B: CursorControl>(
+        &self,
+        visible: Range<usize>,
+        text_style: Option<<B as TextPrint>::Style>,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        builder: &mut LineBuilder<B>,
+    ) {
+        match self.select() {
+            Some((from, to)) if from != to => self.text_cursor_select(
+                from..to,
+                visible,
+                text_style,
+                cursor_style,
+                select_style,
+                builder,
+            ),
+            _ => self.text_cursor(visible, text_style, cursor_style, builder),
+        };
+    }
+
+    fn text_cursor<B: CursorControl>(
+        &self,
+        visible: Range<usize>,
+        text_style: Option<<B as TextPrint>::Style>,
+        cursor_style: <B as TextPrint>::Style,
+        builder: &mut LineBuilder<B>,
+    ) {
+        let Range {
+            start: offset,
+            end: visible_end,
+        } = visible;
+        let push = |builder: &mut LineBuilder<B>, text: &str| match text_style.clone() {
+            Some(style) => builder.push_styled(text, style),
+            None => builder.push(text),
+        };
+        if self.real_cursor {
+            push(builder, &self.text[visible]);
+            return;
+        }
+        match self.get_cursor_range() {
+            Some(cursor) => {
+                let Range { start, end } = cursor;
+                push(builder, &self.text[offset..start]);
+                builder.push_styled(&self.text[cursor], cursor_style);
+                push(builder, &self.text[end..visible_end]);
+            }
+            None => {
+                push(builder, &self.text[visible]);
+                builder.push_styled(" ", cursor_style);
+            }
+        }
+    }
+
+    /// renders visible parts of (pre, selected, post) ranges in order,
+    /// cursor cell is cut out of the range containing it
+    fn text_cursor_select<B: CursorControl>(
+        &self,
+        select: Range<usize>,
+        visible: Range<usize>,
+        text_style: Option<<B as TextPrint>::Style>,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+        builder: &mut LineBuilder<B>,
+    ) {
+        let cursor = match self.real_cursor {
+            true => None,
+            false => self.get_cursor_range(),
+        };
+        let mut push = |range: Range<usize>, style: Option<<B as TextPrint>::Style>| {
+            let range = range.start.max(visible.start)..range.end.min(visible.end);
+            if range.is_empty() {
+                return;
+            }
+            match style {
+                Some(style) => builder.push_styled(&self.text[range], style),
+                None => builder.push(&self.text[range]),
+            };
+        };
+        let parts = [
+            (0..select.start, text_style.clone()),
+            (select.start..select.end, Some(select_style)),
+            (select.end..self.text.len(), text_style),
+        ];
+        for (range, style) in parts {
+            match cursor.clone() {
+                Some(cursor) if range.contains(&cursor.start) => {
+                    push(range.start..cursor.start, style.clone());
+                    push(cursor.clone(), Some(cursor_style.clone()));
+                    push(cursor.end..range.end, style);
+                }
+                _ => push(range, style),
+            }
+        }
+        if cursor.is_none() && !self.real_cursor {
+            builder.push_styled(" ", cursor_style);
+        }
+    }
+
+    /// display copy of masked field - each grapheme is replaced by the mask,
+    /// cursor and select are moved to the matching mask chars
+    fn masked_view(&self) -> Option<Self> {
+        let mask = self.mask?;
+        let to_masked = |idx: usize| self.text[..idx].grapheme_len() * mask.len_utf8();
+        Some(Self {
+            text: std::iter::repeat(mask)
+                .take(self.text.grapheme_len())
+                .collect(),
+            char: to_masked(self.char),
+            select: self.select.map(to_masked),
+            overwrite: self.overwrite,
+            real_cursor: self.real_cursor,
+            mask: None,
+            allow_copy: false,
+            max_chars: None,
+            readonly: false,
+            history: EditHistory::default(),
+            input_history: None,
+            char_idx_cache: CacheCell::default(),
+            layout: CacheCell(Cell::new(self.layout.get())),
+            word_boundary: self.word_boundary,
+            rules: InputRules::default(),
+            revision: self.revision,
+            keymap: self.keymap,
+            kill_buffer: String::new(),
+        })
+    }
+
+    /// byte offset (on char boundary) of the first visible char, based on display width
+    /// keeps 3 cols for the cursor cell (up to 2 for wide char) and the next one,
+    /// or only the cursor cell if the cursor is at the end of the text,
+    /// so the cursor is always visible (narrower fields start at the cursor)
+    fn calculate_width_offset(&self, max_width: usize) -> usize {
+        let reserved = match self.char == self.text.len() {
+            true => 1,
+            false => 3,
+        };
+        let Some(prefix_width) = max_width.checked_sub(reserved) else {
+            return self.char;
+        };
+        let (_, visible_prefix) = self.text[..self.char].truncate_width_start(prefix_width);
+        self.char - visible_prefix.len()
     }
 
     // CLIPBOARD LOGIC
 
     pub fn paste_passthrough(&mut self, clip: String) -> Status {
-        if clip.contains('\n') {
+        self.insert_str_at_cursor(&clip)
+    }
+
+    /// replaces selection (if any) and inserts text at cursor, moving the cursor after it;
+    /// text containing new lines is rejected (use paste_flattened / paste_sanitized to normalize it)
+    pub fn insert_str_at_cursor(&mut self, text: &str) -> Status {
+        if self.readonly || text.contains('\n') {
             return Status::default();
         };
-        self.cut();
-        self.text.insert_str(self.char, clip.as_str());
-        self.char += clip.len();
-        Status::Updated
+        let text = self.filter_chars(text);
+        let replaced = self.select().map(|(from, to)| from..to).unwrap_or_default();
+        let text = self.fitting_prefix(&text, replaced);
+        if text.is_empty() {
+            return Status::Skipped;
+        }
+        self.edit(EditKind::Other, |field| {
+            field.cut_select();
+            field.text.insert_str(field.char, text);
+            field.char += text.len();
+            Status::Updated
+        })
+    }
+
+    /// removes chars not passing the char filter
+    fn filter_chars<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.rules.char_filter {
+            Some(filter) if !text.chars().all(filter) => {
+                Cow::Owned(text.chars().filter(|ch| filter(*ch)).collect())
+            }
+            _ => Cow::Borrowed(text),
+        }
+    }
+
+    /// normalizes the clip before paste (default options replace new lines with spaces)
+    pub fn paste_sanitized(&mut self, clip: String, opts: PasteOptions) -> Status {
+        match normalize_paste(&clip, opts) {
+            Cow::Borrowed(..) => self.paste_passthrough(clip),
+            Cow::Owned(normalized) => self.paste_passthrough(normalized),
+        }
+    }
+
+    /// pastes multi line clip as single line (each line break is replaced by single space)
+    pub fn paste_flattened(&mut self, clip: String) -> Status {
+        self.paste_sanitized(clip, PasteOptions::default())
     }
 
     pub fn copy(&mut self) -> Option<String> {
+        if !self.allow_copy {
+            return None;
+        }
         let (from, to) = self.select()?;
         if from == to {
             return None;
@@ -315,6 +1304,64 @@ impl TextField {
     }
 
     pub fn cut(&mut self) -> Option<String> {
+        if !self.allow_copy || self.readonly {
+            return None;
+        }
+        let mut clip = None;
+        self.edit(EditKind::Other, |field| {
+            clip = field.cut_select();
+            match clip {
+                Some(..) => Status::Updated,
+                None => Status::Skipped,
+            }
+        });
+        clip
+    }
+
+    /// copies selection into clipboard (clipboard is not touched if nothing is copied)
+    pub fn copy_to(&mut self, clip: &mut impl Clipboard) {
+        if let Some(text) = self.copy() {
+            clip.set(&text);
+        }
+    }
+
+    pub fn cut_to(&mut self, clip: &mut impl Clipboard) -> Status {
+        match self.cut() {
+            Some(text) => {
+                clip.set(&text);
+                Status::Updated
+            }
+            None => Status::Skipped,
+        }
+    }
+
+    /// pastes clipboard content as single line (same as paste_flattened)
+    pub fn paste_from(&mut self, clip: &mut impl Clipboard) -> Status {
+        match clip.get() {
+            Some(text) => self.paste_flattened(text),
+            None => Status::Skipped,
+        }
+    }
+
+    /// copies selection to system clipboard (nothing happens if clipboard is not available)
+    #[cfg(feature = "arboard_clipboard")]
+    pub fn copy_to_clipboard(&mut self) {
+        crate::utils::with_system_clipboard(|clip| self.copy_to(clip));
+    }
+
+    /// cuts selection to system clipboard (Skipped and text is kept if clipboard is not available)
+    #[cfg(feature = "arboard_clipboard")]
+    pub fn cut_to_clipboard(&mut self) -> Status {
+        crate::utils::with_system_clipboard(|clip| self.cut_to(clip)).unwrap_or(Status::Skipped)
+    }
+
+    /// pastes system clipboard content as single line (Skipped if clipboard is not available)
+    #[cfg(feature = "arboard_clipboard")]
+    pub fn paste_from_clipboard(&mut self) -> Status {
+        crate::utils::with_system_clipboard(|clip| self.paste_from(clip)).unwrap_or(Status::Skipped)
+    }
+
+    fn cut_select(&mut self) -> Option<String> {
         let (from, to) = self.select_take()?;
         if from == to {
             return None;
@@ -357,78 +1404,370 @@ impl TextField {
     }
 
     pub fn push_char(&mut self, ch: char) -> Status {
-        self.cut();
-        self.text.insert(self.char, ch);
-        self.char += ch.len_utf8();
+        if self.readonly || self.rules.char_filter.is_some_and(|filter| !filter(ch)) {
+            return Status::Skipped;
+        }
+        // typed words are grouped - white space after a word starts new undo step
+        let word_end = ch.is_whitespace()
+            && self.text[..self.char]
+                .chars()
+                .next_back()
+                .is_some_and(|prev| !prev.is_whitespace());
+        let kind = match word_end {
+            true => EditKind::Other,
+            false => EditKind::Insert,
+        };
+        let replaced = match self.select() {
+            Some((from, to)) => from..to,
+            None if self.overwrite => self.get_cursor_range().unwrap_or_default(),
+            None => Range::default(),
+        };
+        if self.fitting_prefix("_", replaced).is_empty() {
+            return Status::Skipped;
+        }
+        self.edit(kind, |field| {
+            if field.cut_select().is_none() && field.overwrite && field.char < field.text.len() {
+                field.text.remove(field.char);
+            }
+            field.text.insert(field.char, ch);
+            field.char += ch.len_utf8();
+            Status::Updated
+        });
+        if word_end {
+            self.history.last_edit = Some((EditKind::Insert, self.char));
+        }
         Status::Updated
     }
 
     pub fn del(&mut self) -> Status {
-        if self.cut().is_some() {
-            Status::Updated
-        } else if self.char < self.text.len() && !self.text.is_empty() {
-            self.text.remove(self.char);
-            Status::Updated
-        } else {
-            Status::Skipped
+        if self.readonly {
+            return Status::Skipped;
         }
+        self.edit(EditKind::Other, |field| {
+            if field.cut_select().is_some() {
+                Status::Updated
+            } else if field.char < field.text.len() && !field.text.is_empty() {
+                field.text.remove(field.char);
+                Status::Updated
+            } else {
+                Status::Skipped
+            }
+        })
     }
 
     pub fn backspace(&mut self) -> Status {
-        if self.cut().is_some() {
-            Status::Updated
-        } else if self.char > 0 && !self.text.is_empty() {
-            self.prev_char();
-            self.text.remove(self.char);
-            Status::Updated
-        } else {
-            Status::Skipped
+        if self.readonly {
+            return Status::Skipped;
         }
+        self.edit(EditKind::Backspace, |field| {
+            if field.cut_select().is_some() {
+                Status::Updated
+            } else if field.char > 0 && !field.text.is_empty() {
+                field.prev_char();
+                field.text.remove(field.char);
+                Status::Updated
+            } else {
+                Status::Skipped
+            }
+        })
     }
 
-    pub fn go_left(&mut self) -> Status {
-        self.select_drop() + self.prev_char()
+    /// removes chars from the cursor to the start of the word (or the selection)
+    pub fn del_word_left(&mut self) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        self.edit(EditKind::Other, |field| {
+            if field.cut_select().is_some() {
+                return Status::Updated;
+            }
+            let end = field.char;
+            if field.prev_char() == Status::Skipped {
+                return Status::Skipped;
+            }
+            field.jump_left_move();
+            field.text.replace_range(field.char..end, "");
+            Status::Updated
+        })
     }
 
-    pub fn select_left(&mut self) -> Status {
-        self.init_select() + self.prev_char()
+    /// removes chars from the cursor to the end of the word (or the selection)
+    pub fn del_word_right(&mut self) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        self.edit(EditKind::Other, |field| {
+            if field.cut_select().is_some() {
+                return Status::Updated;
+            }
+            let start = field.char;
+            if field.next_char() == Status::Skipped {
+                return Status::Skipped;
+            }
+            field.jump_right_move();
+            field.text.replace_range(start..field.char, "");
+            field.char = start;
+            Status::Updated
+        })
     }
 
-    pub fn jump_left(&mut self) -> Status {
-        self.select_drop() + self.prev_char() + self.jump_left_move()
+    /// removes text from the cursor to the end of line, returns the removed text (select is dropped)
+    pub fn kill_to_end(&mut self) -> (String, Status) {
+        if self.readonly {
+            return (String::new(), Status::Skipped);
+        }
+        let mut killed = String::new();
+        let status = self.edit(EditKind::Other, |field| {
+            killed = field.text.split_off(field.char);
+            field.select_drop()
+                + match killed.is_empty() {
+                    true => Status::Skipped,
+                    false => Status::Updated,
+                }
+        });
+        self.stash_killed(&killed);
+        (killed, status)
     }
 
-    pub fn select_jump_left(&mut self) -> Status {
-        self.init_select() + self.prev_char() + self.jump_left_move()
+    /// removes text from the start of line to the cursor, returns the removed text (select is dropped)
+    pub fn kill_to_start(&mut self) -> (String, Status) {
+        if self.readonly {
+            return (String::new(), Status::Skipped);
+        }
+        let mut killed = String::new();
+        let status = self.edit(EditKind::Other, |field| {
+            killed = field.text.drain(..field.char).collect();
+            field.char = 0;
+            field.select_drop()
+                + match killed.is_empty() {
+                    true => Status::Skipped,
+                    false => Status::Updated,
+                }
+        });
+        self.stash_killed(&killed);
+        (killed, status)
     }
 
-    pub fn go_right(&mut self) -> Status {
-        self.select_drop() + self.next_char()
+    /// inserts the last killed text at cursor (replacing selection)
+    pub fn yank(&mut self) -> Status {
+        let killed = std::mem::take(&mut self.kill_buffer);
+        let status = self.insert_str_at_cursor(&killed);
+        self.kill_buffer = killed;
+        status
     }
 
-    pub fn select_right(&mut self) -> Status {
-        self.init_select() + self.next_char()
+    /// last text removed by kill_to_end / kill_to_start (kept empty if copy is not allowed)
+    pub fn kill_buffer(&self) -> &str {
+        &self.kill_buffer
     }
 
-    pub fn jump_right(&mut self) -> Status {
-        self.select_drop() + self.next_char() + self.jump_right_move()
+    fn stash_killed(&mut self, killed: &str) {
+        if self.allow_copy && !killed.is_empty() {
+            self.kill_buffer = killed.to_owned();
+        }
     }
 
-    pub fn select_jump_right(&mut self) -> Status {
-        self.init_select() + self.next_char() + self.jump_right_move()
+    // HISTORY
+
+    /// keeps up to capacity committed inputs (Up / Down in map browse them)
+    pub fn enable_history(&mut self, capacity: usize) {
+        self.input_history = Some(InputHistory {
+            capacity,
+            ..Default::default()
+        });
     }
 
-    fn get_cursor_range(&self) -> Option<Range<usize>> {
-        let cursor_char = self.text[self.char..].chars().next()?;
-        Some(self.char..self.char + cursor_char.len_utf8())
+    pub fn is_history_enabled(&self) -> bool {
+        self.input_history.is_some()
     }
 
-    fn next_char(&mut self) -> Status {
-        match self.text[self.char..]
-            .chars()
-            .next()
-            .map(|ch| ch.len_utf8())
-        {
+    /// pushes current text to input history (empty text and repeated entries are skipped)
+    pub fn commit(&mut self) {
+        let Some(history) = self.input_history.as_mut() else {
+            return;
+        };
+        history.position = None;
+        history.stash.clear();
+        if self.text.is_empty() || history.capacity == 0 {
+            return;
+        }
+        if history.entries.last() == Some(&self.text) {
+            return;
+        }
+        if history.entries.len() == history.capacity {
+            history.entries.remove(0);
+        }
+        history.entries.push(self.text.clone());
+    }
+
+    /// shows previous input (in-progress text is stashed)
+    pub fn history_prev(&mut self) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        let Some(history) = self.input_history.as_mut() else {
+            return Status::Skipped;
+        };
+        let position = match history.position {
+            None if history.entries.is_empty() => return Status::Skipped,
+            None => {
+                history.stash = self.text.clone();
+                history.entries.len() - 1
+            }
+            Some(0) => return Status::Skipped,
+            Some(position) => position - 1,
+        };
+        history.position = Some(position);
+        let text = history.entries[position].clone();
+        self.text_set(text);
+        Status::Updated
+    }
+
+    /// shows next input, after the last one the stashed in-progress text is restored
+    pub fn history_next(&mut self) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        let Some(history) = self.input_history.as_mut() else {
+            return Status::Skipped;
+        };
+        let Some(position) = history.position else {
+            return Status::Skipped;
+        };
+        let text = match history.entries.get(position + 1) {
+            Some(entry) => {
+                history.position = Some(position + 1);
+                entry.clone()
+            }
+            None => {
+                history.position = None;
+                std::mem::take(&mut history.stash)
+            }
+        };
+        self.text_set(text);
+        Status::Updated
+    }
+
+    /// restores text, cursor and select from before the last edit
+    pub fn undo(&mut self) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        let Some(snapshot) = self.history.undo.pop() else {
+            return Status::Skipped;
+        };
+        let current = self.restore(snapshot);
+        self.history.redo.push(current);
+        Status::Updated
+    }
+
+    /// reapplies the last undone edit
+    pub fn redo(&mut self) -> Status {
+        if self.readonly {
+            return Status::Skipped;
+        }
+        let Some(snapshot) = self.history.redo.pop() else {
+            return Status::Skipped;
+        };
+        let current = self.restore(snapshot);
+        self.history.push(current);
+        Status::Updated
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            char: self.char,
+            select: self.select,
+        }
+    }
+
+    /// returns the replaced state
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        self.history.last_edit = None;
+        self.char_idx_cache.set(None);
+        self.revision += 1;
+        Snapshot {
+            text: std::mem::replace(&mut self.text, snapshot.text),
+            char: std::mem::replace(&mut self.char, snapshot.char),
+            select: std::mem::replace(&mut self.select, snapshot.select),
+        }
+    }
+
+    /// records the state before text update (grouped with previous edit if it continues it)
+    fn edit(&mut self, kind: EditKind, op: impl FnOnce(&mut Self) -> Status) -> Status {
+        let grouped = kind != EditKind::Other
+            && self.select.is_none()
+            && self.history.last_edit == Some((kind, self.char));
+        let snapshot = self.snapshot();
+        let status = op(self);
+        if status.is_text_updated() {
+            self.char_idx_cache.set(None);
+            self.revision += 1;
+            if !grouped {
+                self.history.push(snapshot);
+            }
+            self.history.redo.clear();
+            self.history.last_edit = Some((kind, self.char));
+        }
+        status
+    }
+
+    pub fn go_left(&mut self) -> Status {
+        self.select_drop() + self.prev_char()
+    }
+
+    pub fn select_left(&mut self) -> Status {
+        self.init_select() + self.prev_char()
+    }
+
+    pub fn jump_left(&mut self) -> Status {
+        self.select_drop() + self.prev_char() + self.jump_left_move()
+    }
+
+    pub fn select_jump_left(&mut self) -> Status {
+        self.init_select() + self.prev_char() + self.jump_left_move()
+    }
+
+    pub fn go_right(&mut self) -> Status {
+        self.select_drop() + self.next_char()
+    }
+
+    pub fn select_right(&mut self) -> Status {
+        self.init_select() + self.next_char()
+    }
+
+    pub fn jump_right(&mut self) -> Status {
+        self.select_drop() + self.next_char() + self.jump_right_move()
+    }
+
+    pub fn select_jump_right(&mut self) -> Status {
+        self.init_select() + self.next_char() + self.jump_right_move()
+    }
+
+    /// longest prefix of input that fits char limit if the replaced range is removed
+    fn fitting_prefix<'a>(&self, input: &'a str, replaced: Range<usize>) -> &'a str {
+        let Some(limit) = self.max_chars else {
+            return input;
+        };
+        let kept = self.char_len() - self.text[replaced].chars().count();
+        match input.char_indices().nth(limit.saturating_sub(kept)) {
+            Some((idx, _)) => &input[..idx],
+            None => input,
+        }
+    }
+
+    fn get_cursor_range(&self) -> Option<Range<usize>> {
+        let cursor_char = self.text[self.char..].chars().next()?;
+        Some(self.char..self.char + cursor_char.len_utf8())
+    }
+
+    fn next_char(&mut self) -> Status {
+        match self.text[self.char..]
+            .chars()
+            .next()
+            .map(|ch| ch.len_utf8())
+        {
             Some(offset) => {
                 self.char += offset;
                 Status::UpdatedCursor
@@ -454,7 +1793,7 @@ impl TextField {
     fn jump_left_move(&mut self) -> Status {
         let mut new_char = self.char;
         for (idx, ch) in self.text[..self.char].char_indices().rev() {
-            if !should_jump(ch) {
+            if !self.word_boundary.is_word_char(ch) {
                 break;
             }
             new_char = idx;
@@ -468,7 +1807,7 @@ impl TextField {
 
     fn jump_right_move(&mut self) -> Status {
         for (idx, ch) in self.text[self.char..].char_indices() {
-            if !should_jump(ch) {
+            if !self.word_boundary.is_word_char(ch) {
                 self.char += idx;
                 return Status::UpdatedCursor;
             }
@@ -491,101 +1830,1005 @@ impl TextField {
 
 #[cfg(feature = "crossterm_backend")]
 impl TextField {
-    /// Maps crossterm key events
+    /// Maps crossterm key events with the field keymap (converted to Key / KeyMod and passed to apply)
     /// if None is returned the key is not mapped at all
     /// Copy / Cut / Paste logic is not included -> use copy / cut / paste_passthrough instead
     pub fn map(&mut self, key: KeyEvent) -> Option<Status> {
-        match key.code {
-            KeyCode::Char('a' | 'A') if key.modifiers == KeyModifiers::CONTROL => {
-                Some(self.select_all())
-            }
-            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(self.push_char(ch))
-            }
-            KeyCode::Delete => Some(self.del()),
-            KeyCode::Backspace => Some(self.backspace()),
-            KeyCode::Home => Some(self.start_of_line()),
-            KeyCode::End => Some(self.end_of_line()),
-            KeyCode::Left => Some(self.move_left(key.modifiers)),
-            KeyCode::Right => Some(self.move_right(key.modifiers)),
+        let (key, mods) = input::from_crossterm(key)?;
+        self.apply(key, mods)
+    }
+
+    /// Maps crossterm key events with emacs style layout (see apply_emacs)
+    pub fn map_emacs(&mut self, key: KeyEvent) -> Option<Status> {
+        let (key, mods) = input::from_crossterm(key)?;
+        self.apply_emacs(key, mods)
+    }
+
+    /// Maps crossterm mouse events on the line rendered by widget (left click and drag)
+    /// if None is returned the event is not mapped (outside the line or not handled)
+    pub fn map_mouse(&mut self, event: MouseEvent, line: &Line) -> Option<Status> {
+        if !line.contains_position(event.row, event.column) {
+            return None;
+        }
+        let kind = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => ClickKind::Single,
+            MouseEventKind::Drag(MouseButton::Left) => ClickKind::Drag,
+            _ => return None,
+        };
+        let event = input::MouseEvent {
+            kind,
+            row: event.row,
+            column: event.column,
+        };
+        Some(self.handle_mouse(event, line))
+    }
+}
+
+impl TextField {
+    /// Applies backend agnostic key with the field keymap (see set_keymap)
+    /// if None is returned the key is not mapped at all
+    /// Copy / Cut / Paste logic is not included -> use copy / cut / paste_passthrough instead
+    pub fn apply(&mut self, key: Key, mods: KeyMod) -> Option<Status> {
+        match self.keymap {
+            KeyMap::Default => self.apply_default(key, mods),
+            KeyMap::Emacs => self.apply_emacs(key, mods),
+        }
+    }
+
+    fn apply_default(&mut self, key: Key, mods: KeyMod) -> Option<Status> {
+        match key {
+            Key::Char('a' | 'A') if mods == KeyMod::CONTROL => Some(self.select_all()),
+            Key::Char('z' | 'Z') if mods == KeyMod::CONTROL => Some(self.undo()),
+            Key::Char('y' | 'Y') if mods == KeyMod::CONTROL => Some(self.redo()),
+            Key::Char('k' | 'K') if mods == KeyMod::CONTROL => Some(self.kill_to_end().1),
+            Key::Char('u' | 'U') if mods == KeyMod::CONTROL => Some(self.kill_to_start().1),
+            Key::Char(ch) if !mods.contains(KeyMod::CONTROL) => Some(self.push_char(ch)),
+            Key::Insert => Some(self.toggle_overwrite()),
+            Key::Delete if mods.contains(KeyMod::CONTROL) => Some(self.del_word_right()),
+            Key::Delete => Some(self.del()),
+            Key::Backspace if mods.contains(KeyMod::CONTROL) => Some(self.del_word_left()),
+            Key::Backspace => Some(self.backspace()),
+            Key::Home => Some(self.start_of_line()),
+            Key::End => Some(self.end_of_line()),
+            Key::Up if self.input_history.is_some() => Some(self.history_prev()),
+            Key::Down if self.input_history.is_some() => Some(self.history_next()),
+            Key::Left => Some(self.move_left(mods)),
+            Key::Right => Some(self.move_right(mods)),
             _ => None,
         }
     }
 
-    fn move_left(&mut self, mods: KeyModifiers) -> Status {
-        let should_select = mods.contains(KeyModifiers::SHIFT);
+    /// Applies key with emacs (readline) style layout regardless of the keymap,
+    /// keys not included are handled by the default layout:
+    /// Ctrl+A / Ctrl+E start / end of line, Ctrl+F / Ctrl+B char and Alt+F / Alt+B word moves,
+    /// Ctrl+D delete, Ctrl+W delete word left, Ctrl+K / Ctrl+U kill, Ctrl+Y yank
+    /// (select all and redo are not mapped)
+    pub fn apply_emacs(&mut self, key: Key, mods: KeyMod) -> Option<Status> {
+        match key {
+            Key::Char('a' | 'A') if mods == KeyMod::CONTROL => Some(self.start_of_line()),
+            Key::Char('e' | 'E') if mods == KeyMod::CONTROL => Some(self.end_of_line()),
+            Key::Char('f' | 'F') if mods == KeyMod::CONTROL => Some(self.go_right()),
+            Key::Char('b' | 'B') if mods == KeyMod::CONTROL => Some(self.go_left()),
+            Key::Char('f' | 'F') if mods == KeyMod::ALT => Some(self.jump_right()),
+            Key::Char('b' | 'B') if mods == KeyMod::ALT => Some(self.jump_left()),
+            Key::Char('d' | 'D') if mods == KeyMod::CONTROL => Some(self.del()),
+            Key::Char('w' | 'W') if mods == KeyMod::CONTROL => Some(self.del_word_left()),
+            Key::Char('y' | 'Y') if mods == KeyMod::CONTROL => Some(self.yank()),
+            _ => self.apply_default(key, mods),
+        }
+    }
+
+    fn move_left(&mut self, mods: KeyMod) -> Status {
+        let should_select = mods.contains(KeyMod::SHIFT);
         let mut status = if should_select {
             self.init_select()
         } else {
             self.select_drop()
         };
         status += self.prev_char();
-        if mods.contains(KeyModifiers::CONTROL) {
+        if mods.contains(KeyMod::CONTROL) {
             // jump
             status += self.jump_left_move();
         };
-        status
+        status
+    }
+
+    fn move_right(&mut self, mods: KeyMod) -> Status {
+        let should_select = mods.contains(KeyMod::SHIFT);
+        let mut status = if should_select {
+            self.init_select()
+        } else {
+            self.select_drop()
+        };
+        status += self.next_char();
+        if mods.contains(KeyMod::CONTROL) {
+            // jump
+            self.jump_right_move();
+        };
+        status
+    }
+}
+
+pub fn arg_range_at(line: &str, idx: usize) -> Range<usize> {
+    let mut token_start = 0;
+    let mut last_not_in_token = false;
+    for (char_idx, ch) in line.char_indices() {
+        if !ch.is_whitespace() {
+            if last_not_in_token {
+                token_start = char_idx;
+            }
+            last_not_in_token = false;
+        } else if char_idx >= idx {
+            if last_not_in_token {
+                return idx..idx;
+            }
+            return token_start..char_idx;
+        } else {
+            last_not_in_token = true;
+        }
+    }
+    if idx < line.len() {
+        token_start..line.len()
+    } else if !last_not_in_token && token_start <= idx {
+        token_start..idx
+    } else {
+        idx..idx
+    }
+}
+
+/// range of the word (as defined by boundary) containing or ending at idx
+pub fn word_range_at(line: &str, idx: usize, boundary: WordBoundary) -> Range<usize> {
+    let is_word_char = |ch: char| boundary.is_word_char(ch);
+    let start = line[..idx]
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| is_word_char(*ch))
+        .last()
+        .map(|(char_idx, _)| char_idx)
+        .unwrap_or(idx);
+    let end = line[idx..]
+        .char_indices()
+        .find(|(_, ch)| !is_word_char(*ch))
+        .map(|(char_idx, _)| idx + char_idx)
+        .unwrap_or(line.len());
+    start..end
+}
+
+#[inline]
+fn should_jump(ch: char) -> bool {
+    ch.is_alphabetic() || ch.is_numeric()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::{prelude::*, BufferBackend, MockedBackend, MockedStyle};
+    use crate::input::{self, ClickKind, Key, KeyMod};
+    use crate::layout::{Line, Rect};
+    #[allow(unused)]
+    use crate::text_field::Status;
+    use crate::utils::PasteOptions;
+    use crate::Position;
+    use unicode_width::UnicodeWidthStr;
+
+    use super::{
+        should_jump, word_range_at, Clipboard, FieldRenderState, KeyMap, OverflowMarkers,
+        TextField, WordBoundary,
+    };
+
+    #[cfg(feature = "crossterm_backend")]
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    #[test]
+    fn render_masked() {
+        let mut field = TextField::masked('●');
+        for ch in "p🦀e\u{301}s".chars() {
+            field.push_char(ch);
+        }
+        field.go_left();
+        let mut backend = MockedBackend::init();
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "●●●".to_owned()),
+                (MockedStyle::reversed(), "●".to_owned()),
+                (MockedStyle::default(), "".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            ]
+        );
+        assert_eq!(field.as_str(), "p🦀e\u{301}s");
+
+        // no leaks to clipboard
+        field.select_all();
+        assert!(field.copy().is_none());
+        assert!(field.cut().is_none());
+        assert_eq!(field.as_str(), "p🦀e\u{301}s");
+        field.set_allow_copy(true);
+        assert_eq!(field.copy().as_deref(), Some("p🦀e\u{301}s"));
+
+        // wide mask - cursor and select are aligned to the masked graphemes
+        field.text_set("ab🦀c".to_owned());
+        field.set_mask(Some('＊'));
+        field.select_left();
+        field.select_left();
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 14,
+        };
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "＊＊".to_owned()),
+                (MockedStyle::reversed(), "＊".to_owned()),
+                (MockedStyle::bold(), "＊".to_owned()),
+                (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            ]
+        );
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 0, col: 8 })
+        );
+    }
+
+    #[test]
+    fn render_with_prefix() {
+        let field = TextField::new("0123456789".to_owned());
+        let mut backend = MockedBackend::init();
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        field.widget_with_prefix(
+            line.clone(),
+            "🔍 ",
+            Some(MockedStyle::bold()),
+            MockedStyle::reversed(),
+            MockedStyle::default(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::bold(), "🔍 ".to_owned()),
+                (MockedStyle::default(), "456789".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_styled() {
+        let mut backend = MockedBackend::init();
+        let mut field = TextField::new("some text".to_owned());
+        field.cursor_set(2);
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 20,
+        };
+        let base = MockedStyle::fg(1);
+        let merged = |style| MockedBackend::merge_style(MockedStyle::fg(1), style);
+        field.widget_styled(
+            line.clone(),
+            base.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (base.clone(), " >> ".to_owned()),
+                (base.clone(), "so".to_owned()),
+                (merged(MockedStyle::reversed()), "m".to_owned()),
+                (base.clone(), "e text".to_owned()),
+                (MockedStyle::default(), "<<padding: 7>>".to_owned()),
+            ]
+        );
+
+        field.select_word_at_cursor();
+        field.widget_styled(
+            line,
+            base.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (base.clone(), " >> ".to_owned()),
+                (merged(MockedStyle::bold()), "some".to_owned()),
+                (merged(MockedStyle::reversed()), " ".to_owned()),
+                (base, "text".to_owned()),
+                (MockedStyle::default(), "<<padding: 7>>".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_scrolled_select() {
+        let mut backend = MockedBackend::init();
+        let mut field = TextField::new("0123456789abcdef".to_owned());
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        // select starts left of the offset
+        field.select = Some(5);
+        field.char = 14;
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::bold(), "bcd".to_owned()),
+                (MockedStyle::reversed(), "e".to_owned()),
+                (MockedStyle::default(), "f".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+
+        // cursor on select start
+        field.select = Some(16);
+        field.char = 14;
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "bcd".to_owned()),
+                (MockedStyle::reversed(), "e".to_owned()),
+                (MockedStyle::bold(), "f".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+
+        // select fully out of view
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 5,
+        };
+        field.text_set("abc".to_owned());
+        field.select = Some(0);
+        field.char = 2;
+        field.widget(
+            line,
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::reversed(), "c".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_scrolled_wide() {
+        let mut backend = MockedBackend::init();
+        let mut field = TextField::new("🦀🦀🦀🦀🦀🦀".to_owned());
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "🦀🦀".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 0, col: 8 })
+        );
+
+        field.cursor_set_char(3);
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "🦀".to_owned()),
+                (MockedStyle::reversed(), "🦀".to_owned()),
+                (MockedStyle::default(), "🦀".to_owned()),
+            ]
+        );
+
+        // odd width - wide char does not fit in the last col
+        field.cursor_set_char(1);
+        field.widget(
+            Line {
+                row: 0,
+                col: 0,
+                width: 9,
+            },
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "🦀".to_owned()),
+                (MockedStyle::reversed(), "🦀".to_owned()),
+                (MockedStyle::default(), "".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_with_markers() {
+        let mut backend = MockedBackend::init();
+        let mut field = TextField::new("0123456789".to_owned());
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        let mut render = |field: &TextField| {
+            field.widget_with_markers(
+                line.clone(),
+                OverflowMarkers::default(),
+                MockedStyle::bold(),
+                MockedStyle::reversed(),
+                MockedStyle::fg(1),
+                &mut backend,
+            );
+            backend.drain()
+        };
+        assert_eq!(
+            render(&field),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::bold(), "<".to_owned()),
+                (MockedStyle::default(), "6789".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+            ]
+        );
+
+        field.start_of_line();
+        assert_eq!(
+            render(&field),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "".to_owned()),
+                (MockedStyle::reversed(), "0".to_owned()),
+                (MockedStyle::default(), "1234".to_owned()),
+                (MockedStyle::bold(), ">".to_owned()),
+            ]
+        );
+
+        field.cursor_set(5);
+        assert_eq!(
+            render(&field),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::bold(), "<".to_owned()),
+                (MockedStyle::default(), "4".to_owned()),
+                (MockedStyle::reversed(), "5".to_owned()),
+                (MockedStyle::default(), "67".to_owned()),
+                (MockedStyle::bold(), ">".to_owned()),
+            ]
+        );
+        // geometry follows the markers - " >> <4" before the cursor
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 0, col: 6 })
+        );
+        field.click_at(Position { row: 0, col: 7 }, &line);
+        assert_eq!(field.cursor(), 6);
+        field.cursor_set(5);
+        render(&field);
+        // left marker goes to the first visible char
+        field.click_at(Position { row: 0, col: 4 }, &line);
+        assert_eq!(field.cursor(), 4);
+
+        // wide char does not fit - right marker stays in the last col
+        field.text_set("🦀🦀🦀🦀🦀".to_owned());
+        field.start_of_line();
+        assert_eq!(
+            render(&field),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "".to_owned()),
+                (MockedStyle::reversed(), "🦀".to_owned()),
+                (MockedStyle::default(), "🦀".to_owned()),
+                (MockedStyle::default(), " ".to_owned()),
+                (MockedStyle::bold(), ">".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_unfocused() {
+        let mut field = TextField::new("abc".to_owned());
+        field.select_left();
+        let mut backend = MockedBackend::init();
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        field.widget_unfocused(line.clone(), MockedStyle::bold(), &mut backend);
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::bold(), "abc".to_owned()),
+                (MockedStyle::default(), "<<padding: 3>>".to_owned()),
+            ]
+        );
+
+        // same scroll offset as the focused render
+        field.text_set("0123456789".to_owned());
+        field.widget_unfocused(line.clone(), MockedStyle::bold(), &mut backend);
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::bold(), "56789".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn click_at() {
+        let mut field = TextField::new("a🦀b".to_owned());
+        let line = Line {
+            row: 2,
+            col: 1,
+            width: 20,
+        };
+        let click = |col| Position { row: 2, col };
+        assert_eq!(field.click_at(click(16), &line), Status::Skipped);
+        assert_eq!(field.char, 6);
+        assert_eq!(field.click_at(click(5), &line), Status::UpdatedCursor);
+        assert_eq!(field.char, 0);
+        assert_eq!(field.click_at(click(6), &line), Status::UpdatedCursor);
+        assert_eq!(field.char, 1);
+        // second col of wide char
+        assert_eq!(field.click_at(click(7), &line), Status::Skipped);
+        assert_eq!(field.char, 1);
+        field.click_at(click(8), &line);
+        assert_eq!(field.char, 5);
+        // prefix
+        field.click_at(click(2), &line);
+        assert_eq!(field.char, 0);
+        assert_eq!(
+            field.click_at(Position { row: 1, col: 8 }, &line),
+            Status::Skipped
+        );
+        assert_eq!(field.click_at(click(21), &line), Status::Skipped);
+
+        // scrolled
+        field.text_set("abcdefghij".to_owned());
+        let line = Line {
+            row: 2,
+            col: 1,
+            width: 8,
+        };
+        let offset = field.calculate_width_offset(4);
+        assert!(offset > 0);
+        field.click_at(click(5), &line);
+        assert_eq!(field.char, offset);
+
+        // masked
+        let mut field = TextField::masked('＊');
+        field.text_set("a🦀e\u{301}b".to_owned());
+        let line = Line {
+            row: 2,
+            col: 1,
+            width: 20,
+        };
+        field.click_at(click(10), &line);
+        assert_eq!(field.char, "a🦀".len());
+    }
+
+    #[test]
+    fn click_at_scrolled() {
+        let line = Line {
+            row: 0,
+            col: 1,
+            width: 10,
+        };
+        let click = |col| Position { row: 0, col };
+        // scrolled - "defgh" visible
+        let mut field = TextField::new("ab🦀cdefgh".to_owned());
+        assert_eq!(field.click_at(click(5), &line), Status::UpdatedCursor);
+        assert_eq!(field.char, 7);
+        field.end_of_line();
+        field.click_at(click(9), &line);
+        assert_eq!(field.char, 11);
+        field.end_of_line();
+        assert_eq!(field.click_at(click(10), &line), Status::Skipped);
+        assert_eq!(field.char, 12);
+        // prefix goes to the first visible char
+        field.select_all();
+        assert_eq!(field.click_at(click(2), &line), Status::UpdatedCursor);
+        assert_eq!(field.char, 7);
+        assert_eq!(field.select(), None);
+        // both cols of wide char
+        field.start_of_line();
+        field.click_at(click(7), &line);
+        assert_eq!(field.char, 2);
+        assert_eq!(field.click_at(click(8), &line), Status::Skipped);
+        field.click_at(click(9), &line);
+        assert_eq!(field.char, 6);
+        // custom prefix
+        field.start_of_line();
+        let mut backend = MockedBackend::init();
+        let (cursor, select) = (MockedStyle::reversed(), MockedStyle::bold());
+        field.widget_with_prefix(line.clone(), "  3 >> ", None, cursor, select, &mut backend);
+        field.click_at(click(9), &line);
+        assert_eq!(field.char, 1);
+    }
+
+    #[test]
+    fn custom_prefix_geometry() {
+        let line = Line {
+            row: 1,
+            col: 2,
+            width: 12,
+        };
+        let mut backend = MockedBackend::init();
+        let (cursor, select) = (MockedStyle::reversed(), MockedStyle::bold());
+        let mut field = TextField::new("ab cd".to_owned());
+        field.cursor_set(1);
+        let prefix = "$ ";
+        field.widget_with_prefix(
+            line.clone(),
+            prefix,
+            None,
+            cursor.clone(),
+            select.clone(),
+            &mut backend,
+        );
+        let cursor_cell = backend
+            .drain()
+            .into_iter()
+            .position(|(style, _)| style == MockedStyle::reversed());
+        assert_eq!(cursor_cell, Some(3));
+        // "$ " + "a" -> cursor painted on col 3 of the line
+        assert_eq!(field.cursor_display_col(line.width), 3);
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 1, col: 5 })
+        );
+        let click = |col| Position { row: 1, col };
+        field.click_at(click(7), &line);
+        assert_eq!(field.cursor(), 3);
+        assert_eq!(field.select_token_at_col(6, &line), Status::UpdatedCursor);
+        assert_eq!(field.select(), Some((3, 5)));
+        let drag = input::MouseEvent {
+            kind: ClickKind::Drag,
+            row: 1,
+            column: 5,
+        };
+        field.start_of_line();
+        field.handle_mouse(drag, &line);
+        assert_eq!(field.select(), Some((0, 1)));
+        // widget restores the default prefix
+        field.widget(line.clone(), cursor, select, &mut backend);
+        assert_eq!(field.cursor_display_col(line.width), 5);
+    }
+
+    #[test]
+    fn handle_mouse() {
+        let line = Line {
+            row: 1,
+            col: 0,
+            width: 30,
+        };
+        let click = |kind, column| input::MouseEvent {
+            kind,
+            row: 1,
+            column,
+        };
+        let mut field = TextField::new("cd src/main.rs x".to_owned());
+        assert_eq!(
+            field.handle_mouse(click(ClickKind::Single, 7), &line),
+            Status::UpdatedCursor
+        );
+        assert_eq!(field.cursor(), 3);
+        assert_eq!(
+            field.handle_mouse(click(ClickKind::Double, 9), &line),
+            Status::UpdatedCursor
+        );
+        assert_eq!(field.select(), Some((3, 14)));
+        assert_eq!(
+            field.handle_mouse(click(ClickKind::Triple, 9), &line),
+            Status::UpdatedCursor
+        );
+        assert_eq!(field.select(), Some((0, 16)));
+        field.handle_mouse(click(ClickKind::Single, 4), &line);
+        assert_eq!(field.select(), None);
+        assert_eq!(
+            field.handle_mouse(click(ClickKind::Drag, 6), &line),
+            Status::UpdatedCursor
+        );
+        assert_eq!(field.select(), Some((0, 2)));
+        field.handle_mouse(click(ClickKind::Drag, 8), &line);
+        assert_eq!(field.select(), Some((0, 4)));
+        assert_eq!(
+            field.handle_mouse(click(ClickKind::Drag, 8), &line),
+            Status::Skipped
+        );
+        let outside = input::MouseEvent {
+            kind: ClickKind::Single,
+            row: 2,
+            column: 8,
+        };
+        assert_eq!(field.handle_mouse(outside, &line), Status::Skipped);
+        assert_eq!(field.select(), Some((0, 4)));
     }
 
-    fn move_right(&mut self, mods: KeyModifiers) -> Status {
-        let should_select = mods.contains(KeyModifiers::SHIFT);
-        let mut status = if should_select {
-            self.init_select()
-        } else {
-            self.select_drop()
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_mouse() {
+        let line = Line {
+            row: 2,
+            col: 0,
+            width: 10,
         };
-        status += self.next_char();
-        if mods.contains(KeyModifiers::CONTROL) {
-            // jump
-            self.jump_right_move();
+        let click = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
         };
-        status
+        let left = MouseEventKind::Down(MouseButton::Left);
+        let mut field = TextField::new("abc".to_owned());
+        assert_eq!(
+            field.map_mouse(click(left, 5, 2), &line),
+            Some(Status::UpdatedCursor)
+        );
+        assert_eq!(field.char, 1);
+        assert_eq!(field.map_mouse(click(left, 5, 3), &line), None);
+        assert_eq!(field.map_mouse(click(left, 10, 2), &line), None);
+        let right = MouseEventKind::Down(MouseButton::Right);
+        assert_eq!(field.map_mouse(click(right, 4, 2), &line), None);
+        assert_eq!(field.char, 1);
     }
-}
 
-pub fn arg_range_at(line: &str, idx: usize) -> Range<usize> {
-    let mut token_start = 0;
-    let mut last_not_in_token = false;
-    for (char_idx, ch) in line.char_indices() {
-        if !ch.is_whitespace() {
-            if last_not_in_token {
-                token_start = char_idx;
-            }
-            last_not_in_token = false;
-        } else if char_idx >= idx {
-            if last_not_in_token {
-                return idx..idx;
-            }
-            return token_start..char_idx;
-        } else {
-            last_not_in_token = true;
-        }
+    #[test]
+    fn cursor_display_col() {
+        let mut field = TextField::new("ab🦀cdefgh".to_owned());
+        let line = Line {
+            row: 3,
+            col: 2,
+            width: 10,
+        };
+        // scrolled - "defgh" visible
+        assert_eq!(field.cursor_display_col(line.width), 9);
+        field.cursor_set(2);
+        assert_eq!(field.cursor_display_col(line.width), 6);
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 3, col: 8 })
+        );
+        field.cursor_set(6);
+        assert_eq!(field.cursor_display_col(line.width), 7);
+        assert_eq!(field.cursor_display_col(40), 8);
+        // masked
+        field.set_mask(Some('＊'));
+        assert_eq!(field.cursor_display_col(40), 10);
     }
-    if idx < line.len() {
-        token_start..line.len()
-    } else if !last_not_in_token && token_start <= idx {
-        token_start..idx
-    } else {
-        idx..idx
+
+    #[test]
+    fn revision() {
+        let mut field = TextField::new("abc".to_owned());
+        assert_eq!(field.revision(), 0);
+        field.push_char('d');
+        field.push_char('e');
+        assert_eq!(field.revision(), 2);
+        field.go_left();
+        field.select_all();
+        assert_eq!(field.revision(), 2);
+        assert_eq!(field.cut().as_deref(), Some("abcde"));
+        assert_eq!(field.revision(), 3);
+        assert_eq!(field.cut(), None);
+        assert_eq!(field.backspace(), Status::Skipped);
+        assert_eq!(field.revision(), 3);
+        field.undo();
+        field.redo();
+        assert_eq!(field.revision(), 5);
+        field.text_set("x".to_owned());
+        assert_eq!(field.revision(), 6);
     }
-}
 
-#[inline]
-fn should_jump(ch: char) -> bool {
-    ch.is_alphabetic() || ch.is_numeric()
-}
+    #[test]
+    fn eq_ignores_char_idx_cache() {
+        let field = TextField::new("zażółć".to_owned());
+        let clone = field.clone();
+        assert_eq!(field.cursor_char(), 6);
+        assert_eq!(field, clone);
+    }
 
-#[cfg(test)]
-mod test {
-    use crate::backend::{Backend, MockedBackend, MockedStyle};
-    use crate::layout::Line;
-    #[allow(unused)]
-    use crate::text_field::Status;
+    #[test]
+    fn parts() {
+        let field = TextField::from_parts("ząb 🦀 x".to_owned(), 2, None);
+        assert_eq!(field.cursor(), 3);
+        assert_eq!(field.as_parts(), ("ząb 🦀 x", 2, None));
+        // cursor at select start
+        let field = TextField::from_parts("ząb 🦀 x".to_owned(), 1, Some((5, 1)));
+        assert_eq!(field.selection_str(), Some("ąb 🦀"));
+        assert_eq!(field.cursor(), 1);
+        assert_eq!(field.as_parts(), ("ząb 🦀 x", 1, Some((1, 5))));
+        // cursor not on select edge and idxs past the end are clamped
+        let field = TextField::from_parts("ząb 🦀 x".to_owned(), 30, Some((4, 30)));
+        assert_eq!(field.selection_str(), Some("🦀 x"));
+        assert_eq!(field.into_parts(), ("ząb 🦀 x".to_owned(), 7, Some((4, 7))));
+        let field = TextField::from_parts("ząb".to_owned(), 1, Some((2, 2)));
+        assert_eq!(field.select(), None);
+        // round trip
+        let mut field = TextField::new("zażółć gęślą".to_owned());
+        field.select_word_at_cursor();
+        field.select_left();
+        assert!(field.select().is_some());
+        let (text, cursor, select) = field.as_parts();
+        let restored = TextField::from_parts(text.to_owned(), cursor, select);
+        assert_eq!(restored.as_parts(), field.as_parts());
+        assert_eq!(restored.cursor(), field.cursor());
+    }
+
+    #[test]
+    fn set_select() {
+        let mut field = TextField::new("ząb 🦀 x".to_owned());
+        assert_eq!(field.selection_str(), None);
+        assert_eq!(field.set_select(1, 5), Status::UpdatedCursor);
+        assert_eq!(field.selection_str(), Some("ąb 🦀"));
+        assert_eq!(field.cursor(), "ząb 🦀".len());
+        assert_eq!(field.set_select(1, 5), Status::Skipped);
+        // reversed
+        field.set_select(5, 1);
+        assert_eq!(field.selection_str(), Some("ąb 🦀"));
+        assert_eq!(field.cursor(), 1);
+        assert_eq!(field.set_select(3, 3), Status::Skipped);
+        // clamped
+        field.set_select(4, 100);
+        assert_eq!(field.selection_str(), Some("🦀 x"));
+        assert_eq!(field.cursor(), field.len());
+        assert_eq!(field.set_select(50, 100), Status::Skipped);
+        assert_eq!(field.selection_str(), Some("🦀 x"));
+    }
 
-    use super::{should_jump, TextField};
+    #[test]
+    fn select_word_at_cursor() {
+        let mut field = TextField::new("path/to/file.rs:42".to_owned());
+        assert_eq!(field.select_word_at_cursor(), Status::UpdatedCursor);
+        assert_eq!(field.select(), Some((16, 18)));
+        assert_eq!(field.select_word_at_cursor(), Status::Skipped);
+        field.cursor_set(9);
+        field.select_word_at_cursor();
+        assert_eq!(field.select(), Some((8, 12)));
+        field.cursor_set(4);
+        field.select_word_at_cursor();
+        assert_eq!(field.select(), Some((0, 4)));
+        // token selection still splits only on white space
+        field.cursor_set(9);
+        field.select_token_at_cursor();
+        assert_eq!(field.select(), Some((0, 18)));
 
-    #[cfg(feature = "crossterm_backend")]
-    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        field.text_set("a/ foo_bar-ąę".to_owned());
+        field.cursor_set(2);
+        assert_eq!(field.select_word_at_cursor(), Status::Skipped);
+        let identifier = WordBoundary::Identifier;
+        assert_eq!(word_range_at(field.as_str(), 4, identifier), 3..10);
+        assert_eq!(word_range_at(field.as_str(), 13, identifier), 11..15);
+        assert_eq!(
+            word_range_at(field.as_str(), 4, WordBoundary::default()),
+            3..6
+        );
+
+        // selection follows the field word boundary
+        field.cursor_set(4);
+        field.select_word_at_cursor();
+        assert_eq!(field.select(), Some((3, 6)));
+        field.set_word_boundary(WordBoundary::Identifier);
+        field.cursor_set(4);
+        field.select_word_at_cursor();
+        assert_eq!(field.select(), Some((3, 10)));
+        field.set_word_boundary(WordBoundary::NonWhitespace);
+        field.cursor_set(4);
+        field.select_word_at_cursor();
+        assert_eq!(field.select(), Some((3, 15)));
+    }
+
+    #[test]
+    fn select_token_at_col() {
+        let mut field = TextField::new("cd 🦀/src  ../x".to_owned());
+        let line = Line {
+            row: 0,
+            col: 10,
+            width: 30,
+        };
+        // " >> " prefix takes 4 cols
+        assert_eq!(field.select_token_at_col(8, &line), Status::UpdatedCursor);
+        assert_eq!(field.select(), Some((3, 11)));
+        assert_eq!(field.cursor(), 11);
+        assert_eq!(field.select_token_at_col(4, &line), Status::UpdatedCursor);
+        assert_eq!(field.select(), Some((0, 2)));
+        assert_eq!(field.select_token_at_col(15, &line), Status::UpdatedCursor);
+        assert_eq!(field.select(), Some((13, 17)));
+        assert_eq!(field.select_token_at_col(6, &line), Status::Skipped);
+        assert_eq!(field.select_token_at_col(14, &line), Status::Skipped);
+        assert_eq!(field.select_token_at_col(25, &line), Status::Skipped);
+        assert_eq!(field.select_token_at_col(30, &line), Status::Skipped);
+        assert_eq!(field.select(), Some((13, 17)));
+    }
 
     #[test]
     fn render_non_ascii() {
@@ -680,12 +2923,17 @@ mod test {
         };
 
         field.widget_with_count(
-            line,
+            line.clone(),
             3,
             MockedStyle::default(),
             MockedStyle::default(),
             &mut backend,
         );
+        // "  3 >> some text" - cursor cell after the text
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 0, col: 17 })
+        );
 
         assert_eq!(
             backend.drain(),
@@ -701,32 +2949,623 @@ mod test {
     }
 
     #[test]
-    fn test_should_jump() {
-        assert!(should_jump('a'));
-        assert!(should_jump('1'));
-        assert!(should_jump('b'));
-        assert!(!should_jump('🦀'));
+    fn test_should_jump() {
+        assert!(should_jump('a'));
+        assert!(should_jump('1'));
+        assert!(should_jump('b'));
+        assert!(!should_jump('🦀'));
+    }
+
+    #[test]
+    fn word_boundary() {
+        let text = "foo_bar-baz🦀";
+        let mut field = TextField::new(text.to_owned());
+        field.jump_left();
+        assert_eq!(field.cursor(), "foo_bar-".len());
+        field.jump_left();
+        assert_eq!(field.cursor(), "foo_".len());
+
+        field.set_word_boundary(WordBoundary::Identifier);
+        field.cursor_set(text.len());
+        field.jump_left();
+        field.jump_left();
+        assert_eq!(field.cursor(), 0);
+        field.jump_right();
+        assert_eq!(field.cursor(), "foo_bar".len());
+        assert_eq!(field.del_word_left(), Status::Updated);
+        assert_eq!(field.as_str(), "-baz🦀");
+
+        field.set_word_boundary(WordBoundary::NonWhitespace);
+        field.text_set("a foo_bar-baz🦀".to_owned());
+        field.jump_left();
+        assert_eq!(field.cursor(), 2);
+        assert!(WordBoundary::NonWhitespace.is_word_char('🦀'));
+        assert!(!WordBoundary::Identifier.is_word_char('-'));
+
+        // kebab and snake case as single word
+        let kebab = |ch: char| ch == '-' || WordBoundary::Identifier.is_word_char(ch);
+        field.set_word_boundary(WordBoundary::Custom(kebab));
+        field.text_set("a foo_bar-baz b".to_owned());
+        field.cursor_set(2);
+        field.jump_right();
+        assert_eq!(field.cursor(), "a foo_bar-baz".len());
+        field.jump_left();
+        assert_eq!(field.cursor(), 2);
+        assert_eq!(field.word_boundary(), WordBoundary::Custom(|ch| ch == '.'));
+        assert_ne!(field.word_boundary(), WordBoundary::Identifier);
+    }
+
+    #[test]
+    #[allow(clippy::field_reassign_with_default)]
+    fn get_select() {
+        let mut t = TextField::default();
+        t.select = Some(10);
+        t.char = 5;
+        assert_eq!(t.select().unwrap(), (5, 10));
+        t.select = Some(3);
+        t.char = 8;
+        assert_eq!(t.select().unwrap(), (3, 8));
+    }
+
+    #[test]
+    fn test_cut() {
+        let mut t = TextField::new("some text".into());
+        assert_eq!(t.select_jump_left(), Status::UpdatedCursor);
+        let cut = t.cut().unwrap();
+        assert_eq!("text", cut);
+        assert!(t.select().is_none());
+        assert_eq!(t.char, 5);
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut field = TextField::new("some".into());
+        for ch in " text🦀".chars() {
+            field.push_char(ch);
+        }
+        field.backspace();
+        field.backspace();
+        assert_eq!(field.as_str(), "some tex");
+        assert_eq!(field.undo(), Status::Updated);
+        assert_eq!(field.as_str(), "some text🦀");
+        assert_eq!(field.undo(), Status::Updated);
+        assert_eq!(field.as_str(), "some");
+        assert_eq!(field.char, 4);
+        assert_eq!(field.undo(), Status::Skipped);
+        assert_eq!(field.redo(), Status::Updated);
+        assert_eq!(field.as_str(), "some text🦀");
+        assert_eq!(field.char, 13);
+
+        // cursor move breaks the group
+        field.go_left();
+        field.push_char('s');
+        field.push_char('s');
+        assert_eq!(field.as_str(), "some textss🦀");
+        field.undo();
+        assert_eq!(field.as_str(), "some text🦀");
+        assert_eq!(field.char, 9);
+
+        // new edit drops redo
+        field.push_char('!');
+        assert_eq!(field.redo(), Status::Skipped);
+
+        // select is restored
+        field.select_all();
+        field.push_char('x');
+        assert_eq!(field.as_str(), "x");
+        field.undo();
+        assert_eq!(field.as_str(), "some text!🦀");
+        assert_eq!(field.select(), Some((0, 14)));
+    }
+
+    #[test]
+    fn undo_limit() {
+        let mut field = TextField::default();
+        // each " a" is separate word
+        for _ in 0..150 {
+            field.push_char(' ');
+            field.push_char('a');
+        }
+        let mut undone = 0;
+        while field.undo().is_updated() {
+            undone += 1;
+        }
+        assert_eq!(undone, 100);
+        assert_eq!(field.char_len(), 100);
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_undo_redo() {
+        let mut field = TextField::default();
+        field.map(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        field.map(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
+        let undo = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(field.map(undo), Some(Status::Updated));
+        assert!(field.is_empty());
+        let redo = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(field.map(redo), Some(Status::Updated));
+        assert_eq!(field.as_str(), "ab");
+    }
+
+    #[test]
+    fn apply_keys() {
+        let mut field = TextField::default();
+        for ch in "ab cd".chars() {
+            field.apply(Key::Char(ch), KeyMod::empty());
+        }
+        assert_eq!(
+            field.apply(Key::Left, KeyMod::CONTROL | KeyMod::SHIFT),
+            Some(Status::UpdatedCursor)
+        );
+        assert_eq!(field.select(), Some((3, 5)));
+        assert_eq!(
+            field.apply(Key::Backspace, KeyMod::empty()),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.as_str(), "ab ");
+        assert_eq!(
+            field.apply(Key::Char('u'), KeyMod::CONTROL),
+            Some(Status::Updated)
+        );
+        assert!(field.is_empty());
+        assert_eq!(
+            field.apply(Key::Char('z'), KeyMod::CONTROL),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.as_str(), "ab ");
+        assert_eq!(field.apply(Key::Up, KeyMod::empty()), None);
+        assert_eq!(field.apply(Key::Enter, KeyMod::empty()), None);
+    }
+
+    #[test]
+    fn apply_emacs_keys() {
+        let mut field = TextField::new("cd src/bin".to_owned());
+        let ctrl = |field: &mut TextField, ch| field.apply_emacs(Key::Char(ch), KeyMod::CONTROL);
+        assert_eq!(ctrl(&mut field, 'a'), Some(Status::UpdatedCursor));
+        assert_eq!(field.cursor(), 0);
+        assert_eq!(field.select(), None);
+        ctrl(&mut field, 'f');
+        assert_eq!(field.cursor(), 1);
+        ctrl(&mut field, 'b');
+        assert_eq!(field.cursor(), 0);
+        assert_eq!(ctrl(&mut field, 'd'), Some(Status::Updated));
+        assert_eq!(field.as_str(), "d src/bin");
+        field.apply_emacs(Key::Char('f'), KeyMod::ALT);
+        assert_eq!(field.cursor(), 1);
+        ctrl(&mut field, 'e');
+        assert_eq!(field.cursor(), field.len());
+        assert_eq!(ctrl(&mut field, 'w'), Some(Status::Updated));
+        assert_eq!(field.as_str(), "d src/");
+        field.apply_emacs(Key::Char('b'), KeyMod::ALT);
+        assert_eq!(field.cursor(), 2);
+        // not included keys use default layout
+        assert_eq!(ctrl(&mut field, 'k'), Some(Status::Updated));
+        assert_eq!(field.as_str(), "d ");
+        field.apply_emacs(Key::Char('x'), KeyMod::empty());
+        assert_eq!(field.as_str(), "d x");
+    }
+
+    #[test]
+    fn keymap_emacs() {
+        let mut field = TextField::new("some text".to_owned());
+        assert_eq!(field.keymap(), KeyMap::Default);
+        field.apply(Key::Char('a'), KeyMod::CONTROL);
+        assert_eq!(field.select(), Some((0, 9)));
+        field.set_keymap(KeyMap::Emacs);
+        assert_eq!(
+            field.apply(Key::Char('a'), KeyMod::CONTROL),
+            Some(Status::UpdatedCursor)
+        );
+        assert_eq!(field.cursor(), 0);
+        assert_eq!(field.select(), None);
+        field.apply(Key::Char('f'), KeyMod::ALT);
+        assert_eq!(field.cursor(), 4);
+        assert_eq!(
+            field.apply(Key::Char('k'), KeyMod::CONTROL),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.as_str(), "some");
+        assert_eq!(field.kill_buffer(), " text");
+        field.apply(Key::Char('a'), KeyMod::CONTROL);
+        assert_eq!(
+            field.apply(Key::Char('y'), KeyMod::CONTROL),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.as_str(), " textsome");
+        assert_eq!(field.cursor(), 5);
+        field.apply(Key::Char('b'), KeyMod::CONTROL);
+        field.apply(Key::Char('u'), KeyMod::CONTROL);
+        assert_eq!(field.as_str(), "tsome");
+        assert_eq!(field.kill_buffer(), " tex");
+        // empty kill keeps the buffer
+        field.apply(Key::Char('u'), KeyMod::CONTROL);
+        assert_eq!(field.kill_buffer(), " tex");
+        field.apply(Key::Char('e'), KeyMod::CONTROL);
+        field.yank();
+        field.yank();
+        assert_eq!(field.as_str(), "tsome tex tex");
+    }
+
+    #[test]
+    fn kill_masked() {
+        let mut field = TextField::masked('*');
+        field.text_set("secret".to_owned());
+        field.kill_to_start();
+        assert!(field.is_empty());
+        assert_eq!(field.kill_buffer(), "");
+        assert_eq!(field.yank(), Status::Skipped);
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_keymap() {
+        let mut field = TextField::new("some text".to_owned());
+        field.set_keymap(KeyMap::Emacs);
+        field.map(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL));
+        assert_eq!(field.cursor(), 0);
+        field.map(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert!(field.is_empty());
+        field.map(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+        assert_eq!(field.as_str(), "some text");
+    }
+
+    #[test]
+    fn char_filter_and_validator() {
+        let mut field = TextField::default();
+        field.set_char_filter(Some(|ch| ch.is_ascii_digit()));
+        assert_eq!(field.push_char('1'), Status::Updated);
+        assert_eq!(field.push_char('a'), Status::Skipped);
+        assert_eq!(
+            field.paste_passthrough("2a3 b4".to_owned()),
+            Status::Updated
+        );
+        assert_eq!(field.as_str(), "1234");
+        assert_eq!(field.cursor(), 4);
+        assert_eq!(field.paste_passthrough("abc".to_owned()), Status::Skipped);
+        assert!(field.is_valid());
+
+        field.set_validator(Some(|text| text.len() < 5));
+        assert!(field.is_valid());
+        field.push_char('5');
+        assert!(!field.is_valid());
+        let mut backend = MockedBackend::init();
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 14,
+        };
+        field.widget_validated(
+            line,
+            MockedStyle::fg(1),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+                (MockedStyle::fg(1), " >> ".to_owned()),
+                (MockedStyle::default(), "12345".to_owned()),
+                (MockedStyle::reversed(), " ".to_owned()),
+                (MockedStyle::default(), "<<padding: 4>>".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn char_limit() {
+        let mut field = TextField::default();
+        field.set_char_limit(3);
+        assert_eq!(field.max_chars(), Some(3));
+        assert_eq!(field.push_char('🦀'), Status::Updated);
+        assert_eq!(field.push_char('ą'), Status::Updated);
+        assert_eq!(field.push_char('1'), Status::Updated);
+        assert_eq!(field.push_char('2'), Status::Skipped);
+        assert_eq!(field.as_str(), "🦀ą1");
+
+        // select / overwrite free space for new char
+        field.select_left();
+        assert_eq!(field.push_char('2'), Status::Updated);
+        assert_eq!(field.as_str(), "🦀ą2");
+        field.start_of_line();
+        field.set_overwrite(true);
+        assert_eq!(field.push_char('ż'), Status::Updated);
+        assert_eq!(field.as_str(), "żą2");
+        field.set_overwrite(false);
+
+        // partial paste inserts fitting prefix
+        field.text_set("🦀".to_owned());
+        assert_eq!(field.paste_passthrough("ąę🦀".to_owned()), Status::Updated);
+        assert_eq!(field.as_str(), "🦀ąę");
+        assert_eq!(field.cursor(), "🦀ąę".len());
+        assert_eq!(field.paste_passthrough("x".to_owned()), Status::Skipped);
+
+        field.text_set("a ab".to_owned());
+        field.replace_token("🦀🦀🦀");
+        assert_eq!(field.as_str(), "a 🦀");
+        assert_eq!(field.cursor(), "a 🦀".len());
+
+        field.set_max_chars(None);
+        assert_eq!(field.push_char('x'), Status::Updated);
+    }
+
+    #[test]
+    fn replace_char_range() {
+        let mut field = TextField::new("src/ząb/🦀.rs".to_owned());
+        field.set_select(0, 3);
+        assert_eq!(field.replace_char_range(4..7, "zęby"), Status::Updated);
+        assert_eq!(field.as_str(), "src/zęby/🦀.rs");
+        assert_eq!(field.cursor(), "src/zęby".len());
+        assert_eq!(field.select(), None);
+        // no-op
+        assert_eq!(field.replace_char_range(4..8, "zęby"), Status::Skipped);
+        assert_eq!(field.replace_char_range(2..2, ""), Status::Skipped);
+        // out of bounds is clamped
+        assert_eq!(field.replace_char_range(10..40, ".txt"), Status::Updated);
+        assert_eq!(field.as_str(), "src/zęby/🦀.txt");
+        assert_eq!(field.cursor(), field.len());
+        assert_eq!(field.replace_char_range(30..40, "/"), Status::Updated);
+        assert_eq!(field.as_str(), "src/zęby/🦀.txt/");
+        assert_eq!(field.replace_char_range(5..5, "x"), Status::Updated);
+        assert_eq!(field.as_str(), "src/zxęby/🦀.txt/");
+        assert_eq!(field.cursor(), "src/zx".len());
+        field.undo();
+        assert_eq!(field.as_str(), "src/zęby/🦀.txt/");
+        // char limit
+        field.set_max_chars(Some(17));
+        assert_eq!(field.replace_char_range(0..3, "source"), Status::Updated);
+        assert_eq!(field.as_str(), "sourc/zęby/🦀.txt/");
+        field.set_readonly(true);
+        assert_eq!(field.replace_char_range(0..4, ""), Status::Skipped);
+    }
+
+    #[test]
+    fn max_chars() {
+        let mut field = TextField::new("🦀🦀".to_owned());
+        field.set_max_chars(Some(4));
+        assert_eq!(field.max_chars(), Some(4));
+        assert_eq!(field.paste_passthrough("a🦀bc".to_owned()), Status::Updated);
+        assert_eq!(field.as_str(), "🦀🦀a🦀");
+        assert_eq!(field.push_char('🦀'), Status::Skipped);
+        assert_eq!(field.push_char('b'), Status::Skipped);
+        field.go_left();
+        assert_eq!(field.backspace(), Status::Updated);
+        assert_eq!(field.push_char('🦀'), Status::Updated);
+        assert_eq!(field.as_str(), "🦀🦀🦀🦀");
+        assert_eq!(field.cursor(), 12);
+    }
+
+    #[test]
+    fn cursor_set_boundaries() {
+        let mut field = TextField::new("a🦀b".to_owned());
+        assert_eq!(field.cursor_set(3), Status::UpdatedCursor);
+        assert_eq!(field.cursor(), 1);
+        assert_eq!(field.get_cursor_range(), Some(1..5));
+        assert_eq!(field.cursor_set(1), Status::Skipped);
+        assert_eq!(field.cursor_set(99), Status::UpdatedCursor);
+        assert_eq!(field.cursor(), 6);
+        assert_eq!(field.cursor_set_char(2), Status::UpdatedCursor);
+        assert_eq!(field.cursor(), 5);
+        assert_eq!(field.cursor_char(), 2);
+        assert_eq!(field.cursor_set_char(99), Status::UpdatedCursor);
+        assert_eq!(field.cursor(), 6);
+        field.push_char('c');
+        assert_eq!(field.as_str(), "a🦀bc");
+    }
+
+    #[test]
+    fn char_indices() {
+        let mut field = TextField::new("a🦀🦀b 🦀c".to_owned());
+        assert_eq!(field.cursor(), 16);
+        assert_eq!(field.cursor_char(), 7);
+        assert_eq!(field.select_chars(), None);
+        field.go_left();
+        field.select_left();
+        field.select_left();
+        assert_eq!(field.select(), Some((10, 15)));
+        assert_eq!(field.select_chars(), Some((4, 6)));
+        assert_eq!(field.select_chars(), Some((4, 6)));
+        assert_eq!(field.cursor_char(), 4);
+        field.start_of_line();
+        field.select_right();
+        field.select_right();
+        assert_eq!(field.select(), Some((0, 5)));
+        assert_eq!(field.select_chars(), Some((0, 2)));
+
+        // cache is dropped on text change
+        field.cursor_set(4);
+        field.text_set("🦀a".to_owned());
+        field.cursor_set(4);
+        assert_eq!(field.cursor_char(), 1);
+        field.select_all();
+        field.paste_passthrough("abcd".to_owned());
+        assert_eq!(field.cursor(), 4);
+        assert_eq!(field.cursor_char(), 4);
+        field.undo();
+        assert_eq!(field.cursor_char(), 2);
+        assert_eq!(field.select_chars(), Some((0, 2)));
+    }
+
+    #[test]
+    fn input_history() {
+        let mut field = TextField::default();
+        field.text_set("ls".to_owned());
+        field.commit();
+        assert_eq!(field.history_prev(), Status::Skipped);
+
+        field.enable_history(2);
+        for cmd in ["cd src", "ls", "ls", "", "cat 🦀.rs"] {
+            field.text_set(cmd.to_owned());
+            field.commit();
+        }
+        field.text_set("in progr".to_owned());
+        assert_eq!(field.history_next(), Status::Skipped);
+        assert_eq!(field.history_prev(), Status::Updated);
+        assert_eq!(field.as_str(), "cat 🦀.rs");
+        assert_eq!(field.history_prev(), Status::Updated);
+        assert_eq!(field.as_str(), "ls");
+        assert_eq!(field.history_prev(), Status::Skipped);
+        assert_eq!(field.history_next(), Status::Updated);
+        assert_eq!(field.as_str(), "cat 🦀.rs");
+        assert_eq!(field.history_next(), Status::Updated);
+        assert_eq!(field.as_str(), "in progr");
+        assert_eq!(field.cursor(), 8);
+        assert_eq!(field.history_next(), Status::Skipped);
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_input_history() {
+        let mut field = TextField::new("ls".to_owned());
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(field.map(up), None);
+        field.enable_history(10);
+        field.commit();
+        field.text_take();
+        assert_eq!(field.map(up), Some(Status::Updated));
+        assert_eq!(field.as_str(), "ls");
+        assert_eq!(field.map(down), Some(Status::Updated));
+        assert!(field.is_empty());
+    }
+
+    #[test]
+    fn readonly() {
+        let mut field = TextField::new("some 🦀 text".to_owned());
+        field.set_readonly(true);
+        assert!(field.is_readonly());
+        assert_eq!(field.push_char('x'), Status::Skipped);
+        assert_eq!(field.backspace(), Status::Skipped);
+        assert_eq!(field.del_word_left(), Status::Skipped);
+        assert_eq!(field.paste_passthrough("x".to_owned()), Status::Skipped);
+        field.replace_token("x");
+        assert_eq!(field.as_str(), "some 🦀 text");
+        assert_eq!(field.go_left(), Status::UpdatedCursor);
+        assert_eq!(field.select_jump_left(), Status::UpdatedCursor);
+        assert_eq!(field.del(), Status::Skipped);
+        assert_eq!(field.cut(), None);
+        assert_eq!(field.copy().as_deref(), Some("tex"));
+        assert_eq!(field.undo(), Status::Skipped);
+        assert_eq!(field.as_str(), "some 🦀 text");
+
+        // values can be still set by the owner
+        field.text_set("/home".to_owned());
+        assert_eq!(field.as_str(), "/home");
+        field.set_readonly(false);
+        assert_eq!(field.push_char('/'), Status::Updated);
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_readonly() {
+        let mut field = TextField::new("abc".to_owned());
+        field.set_readonly(true);
+        for code in [KeyCode::Char('x'), KeyCode::Backspace, KeyCode::Delete] {
+            assert_eq!(
+                field.map(KeyEvent::new(code, KeyModifiers::NONE)),
+                Some(Status::Skipped)
+            );
+        }
+        let ctrl_z = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(field.map(ctrl_z), Some(Status::Skipped));
+        assert_eq!(
+            field.map(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE)),
+            Some(Status::UpdatedCursor)
+        );
+        assert_eq!(field.as_str(), "abc");
+    }
+
+    #[test]
+    fn paste_flattened() {
+        let mut field = TextField::new("$ ".to_owned());
+        assert_eq!(
+            field.paste_passthrough("ls\n-la".to_owned()),
+            Status::Skipped
+        );
+        assert_eq!(
+            field.paste_flattened("ls\r\n-la\rsrc\n🦀".to_owned()),
+            Status::Updated
+        );
+        assert_eq!(field.as_str(), "$ ls -la src 🦀");
+        assert_eq!(field.cursor(), field.len());
+    }
+
+    #[test]
+    fn insert_str_at_cursor() {
+        let mut field = TextField::new("fn () {}".to_owned());
+        field.cursor_set(3);
+        assert_eq!(field.insert_str_at_cursor("main"), Status::Updated);
+        assert_eq!(field.as_str(), "fn main() {}");
+        assert_eq!(field.cursor(), 7);
+        field.select_jump_left();
+        assert_eq!(field.insert_str_at_cursor("ąę"), Status::Updated);
+        assert_eq!(field.as_str(), "fn ąę() {}");
+        assert_eq!(field.cursor(), 7);
+        assert_eq!(field.select(), None);
+        assert_eq!(field.insert_str_at_cursor("a\nb"), Status::Skipped);
+        assert_eq!(field.insert_str_at_cursor(""), Status::Skipped);
+        field.set_readonly(true);
+        assert_eq!(field.insert_str_at_cursor("x"), Status::Skipped);
+        assert_eq!(field.as_str(), "fn ąę() {}");
+    }
+
+    #[derive(Default)]
+    struct MockClipboard(Option<String>);
+
+    impl Clipboard for MockClipboard {
+        fn get(&mut self) -> Option<String> {
+            self.0.clone()
+        }
+
+        fn set(&mut self, clip: &str) {
+            self.0 = Some(clip.to_owned());
+        }
     }
 
     #[test]
-    fn get_select() {
-        let mut t = TextField::default();
-        t.select = Some(10);
-        t.char = 5;
-        assert_eq!(t.select().unwrap(), (5, 10));
-        t.select = Some(3);
-        t.char = 8;
-        assert_eq!(t.select().unwrap(), (3, 8));
+    fn clipboard() {
+        let mut clip = MockClipboard::default();
+        let mut field = TextField::new("ls -la".to_owned());
+        field.copy_to(&mut clip);
+        assert_eq!(clip.0, None);
+        assert_eq!(field.paste_from(&mut clip), Status::Skipped);
+        field.select_jump_left();
+        field.copy_to(&mut clip);
+        assert_eq!(clip.0.as_deref(), Some("la"));
+        assert_eq!(field.cut_to(&mut clip), Status::Updated);
+        assert_eq!(field.as_str(), "ls -");
+        assert_eq!(field.cut_to(&mut clip), Status::Skipped);
+        clip.set("a\r\nsrc");
+        assert_eq!(field.paste_from(&mut clip), Status::Updated);
+        assert_eq!(field.as_str(), "ls -a src");
+        field.set_allow_copy(false);
+        field.select_all();
+        field.copy_to(&mut clip);
+        assert_eq!(clip.0.as_deref(), Some("a\r\nsrc"));
+        field.set_allow_copy(true);
+        field.set_readonly(true);
+        field.select_all();
+        assert_eq!(field.cut_to(&mut clip), Status::Skipped);
+        assert_eq!(field.paste_from(&mut clip), Status::Skipped);
+        assert_eq!(field.as_str(), "ls -a src");
+        assert_eq!(clip.0.as_deref(), Some("a\r\nsrc"));
     }
 
+    #[cfg(all(feature = "arboard_clipboard", target_os = "linux"))]
     #[test]
-    fn test_cut() {
-        let mut t = TextField::new("some text".into());
-        assert_eq!(t.select_jump_left(), Status::UpdatedCursor);
-        let cut = t.cut().unwrap();
-        assert_eq!("text", cut);
-        assert!(t.select().is_none());
-        assert_eq!(t.char, 5);
+    fn system_clipboard_headless() {
+        if std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            return;
+        }
+        let mut field = TextField::new("ls -la".to_owned());
+        field.select_all();
+        field.copy_to_clipboard();
+        assert_eq!(field.cut_to_clipboard(), Status::Skipped);
+        assert_eq!(field.as_str(), "ls -la");
+        assert_eq!(field.paste_from_clipboard(), Status::Skipped);
+        assert_eq!(field.as_str(), "ls -la");
     }
 
     #[test]
@@ -879,6 +3718,78 @@ mod test {
         assert_eq!(field.as_str(), "a ad");
     }
 
+    #[test]
+    fn del_word_left() {
+        let mut field = TextField::new("zażółć 🦀gęślą".to_owned());
+        assert_eq!(field.del_word_left(), Status::Updated);
+        assert_eq!(field.as_str(), "zażółć 🦀");
+        assert_eq!(field.del_word_left(), Status::Updated);
+        assert_eq!(field.as_str(), "zażółć ");
+        assert_eq!(field.del_word_left(), Status::Updated);
+        assert_eq!(field.as_str(), "");
+        assert_eq!(field.cursor(), 0);
+        assert_eq!(field.del_word_left(), Status::Skipped);
+
+        field.text_set("ą🦀bę c".to_owned());
+        field.go_left();
+        field.go_left();
+        assert_eq!(field.del_word_left(), Status::Updated);
+        assert_eq!(field.as_str(), "ą🦀 c");
+        assert_eq!(field.cursor(), "ą🦀".len());
+
+        field.text_set("ab cd".to_owned());
+        field.select_left();
+        field.select_left();
+        field.select_left();
+        assert_eq!(field.del_word_left(), Status::Updated);
+        assert_eq!(field.as_str(), "ab");
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_del_word_left() {
+        let mut field = TextField::new("some text".to_owned());
+        field.map(KeyEvent::new(KeyCode::Backspace, KeyModifiers::CONTROL));
+        assert_eq!(field.as_str(), "some ");
+        field.map(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(field.as_str(), "some");
+    }
+
+    #[test]
+    fn kill_line() {
+        let mut field = TextField::new("zażółć 🦀gęślą".to_owned());
+        field.go_left();
+        field.go_left();
+        field.select_left();
+        assert_eq!(field.kill_to_end(), ("ślą".to_owned(), Status::Updated));
+        assert_eq!(field.as_str(), "zażółć 🦀gę");
+        assert_eq!(field.select(), None);
+        assert_eq!(field.kill_to_end(), (String::new(), Status::Skipped));
+        field.go_left();
+        field.go_left();
+        assert_eq!(
+            field.kill_to_start(),
+            ("zażółć 🦀".to_owned(), Status::Updated)
+        );
+        assert_eq!(field.as_str(), "gę");
+        assert_eq!(field.cursor(), 0);
+        assert_eq!(field.kill_to_start(), (String::new(), Status::Skipped));
+        field.undo();
+        assert_eq!(field.as_str(), "zażółć 🦀gę");
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_kill_line() {
+        let mut field = TextField::new("some text".to_owned());
+        field.jump_left();
+        field.map(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert_eq!(field.as_str(), "some ");
+        field.go_left();
+        field.map(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(field.as_str(), " ");
+    }
+
     #[cfg(feature = "crossterm_backend")]
     #[test]
     fn test_del() {
@@ -910,6 +3821,70 @@ mod test {
         assert_eq!(field.as_str(), "a ad");
     }
 
+    #[test]
+    fn del_word_right_boundaries() {
+        let mut field = TextField::new("ąę🦀 x".to_owned());
+        assert_eq!(field.del_word_right(), Status::Skipped);
+        field.cursor_set(0);
+        assert_eq!(field.del_word_right(), Status::Updated);
+        assert_eq!(field.as_str(), "🦀 x");
+        assert_eq!(field.del_word_right(), Status::Updated);
+        assert_eq!(field.as_str(), " x");
+        assert_eq!(field.del_word_right(), Status::Updated);
+        assert!(field.is_empty());
+        assert_eq!(field.del_word_right(), Status::Skipped);
+        assert_eq!(
+            field.apply(Key::Backspace, KeyMod::CONTROL),
+            Some(Status::Skipped)
+        );
+    }
+
+    #[test]
+    fn del_word_right() {
+        let mut field = TextField::new("a a🦀🦀ssd asd".to_owned());
+        field.cursor_set(3);
+        assert_eq!(
+            field.apply(Key::Delete, KeyMod::CONTROL),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.char, 3);
+        assert_eq!(field.as_str(), "a a🦀ssd asd");
+        field.del_word_right();
+        assert_eq!(field.char, 3);
+        assert_eq!(field.as_str(), "a a asd");
+        assert_eq!(field.del_word_right(), Status::Updated);
+        assert_eq!(field.char, 3);
+        assert_eq!(field.as_str(), "a a");
+        assert_eq!(field.del_word_right(), Status::Skipped);
+        assert_eq!(field.as_str(), "a a");
+
+        field.text_set("a🦀ssd asd".to_owned());
+        field.cursor_set(0);
+        field.select_right();
+        field.select_right();
+        assert_eq!(field.del_word_right(), Status::Updated);
+        assert_eq!(field.char, 0);
+        assert_eq!(field.as_str(), "ssd asd");
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn map_del_word_right() {
+        let mut field = TextField::new("a a🦀🦀ssd asd".to_owned());
+        field.cursor_set(3);
+        assert_eq!(
+            field.map(KeyEvent::new(KeyCode::Delete, KeyModifiers::CONTROL)),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.char, 3);
+        assert_eq!(field.as_str(), "a a🦀ssd asd");
+        assert_eq!(
+            field.map(KeyEvent::new(KeyCode::Delete, KeyModifiers::empty())),
+            Some(Status::Updated)
+        );
+        assert_eq!(field.as_str(), "a assd asd");
+    }
+
     #[cfg(feature = "crossterm_backend")]
     #[test]
     fn select() {
@@ -1321,6 +4296,285 @@ mod test {
         assert_eq!(field.copy().unwrap(), "data");
     }
 
+    #[test]
+    fn backspace_at_start_bell() {
+        let mut field = TextField::new("a".to_owned());
+        field.start_of_line();
+        assert_eq!(field.backspace(), Status::Skipped);
+        assert_eq!(field.as_str(), "a");
+
+        let mut backend = MockedBackend::init();
+        backend.bell();
+        assert_eq!(
+            backend.drain(),
+            [(MockedStyle::default(), "<<bell>>".to_owned())]
+        );
+
+        // default visual bell flashes the rect and the next render paints over it
+        let mut backend = BufferBackend::<MockedStyle>::sized(8, 2);
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 8,
+        };
+        backend.visual_bell(Rect::new(0, 0, 8, 1), MockedStyle::reversed());
+        for col in 0..8 {
+            let cell = backend.cell(0, col).unwrap();
+            assert_eq!(cell.ch, Some(' '));
+            assert_eq!(cell.style, MockedStyle::reversed());
+        }
+        assert_eq!(backend.cell(1, 0).unwrap().style, MockedStyle::default());
+        field.widget(
+            line,
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(backend.to_string_lines()[0], " >> a   ");
+        assert_eq!(backend.cell(0, 0).unwrap().style, MockedStyle::default());
+        assert_eq!(backend.cell(0, 4).unwrap().style, MockedStyle::reversed());
+        assert_eq!(backend.cell(0, 6).unwrap().style, MockedStyle::default());
+    }
+
+    #[test]
+    fn cursor_screen_position() {
+        let mut field = TextField::new("a🦀b".to_owned());
+        field.char = 5;
+        let line = Line {
+            row: 2,
+            col: 1,
+            width: 50,
+        };
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 2, col: 8 })
+        );
+        field.char = 0;
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 2, col: 5 })
+        );
+        let line = Line {
+            row: 2,
+            col: 1,
+            width: 4,
+        };
+        assert_eq!(field.cursor_screen_position(&line), None);
+    }
+
+    /// screen col of the first cell printed with cursor style
+    fn rendered_cursor_col(backend: &mut MockedBackend, cursor_style: MockedStyle) -> Option<u16> {
+        let mut col = 0;
+        for (style, text) in backend.drain() {
+            if let Some(go_to) = text.strip_prefix("<<go to row: ") {
+                let (_, go_col) = go_to.trim_end_matches(">>").split_once(" col: ")?;
+                col = go_col.parse().ok()?;
+            } else if style == cursor_style {
+                return Some(col);
+            } else if !text.starts_with("<<") {
+                col += UnicodeWidthStr::width(text.as_str()) as u16;
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn cursor_screen_position_rendered() {
+        let mut backend = MockedBackend::init();
+        let line = Line {
+            row: 1,
+            col: 3,
+            width: 12,
+        };
+        let mut field = TextField::new("some long text".to_owned());
+        for cursor in [0, 5, 9, 14] {
+            field.cursor_set(cursor);
+            field.widget(
+                line.clone(),
+                MockedStyle::reversed(),
+                MockedStyle::bold(),
+                &mut backend,
+            );
+            assert_eq!(
+                rendered_cursor_col(&mut backend, MockedStyle::reversed()),
+                field
+                    .cursor_screen_position(&line)
+                    .map(|position| position.col)
+            );
+        }
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 1, col: 14 })
+        );
+
+        let mut field = TextField::new("a🦀b🦀🦀c🦀".to_owned());
+        for cursor in 0..=field.char_len() {
+            field.cursor_set_char(cursor);
+            field.widget(
+                line.clone(),
+                MockedStyle::reversed(),
+                MockedStyle::bold(),
+                &mut backend,
+            );
+            assert_eq!(
+                rendered_cursor_col(&mut backend, MockedStyle::reversed()),
+                field
+                    .cursor_screen_position(&line)
+                    .map(|position| position.col)
+            );
+        }
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 1, col: 14 })
+        );
+    }
+
+    #[test]
+    fn cursor_screen_position_offset() {
+        let mut field = TextField::new("🦀🦀🦀🦀".to_owned());
+        let line = Line {
+            row: 0,
+            col: 1,
+            width: 10,
+        };
+        // cursor at the end keeps one col - last two crabs are visible before it
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 0, col: 9 })
+        );
+        field.char = 12;
+        assert_eq!(
+            field.cursor_screen_position(&line),
+            Some(Position { row: 0, col: 7 })
+        );
+    }
+
+    #[test]
+    fn render_real_cursor() {
+        let mut field = TextField::new("a🦀b".to_owned());
+        field.set_real_cursor(true);
+        let mut backend = MockedBackend::init();
+        let line = Line {
+            row: 0,
+            col: 1,
+            width: 12,
+        };
+        field.widget(
+            line.clone(),
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "a🦀b".to_owned()),
+                (MockedStyle::default(), "<<padding: 4>>".to_owned()),
+            ]
+        );
+        field.select_left();
+        field.widget(
+            line,
+            MockedStyle::reversed(),
+            MockedStyle::bold(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned()),
+                (MockedStyle::default(), " >> ".to_owned()),
+                (MockedStyle::default(), "a🦀".to_owned()),
+                (MockedStyle::bold(), "b".to_owned()),
+                (MockedStyle::default(), "<<padding: 4>>".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn paste_sanitized() {
+        let mut field = TextField::new("ab".to_owned());
+        field.char = 1;
+        let clip = "x\r\ny\tz\x07".to_owned();
+        assert_eq!(
+            field.paste_sanitized(clip.clone(), PasteOptions::default()),
+            Status::Updated
+        );
+        assert_eq!(field.as_str(), "ax y    zb");
+        assert_eq!(field.char, 9);
+        let multiline = PasteOptions {
+            newline_to_space: false,
+            ..Default::default()
+        };
+        assert_eq!(field.paste_sanitized(clip, multiline), Status::Skipped);
+        assert_eq!(field.as_str(), "ax y    zb");
+    }
+
+    #[test]
+    fn overwrite_mode() {
+        let mut field = TextField::new("a🦀c".to_owned());
+        field.start_of_line();
+        field.set_overwrite(true);
+        assert!(field.is_overwrite());
+        assert_eq!(field.push_char('x'), Status::Updated);
+        assert_eq!(field.as_str(), "x🦀c");
+        assert_eq!(field.char, 1);
+        field.push_char('y');
+        assert_eq!(field.as_str(), "xyc");
+        assert_eq!(field.char, 2);
+        field.push_char('🦀');
+        assert_eq!(field.as_str(), "xy🦀");
+        assert_eq!(field.char, 6);
+        // end of text behaves like insert
+        field.push_char('z');
+        assert_eq!(field.as_str(), "xy🦀z");
+        // selection is replaced without removing extra char
+        field.start_of_line();
+        field.select_right();
+        field.push_char('a');
+        assert_eq!(field.as_str(), "ay🦀z");
+        field.set_overwrite(false);
+        field.push_char('b');
+        assert_eq!(field.as_str(), "aby🦀z");
+    }
+
+    #[test]
+    fn overwrite_undo() {
+        let mut field = TextField::new("a🦀c d".to_owned());
+        field.start_of_line();
+        field.toggle_overwrite();
+        for ch in "xy🦀 z".chars() {
+            field.push_char(ch);
+        }
+        assert_eq!(field.as_str(), "xy🦀 z");
+        field.undo();
+        assert_eq!(field.as_str(), "xy🦀 d");
+        field.undo();
+        assert_eq!(field.as_str(), "a🦀c d");
+        assert_eq!(field.cursor(), 0);
+        assert!(field.is_overwrite());
+    }
+
+    #[cfg(feature = "crossterm_backend")]
+    #[test]
+    fn overwrite_toggle_map() {
+        let mut field = TextField::new("abc".to_owned());
+        field.start_of_line();
+        assert_eq!(
+            field.map(KeyEvent::new(KeyCode::Insert, KeyModifiers::empty())),
+            Some(Status::UpdatedCursor)
+        );
+        assert!(field.is_overwrite());
+        field.map(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty()));
+        assert_eq!(field.as_str(), "xbc");
+        field.map(KeyEvent::new(KeyCode::Insert, KeyModifiers::empty()));
+        assert!(!field.is_overwrite());
+        field.map(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty()));
+        assert_eq!(field.as_str(), "xxbc");
+    }
+
     #[test]
     fn test_ord_status() {
         assert!(Status::Skipped < Status::UpdatedCursor);
@@ -1328,4 +4582,128 @@ mod test {
         assert!(Status::Updated > Status::Skipped);
         assert!(Status::Updated == Status::Updated);
     }
+
+    #[test]
+    fn widget_incremental_cursor_move() {
+        let mut field = TextField::new("a🦀bc".to_owned());
+        let mut backend = MockedBackend::init();
+        let mut prev = FieldRenderState::new();
+        let line = Line {
+            row: 1,
+            col: 2,
+            width: 20,
+        };
+        let cursor_style = MockedStyle::reversed();
+        field.widget_incremental(
+            line.clone(),
+            &mut prev,
+            cursor_style.clone(),
+            MockedStyle::default(),
+            &mut backend,
+        );
+        let mut full_backend = MockedBackend::init();
+        field.widget(
+            line.clone(),
+            cursor_style.clone(),
+            MockedStyle::default(),
+            &mut full_backend,
+        );
+        assert_eq!(backend.drain(), full_backend.drain());
+
+        // from end of text onto the wide char
+        field.cursor_set(1);
+        field.widget_incremental(
+            line.clone(),
+            &mut prev,
+            cursor_style.clone(),
+            MockedStyle::default(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (
+                    MockedStyle::default(),
+                    "<<go to row: 1 col: 11>>".to_owned()
+                ),
+                (MockedStyle::default(), " ".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 7>>".to_owned()),
+                (cursor_style.clone(), "🦀".to_owned()),
+            ]
+        );
+
+        // from the wide char to the next one
+        field.cursor_set(5);
+        field.widget_incremental(
+            line.clone(),
+            &mut prev,
+            cursor_style.clone(),
+            MockedStyle::default(),
+            &mut backend,
+        );
+        assert_eq!(
+            backend.drain(),
+            &[
+                (MockedStyle::default(), "<<go to row: 1 col: 7>>".to_owned()),
+                (MockedStyle::default(), "🦀".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 9>>".to_owned()),
+                (cursor_style.clone(), "b".to_owned()),
+            ]
+        );
+
+        // nothing changed
+        field.widget_incremental(
+            line,
+            &mut prev,
+            cursor_style,
+            MockedStyle::default(),
+            &mut backend,
+        );
+        assert!(backend.drain().is_empty());
+    }
+
+    #[test]
+    fn widget_incremental_fallback() {
+        let mut field = TextField::new("0123456789".to_owned());
+        let mut backend = MockedBackend::init();
+        let mut prev = FieldRenderState::new();
+        let line = Line {
+            row: 0,
+            col: 0,
+            width: 10,
+        };
+        let render =
+            |field: &TextField, prev: &mut FieldRenderState, backend: &mut MockedBackend| {
+                field.widget_incremental(
+                    line.clone(),
+                    prev,
+                    MockedStyle::reversed(),
+                    MockedStyle::default(),
+                    backend,
+                );
+                let mut full_backend = MockedBackend::init();
+                field.widget(
+                    line.clone(),
+                    MockedStyle::reversed(),
+                    MockedStyle::default(),
+                    &mut full_backend,
+                );
+                assert_eq!(backend.drain(), full_backend.drain());
+            };
+        render(&field, &mut prev, &mut backend);
+
+        // text change
+        field.text_set("01234".to_owned());
+        render(&field, &mut prev, &mut backend);
+
+        // offset change
+        field.text_set("0123456789".to_owned());
+        render(&field, &mut prev, &mut backend);
+        field.cursor_set(0);
+        render(&field, &mut prev, &mut backend);
+
+        // after reset
+        prev.reset();
+        render(&field, &mut prev, &mut backend);
+    }
 }
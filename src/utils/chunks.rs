@@ -1,3 +1,4 @@
+use super::UTFSafe;
 use std::str::{CharIndices, Chars};
 use unicode_width::UnicodeWidthChar;
 
@@ -52,7 +53,11 @@ pub struct ByteChunks<'a> {
 }
 
 impl<'a> ByteChunks<'a> {
+    /// yields nothing for 0 width
     pub fn new(text: &'a str, width: usize) -> Self {
+        if width == 0 {
+            return Self { text: "", width };
+        }
         Self { text, width }
     }
 
@@ -66,6 +71,9 @@ impl<'a> ByteChunks<'a> {
 impl<'a> Iterator for ByteChunks<'a> {
     type Item = StrChunks<'a>;
     fn next(&mut self) -> Option<Self::Item> {
+        if self.width == 0 {
+            return None;
+        }
         if self.text.len() >= self.width {
             let result = self.text.get(..self.width).map(|text| StrChunks {
                 text,
@@ -95,6 +103,7 @@ pub struct WriteChunks<'a> {
 }
 
 impl<'a> WriteChunks<'a> {
+    /// yields nothing for 0 width
     pub fn new(text: &'a str, width: usize) -> Self {
         Self {
             inner: text.char_indices(),
@@ -144,3 +153,117 @@ impl<'a> Iterator for WriteChunks<'a> {
         // (width, unsafe { self.text.get_unchecked(start..) }));
     }
 }
+
+/// Same as WriteChunks, but yields chunks from the end of the text,
+/// the last chunk (start of the text) may be narrower
+pub struct WriteChunksRev<'a> {
+    pub width: usize,
+    end_byte: usize,
+    text: &'a str,
+    inner: CharIndices<'a>,
+    width_offset: usize,
+}
+
+impl<'a> WriteChunksRev<'a> {
+    /// yields nothing for 0 width
+    pub fn new(text: &'a str, width: usize) -> Self {
+        Self {
+            inner: text.char_indices(),
+            text,
+            end_byte: text.len(),
+            width,
+            width_offset: 0,
+        }
+    }
+
+    #[allow(dead_code)]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.width == 0
+    }
+}
+
+impl<'a> Iterator for WriteChunksRev<'a> {
+    type Item = StrChunks<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.width == 0 {
+            return None;
+        }
+        let end = self.end_byte;
+        let mut width = self.width_offset;
+        while let Some((idx, ch)) = self.inner.next_back() {
+            let current_w = UnicodeWidthChar::width(ch).unwrap_or_default();
+            if self.width < width + current_w {
+                if current_w > self.width {
+                    self.width = 0;
+                    return None;
+                }
+                self.width_offset = current_w;
+                self.end_byte = idx + ch.len_utf8();
+                return Some(StrChunks {
+                    width,
+                    text: unsafe { self.text.get_unchecked(self.end_byte..end) },
+                });
+            };
+            width += current_w;
+        }
+        self.width = 0;
+        Some(StrChunks {
+            width,
+            text: unsafe { self.text.get_unchecked(..end) },
+        })
+    }
+}
+
+/// Word wrapping chunks - breaks lines on whitespace (consumed), words wider than width are hard wrapped
+/// yields nothing for 0 width
+pub struct WordChunks<'a> {
+    pub width: usize,
+    text: &'a str,
+}
+
+impl<'a> WordChunks<'a> {
+    pub fn new(text: &'a str, width: usize) -> Self {
+        Self { text, width }
+    }
+}
+
+impl<'a> Iterator for WordChunks<'a> {
+    type Item = StrChunks<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.width == 0 || self.text.is_empty() {
+            return None;
+        }
+        let (remaining, prefix) = self.text.truncate_width(self.width);
+        if prefix.len() == self.text.len() {
+            let text = std::mem::take(&mut self.text);
+            return Some(StrChunks {
+                text,
+                width: self.width - remaining,
+            });
+        }
+        if prefix.is_empty() {
+            // char wider than the line is emitted on its own (wider than the chunk width)
+            let ch = self.text.chars().next()?;
+            let (text, rest) = self.text.split_at(ch.len_utf8());
+            self.text = rest.trim_start();
+            return Some(StrChunks {
+                text,
+                width: UTFSafe::width(text),
+            });
+        }
+        let end = match self.text[prefix.len()..].starts_with(char::is_whitespace) {
+            true => prefix.len(),
+            false => match prefix.rfind(char::is_whitespace) {
+                Some(idx) if !prefix[..idx].trim_end().is_empty() => idx,
+                _ => prefix.len(),
+            },
+        };
+        let text = self.text[..end].trim_end();
+        self.text = self.text[end..].trim_start();
+        Some(StrChunks {
+            text,
+            width: UTFSafe::width(text),
+        })
+    }
+}
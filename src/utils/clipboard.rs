@@ -0,0 +1,36 @@
+/// Clipboard used by TextField / TextArea copy_to / cut_to / paste_from
+pub trait Clipboard {
+    /// returns None if clipboard is empty or can not be read
+    fn get(&mut self) -> Option<String>;
+    fn set(&mut self, clip: &str);
+}
+
+/// system clipboard (errors on get / set are ignored)
+#[cfg(feature = "arboard_clipboard")]
+impl Clipboard for arboard::Clipboard {
+    fn get(&mut self) -> Option<String> {
+        self.get_text().ok()
+    }
+
+    fn set(&mut self, clip: &str) {
+        let _ = self.set_text(clip);
+    }
+}
+
+#[cfg(feature = "arboard_clipboard")]
+thread_local! {
+    static SYSTEM_CLIPBOARD: std::cell::RefCell<Option<arboard::Clipboard>> = const { std::cell::RefCell::new(None) };
+}
+
+/// runs with system clipboard kept alive for the thread (on X11 / Wayland contents are lost when it is dropped),
+/// returns None if clipboard is not available (e.g. headless environment)
+#[cfg(feature = "arboard_clipboard")]
+pub(crate) fn with_system_clipboard<T>(f: impl FnOnce(&mut arboard::Clipboard) -> T) -> Option<T> {
+    SYSTEM_CLIPBOARD.with(|cell| {
+        let mut clipboard = cell.borrow_mut();
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().ok()?);
+        }
+        clipboard.as_mut().map(f)
+    })
+}
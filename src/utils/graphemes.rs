@@ -0,0 +1,71 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Counterpart of UTFSafe working on extended grapheme clusters instead of chars
+/// (combining marks, ZWJ sequences, flags and skin tones are never split)
+pub trait GraphemeSafe {
+    /// returns str that will fit into width of columns, removing graphemes at the end returning info about remaining width
+    fn truncate_width_graphemes(&self, width: usize) -> (usize, &str);
+    /// return split at grapheme idx
+    fn split_at_grapheme(&self, mid: usize) -> (&str, &str);
+    /// returns grapheme clusters len
+    fn grapheme_len(&self) -> usize;
+    /// calcs the width of first graphemes up to position
+    fn width_at_grapheme(&self, at: usize) -> usize;
+}
+
+impl GraphemeSafe for str {
+    #[inline]
+    fn truncate_width_graphemes(&self, mut width: usize) -> (usize, &str) {
+        for (idx, grapheme) in self.grapheme_indices(true) {
+            let grapheme_width = UnicodeWidthStr::width(grapheme);
+            if grapheme_width > width {
+                return (width, &self[..idx]);
+            }
+            width -= grapheme_width;
+        }
+        (width, self)
+    }
+
+    #[inline]
+    fn split_at_grapheme(&self, mid: usize) -> (&str, &str) {
+        match self.grapheme_indices(true).nth(mid) {
+            Some((idx, _)) => self.split_at(idx),
+            None => (self, ""),
+        }
+    }
+
+    #[inline]
+    fn grapheme_len(&self) -> usize {
+        self.graphemes(true).count()
+    }
+
+    #[inline]
+    fn width_at_grapheme(&self, at: usize) -> usize {
+        self.graphemes(true)
+            .take(at)
+            .fold(0, |sum, grapheme| sum + UnicodeWidthStr::width(grapheme))
+    }
+}
+
+impl GraphemeSafe for String {
+    #[inline]
+    fn truncate_width_graphemes(&self, width: usize) -> (usize, &str) {
+        self.as_str().truncate_width_graphemes(width)
+    }
+
+    #[inline]
+    fn split_at_grapheme(&self, mid: usize) -> (&str, &str) {
+        self.as_str().split_at_grapheme(mid)
+    }
+
+    #[inline]
+    fn grapheme_len(&self) -> usize {
+        self.as_str().grapheme_len()
+    }
+
+    #[inline]
+    fn width_at_grapheme(&self, at: usize) -> usize {
+        self.as_str().width_at_grapheme(at)
+    }
+}
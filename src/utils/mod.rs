@@ -1,6 +1,16 @@
 mod chunks;
-pub use chunks::{ByteChunks, CharLimitedWidths, StrChunks, WriteChunks};
-use std::ops::Range;
+mod clipboard;
+mod graphemes;
+mod paste;
+pub use chunks::{
+    ByteChunks, CharLimitedWidths, StrChunks, WordChunks, WriteChunks, WriteChunksRev,
+};
+#[cfg(feature = "arboard_clipboard")]
+pub(crate) use clipboard::with_system_clipboard;
+pub use clipboard::Clipboard;
+pub use graphemes::GraphemeSafe;
+pub use paste::{normalize_paste, sanitize_control_chars, PasteOptions, PasteTabs};
+use std::{borrow::Cow, ops::Range};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub type Utf8Byte = usize;
@@ -16,6 +26,9 @@ pub trait UTFSafe {
     fn truncate_if_wider(&self, width: usize) -> Result<&str, usize>;
     /// return Some(&str) truncated from start if wider than allowed width
     fn truncate_if_wider_start(&self, width: usize) -> Result<&str, usize>;
+    /// keeps the start and the end with ellipsis in the middle (wide chars are never split),
+    /// degrades to truncate_width if width can not fit ellipsis and one char from each side
+    fn truncate_width_middle(&self, width: usize, ellipsis: &str) -> Cow<'_, str>;
     /// split on width
     fn width_split(&self, width: usize) -> (&str, Option<&str>);
     /// returns display len of the str
@@ -123,6 +136,31 @@ impl UTFSafe for str {
         Err(current_width)
     }
 
+    fn truncate_width_middle(&self, width: usize, ellipsis: &str) -> Cow<'_, str> {
+        if UnicodeWidthStr::width(self) <= width {
+            return Cow::Borrowed(self);
+        }
+        let first_width = self
+            .chars()
+            .next()
+            .and_then(UnicodeWidthChar::width)
+            .unwrap_or(0);
+        let last_width = self
+            .chars()
+            .next_back()
+            .and_then(UnicodeWidthChar::width)
+            .unwrap_or(0);
+        let ellipsis_width = UnicodeWidthStr::width(ellipsis);
+        if width < ellipsis_width + first_width + last_width {
+            return Cow::Borrowed(self.truncate_width(width).1);
+        }
+        let budget = width - ellipsis_width;
+        let tail_width = std::cmp::max(budget / 2, last_width);
+        let (remaining, head) = self.truncate_width(budget - tail_width);
+        let (_, tail) = self.truncate_width_start(tail_width + remaining);
+        Cow::Owned(format!("{head}{ellipsis}{tail}"))
+    }
+
     #[inline]
     fn width_split(&self, mut width: usize) -> (&str, Option<&str>) {
         for (current_mid, ch) in self.char_indices() {
@@ -233,6 +271,11 @@ impl UTFSafe for String {
         self.as_str().truncate_if_wider_start(width)
     }
 
+    #[inline]
+    fn truncate_width_middle(&self, width: usize, ellipsis: &str) -> Cow<'_, str> {
+        self.as_str().truncate_width_middle(width, ellipsis)
+    }
+
     #[inline]
     fn width_split(&self, width: usize) -> (&str, Option<&str>) {
         self.as_str().width_split(width)
@@ -417,6 +460,41 @@ fn prev_char_utf8_and_utf16(text: &str, idx: usize) -> (Utf8Byte, Utf16Byte) {
     )
 }
 
+/// (widest line, rows) of text word wrapped within max_width, each line ("\n") takes at least one row
+/// (char wider than max_width takes a row on its own and is counted with its width)
+pub fn measure_wrapped(text: &str, max_width: usize) -> (usize, u16) {
+    measure(text.split('\n'), max_width)
+}
+
+/// (widest line, rows) of pre-split lines word wrapped within max_width
+pub fn measure_lines(lines: &[&str], max_width: usize) -> (usize, u16) {
+    measure(lines.iter().copied(), max_width)
+}
+
+fn measure<'a>(lines: impl Iterator<Item = &'a str>, max_width: usize) -> (usize, u16) {
+    if max_width == 0 {
+        return (0, 0);
+    }
+    let mut width = 0;
+    let mut rows: u16 = 0;
+    for line in lines {
+        let mut line_rows: u16 = 0;
+        for chunk in WordChunks::new(line.trim_end_matches('\r'), max_width) {
+            width = std::cmp::max(width, chunk.width);
+            line_rows = line_rows.saturating_add(1);
+        }
+        rows = rows.saturating_add(std::cmp::max(1, line_rows));
+    }
+    (width, rows)
+}
+
+/// truncates str to width keeping the start and the end with ellipsis in the middle
+/// (useful for paths "/home/…/project/file.rs"), custom ellipsis: UTFSafe::truncate_width_middle
+#[inline]
+pub fn truncate_middle(text: &str, width: usize) -> Cow<'_, str> {
+    text.truncate_width_middle(width, "…")
+}
+
 #[inline]
 fn maybe_prev_char_bytes_end(text: &str, idx: usize) -> Option<usize> {
     if idx == 0 {
@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+
+/// Handling of tabs in pasted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteTabs {
+    Keep,
+    /// replaced with number of spaces
+    Expand(usize),
+    Strip,
+}
+
+/// Options for normalize_paste - default is fit for single line field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasteOptions {
+    /// newlines ("\r\n" / "\r" / "\n") are replaced with single space, otherwise normalized to "\n"
+    pub newline_to_space: bool,
+    pub tabs: PasteTabs,
+}
+
+impl Default for PasteOptions {
+    fn default() -> Self {
+        Self {
+            newline_to_space: true,
+            tabs: PasteTabs::Expand(4),
+        }
+    }
+}
+
+/// removes control chars (except new line and tab)
+pub fn sanitize_control_chars(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_sanitized) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().filter(|ch| !is_sanitized(*ch)).collect())
+}
+
+/// normalizes raw clipboard content (from bracketed paste) based on options
+pub fn normalize_paste(clip: &str, opts: PasteOptions) -> Cow<'_, str> {
+    if !clip.chars().any(char::is_control) {
+        return Cow::Borrowed(clip);
+    }
+    let newline = if opts.newline_to_space { ' ' } else { '\n' };
+    let mut normalized = String::with_capacity(clip.len());
+    let mut chars = clip.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                normalized.push(newline);
+            }
+            '\n' => normalized.push(newline),
+            '\t' => match opts.tabs {
+                PasteTabs::Keep => normalized.push('\t'),
+                PasteTabs::Expand(width) => normalized.extend(std::iter::repeat(' ').take(width)),
+                PasteTabs::Strip => (),
+            },
+            _ => normalized.push(ch),
+        }
+    }
+    match sanitize_control_chars(&normalized) {
+        Cow::Borrowed(..) => Cow::Owned(normalized),
+        Cow::Owned(sanitized) => Cow::Owned(sanitized),
+    }
+}
+
+#[inline]
+fn is_sanitized(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t'
+}
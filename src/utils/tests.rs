@@ -1,6 +1,11 @@
 use crate::utils::chunks::ByteChunks;
 
-use super::{CharLimitedWidths, StrChunks, UTFSafe, UTFSafeStringExt, WriteChunks};
+use super::{
+    measure_lines, measure_wrapped, normalize_paste, sanitize_control_chars, truncate_middle,
+    CharLimitedWidths, GraphemeSafe, PasteOptions, PasteTabs, StrChunks, UTFSafe, UTFSafeStringExt,
+    WordChunks, WriteChunks, WriteChunksRev,
+};
+use std::borrow::Cow;
 const TEXT: &str = "123🚀13";
 
 #[test]
@@ -437,6 +442,115 @@ fn test_chunks_short() {
     assert_eq!(chunks.next(), None);
 }
 
+#[test]
+fn test_chunks_rev() {
+    let text = "123🚀asdas123123123afsadasras";
+    let mut chunks = WriteChunksRev::new(text, 4);
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "sras"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "sada"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "23af"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "1231"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "s123"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "asda"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 4,
+            text: "23🚀"
+        })
+    );
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 1,
+            text: "1"
+        })
+    );
+    assert_eq!(chunks.next(), None);
+}
+
+#[test]
+fn test_chunks_rev_wide_end() {
+    let text = "ab🚀cd🚀";
+    let chunks: Vec<_> = WriteChunksRev::new(text, 3).collect();
+    assert_eq!(
+        chunks,
+        [
+            StrChunks {
+                width: 3,
+                text: "d🚀"
+            },
+            StrChunks {
+                width: 3,
+                text: "🚀c"
+            },
+            StrChunks {
+                width: 2,
+                text: "ab"
+            },
+        ]
+    );
+    // reversed chunks join to the text
+    let mut parts: Vec<_> = WriteChunksRev::new(text, 3)
+        .map(|chunk| chunk.text)
+        .collect();
+    parts.reverse();
+    assert_eq!(parts.concat(), text);
+    // wide char wider than the chunk
+    assert_eq!(WriteChunksRev::new(text, 1).next(), None);
+    assert_eq!(WriteChunksRev::new(text, 0).next(), None);
+}
+
+#[test]
+fn test_chunks_rev_short() {
+    let text = "123";
+    let mut chunks = WriteChunksRev::new(text, 5);
+    assert_eq!(
+        chunks.next(),
+        Some(StrChunks {
+            width: 3,
+            text: "123"
+        })
+    );
+    assert_eq!(chunks.next(), None);
+}
+
 #[test]
 fn test_chunks_byte() {
     let text = "123asdas123123123afsadasras";
@@ -519,3 +633,183 @@ fn test_char_limited_chunk() {
     assert_eq!(chunks.next(), Some(('a', 1)));
     assert_eq!(chunks.next(), None);
 }
+
+#[test]
+fn test_normalize_paste() {
+    let clip = "first\r\n\tsecond\x07 line\r\nthird\rend\x1b[0m";
+    assert_eq!(
+        normalize_paste(clip, PasteOptions::default()),
+        "first     second line third end[0m"
+    );
+    let multiline = PasteOptions {
+        newline_to_space: false,
+        tabs: PasteTabs::Keep,
+    };
+    assert_eq!(
+        normalize_paste(clip, multiline),
+        "first\n\tsecond line\nthird\nend[0m"
+    );
+    let stripped = PasteOptions {
+        newline_to_space: true,
+        tabs: PasteTabs::Strip,
+    };
+    assert_eq!(
+        normalize_paste(clip, stripped),
+        "first second line third end[0m"
+    );
+    let expanded = PasteOptions {
+        newline_to_space: false,
+        tabs: PasteTabs::Expand(2),
+    };
+    assert_eq!(normalize_paste("a\r\n\r\n\tb", expanded), "a\n\n  b");
+    assert!(matches!(
+        normalize_paste("clean 🚀", PasteOptions::default()),
+        Cow::Borrowed(..)
+    ));
+}
+
+#[test]
+fn test_sanitize_control_chars() {
+    assert_eq!(sanitize_control_chars("a\x00b\tc\nd\x7f"), "ab\tc\nd");
+    assert!(matches!(sanitize_control_chars("a\tb"), Cow::Borrowed(..)));
+}
+
+#[test]
+fn test_truncate_middle() {
+    let path = "/home/user/project/file.rs";
+    assert_eq!(truncate_middle(path, 30), path);
+    assert!(matches!(truncate_middle(path, 26), Cow::Borrowed(..)));
+    assert_eq!(truncate_middle(path, 15), "/home/u…file.rs");
+    assert_eq!(truncate_middle(path, 16), "/home/us…file.rs");
+    // too narrow for ellipsis and a char from each side - truncated at the end
+    assert_eq!(truncate_middle(path, 2), "/h");
+    assert_eq!(truncate_middle(path, 1), "/");
+    assert_eq!(truncate_middle(path, 0), "");
+    // wide chars - unused width from head is given to tail
+    assert_eq!(truncate_middle("🚀🚀🚀abcd", 6), "🚀…bcd");
+    assert_eq!(truncate_middle("abcd🚀🚀🚀", 6), "abc…🚀");
+}
+
+#[test]
+fn test_zero_width_chunks() {
+    assert_eq!(ByteChunks::new("abc", 0).next(), None);
+    assert_eq!(WriteChunks::new("abc", 0).next(), None);
+    assert_eq!(WriteChunks::new("🚀", 1).next(), None);
+}
+
+#[test]
+fn test_truncate_width_middle() {
+    let path = "🦀/src/🚀🚀/main.rs";
+    assert!(matches!(
+        path.truncate_width_middle(30, "…"),
+        Cow::Borrowed(..)
+    ));
+    let expected = [
+        "",
+        "🦀",
+        "🦀/",
+        "🦀…s",
+        "🦀…rs",
+        "🦀/…rs",
+        "🦀/….rs",
+        "🦀/s….rs",
+        "🦀/s…n.rs",
+        "🦀/sr…n.rs",
+    ];
+    for (width, expected) in (1..=10).zip(expected) {
+        let truncated = path.truncate_width_middle(width, "…");
+        assert_eq!(truncated, expected, "width {width}");
+        assert!(UTFSafe::width(truncated.as_ref()) <= width);
+    }
+    // wide chars are never split on either side
+    let wide = "🚀🚀🚀🚀🚀🚀";
+    assert_eq!(wide.truncate_width_middle(5, "…"), "🚀…🚀");
+    assert_eq!(wide.truncate_width_middle(6, "…"), "🚀…🚀");
+    assert_eq!(wide.truncate_width_middle(7, "…"), "🚀…🚀🚀");
+    assert_eq!(wide.truncate_width_middle(4, "…"), "🚀🚀");
+    assert_eq!(
+        String::from(wide).truncate_width_middle(8, ".."),
+        "🚀..🚀🚀"
+    );
+}
+
+#[test]
+fn test_word_chunks() {
+    let chunks: Vec<_> = WordChunks::new("the quick  brown fox", 9)
+        .map(|chunk| chunk.text)
+        .collect();
+    assert_eq!(chunks, ["the quick", "brown fox"]);
+    let chunks: Vec<_> = WordChunks::new("a extraordinary 🦀🦀🦀", 6)
+        .map(|chunk| (chunk.text, chunk.width))
+        .collect();
+    assert_eq!(
+        chunks,
+        [
+            ("a", 1),
+            ("extrao", 6),
+            ("rdinar", 6),
+            ("y", 1),
+            ("🦀🦀🦀", 6)
+        ]
+    );
+    assert_eq!(WordChunks::new("abc", 0).next(), None);
+    let chunks: Vec<_> = WordChunks::new("a🦀 b", 1)
+        .map(|chunk| (chunk.text, chunk.width))
+        .collect();
+    assert_eq!(chunks, [("a", 1), ("🦀", 2), ("b", 1)]);
+}
+
+#[test]
+fn test_measure_wrapped() {
+    let text = "Are you sure you want to delete the selected files?";
+    // "Are you sure" / "you want to" / "delete the" / "selected" / "files?"
+    assert_eq!(measure_wrapped(text, 13), (12, 5));
+    // "Are you sure you want to" / "delete the selected files?"
+    assert_eq!(measure_wrapped(text, 30), (26, 2));
+    assert_eq!(measure_wrapped(text, 60), (51, 1));
+    // "first line" / "" / "second line" / "after an" / "empty one"
+    let text = "first line\n\nsecond line after an empty one";
+    assert_eq!(measure_wrapped(text, 13), (11, 5));
+    // "🦀 crabs" / "🦀🦀" / "everywhe" / "re and" / "anextrem" / "elylongw" / "ordwitho" / "utbreaks"
+    let text = "🦀 crabs 🦀🦀 everywhere and anextremelylongwordwithoutbreaks";
+    assert_eq!(measure_wrapped(text, 8), (8, 8));
+    // char wider than the line takes a row on its own
+    assert_eq!(measure_wrapped("a🦀b", 1), (2, 3));
+    assert_eq!(measure_wrapped("", 10), (0, 1));
+    assert_eq!(measure_wrapped("text", 0), (0, 0));
+    assert_eq!(measure_lines(&["ab cd", "", "e"], 2), (2, 4));
+}
+
+#[test]
+fn test_grapheme_safe() {
+    let accent = "e\u{301}te\u{301}";
+    assert_eq!(accent.char_len(), 5);
+    assert_eq!(accent.grapheme_len(), 3);
+    assert_eq!(accent.split_at_grapheme(1), ("e\u{301}", "te\u{301}"));
+    assert_eq!(accent.truncate_width_graphemes(2), (0, "e\u{301}t"));
+    assert_eq!(accent.width_at_grapheme(3), 3);
+
+    let flags = "🇧🇬🇩🇪";
+    assert_eq!(flags.char_len(), 4);
+    assert_eq!(flags.grapheme_len(), 2);
+    assert_eq!(flags.split_at_grapheme(1), ("🇧🇬", "🇩🇪"));
+    assert_eq!(flags.truncate_width_graphemes(3), (1, "🇧🇬"));
+    assert_eq!(flags.split_at_grapheme(5), (flags, ""));
+
+    let skin_tone = String::from("👍🏽ok");
+    assert_eq!(skin_tone.char_len(), 4);
+    assert_eq!(skin_tone.grapheme_len(), 3);
+    assert_eq!(skin_tone.split_at_grapheme(1), ("👍🏽", "ok"));
+    assert_eq!(skin_tone.truncate_width_graphemes(1), (1, ""));
+    assert_eq!(skin_tone.truncate_width_graphemes(3), (0, "👍🏽o"));
+    assert_eq!(skin_tone.width_at_grapheme(1), 2);
+
+    let family = "👨‍👩‍👧!";
+    assert_eq!(family.grapheme_len(), 2);
+    let (head, tail) = family.split_at_grapheme(1);
+    assert_eq!(head, "👨‍👩‍👧");
+    assert_eq!(tail, "!");
+    // never splits the cluster
+    let (_, truncated) = family.truncate_width_graphemes(1);
+    assert_eq!(truncated, "");
+}
@@ -0,0 +1,70 @@
+use super::Text;
+use crate::backend::CursorControl;
+use std::collections::HashMap;
+
+/// Memoization of Text (width / char len) for strings rendered on every frame (labels / menus)
+/// Every unique string is stored as owned copy - memory grows with the number of unique strings,
+/// once the capacity is reached the cache is cleared before inserting the new string.
+#[derive(Debug, Clone)]
+pub struct TextCache<B: CursorControl> {
+    inner: HashMap<String, Text<B>>,
+    capacity: usize,
+}
+
+impl<B: CursorControl> Default for TextCache<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: CursorControl> TextCache<B> {
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// capacity is the max number of cached strings (min 1)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: HashMap::new(),
+            capacity: std::cmp::max(1, capacity),
+        }
+    }
+
+    pub fn get_or_insert(&mut self, text: &str) -> &Text<B> {
+        if !self.inner.contains_key(text) {
+            if self.inner.len() >= self.capacity {
+                self.inner.clear();
+            }
+            self.inner
+                .insert(text.to_owned(), Text::raw(text.to_owned()));
+        }
+        &self.inner[text]
+    }
+
+    #[inline]
+    pub fn get(&self, text: &str) -> Option<&Text<B>> {
+        self.inner.get(text)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    backend::{FrameGuard, ScreenControl},
+    layout::Rect,
+};
+
+type RenderCallback<'a, B> = Box<dyn FnOnce(Rect, &mut B) + 'a>;
+
+struct Layer<'a, B: ScreenControl> {
+    z_index: usize,
+    rect: Rect,
+    opaque: bool,
+    callback: RenderCallback<'a, B>,
+}
+
+/// Collects render callbacks for the frame and executes them ordered by z index,
+/// bigger z index is rendered on top - equal z index keeps order of registration.
+/// Layers fully covered by opaque layer on top are skipped.
+pub struct Layers<'a, B: ScreenControl> {
+    layers: Vec<Layer<'a, B>>,
+}
+
+impl<B: ScreenControl> Default for Layers<'_, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, B: ScreenControl> Layers<'a, B> {
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// registers opaque layer - it fully redraws its rect
+    pub fn push(&mut self, z_index: usize, rect: Rect, callback: impl FnOnce(Rect, &mut B) + 'a) {
+        self.layers.push(Layer {
+            z_index,
+            rect,
+            opaque: true,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// registers layer that does not fully redraw its rect (will not hide layers below)
+    pub fn push_transparent(
+        &mut self,
+        z_index: usize,
+        rect: Rect,
+        callback: impl FnOnce(Rect, &mut B) + 'a,
+    ) {
+        self.layers.push(Layer {
+            z_index,
+            rect,
+            opaque: false,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// executes layers in z order within freeze / unfreeze
+    pub fn render(self, backend: &mut B) {
+        backend.freeze();
+        self.execute(backend);
+        backend.unfreeze();
+    }
+
+    /// executes layers in z order within the frame (already frozen - unfreezes and flushes on drop)
+    /// layers rendered into frame are not bracketed again, so they do not end the frame early
+    pub fn render_frame(self, frame: &mut FrameGuard<'_, B>) {
+        self.execute(frame);
+    }
+
+    fn execute(mut self, backend: &mut B) {
+        self.layers.sort_by_key(|layer| layer.z_index);
+        let mut layers = self.layers.into_iter();
+        while let Some(layer) = layers.next() {
+            let is_covered = layers
+                .as_slice()
+                .iter()
+                .any(|upper| upper.opaque && upper.rect.contains_rect(&layer.rect));
+            if is_covered {
+                continue;
+            }
+            (layer.callback)(layer.rect, backend);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use super::{StyledLine, Text};
+use crate::{backend::CursorControl, layout::LineBuilder, utils::UTFSafe};
+
+/// Row of a list that renders itself directly into the line (no per frame String formatting)
+pub trait ListItem<B: CursorControl> {
+    fn render_into(&self, builder: LineBuilder<B>);
+    /// expected width of the rendered row
+    fn width_hint(&self) -> usize;
+}
+
+impl<B: CursorControl, T: ListItem<B> + ?Sized> ListItem<B> for &T {
+    #[inline]
+    fn render_into(&self, builder: LineBuilder<B>) {
+        (*self).render_into(builder);
+    }
+
+    #[inline]
+    fn width_hint(&self) -> usize {
+        (*self).width_hint()
+    }
+}
+
+impl<B: CursorControl> ListItem<B> for str {
+    #[inline]
+    fn render_into(&self, mut builder: LineBuilder<B>) {
+        builder.push(self);
+    }
+
+    #[inline]
+    fn width_hint(&self) -> usize {
+        UTFSafe::width(self)
+    }
+}
+
+impl<B: CursorControl> ListItem<B> for String {
+    #[inline]
+    fn render_into(&self, builder: LineBuilder<B>) {
+        self.as_str().render_into(builder);
+    }
+
+    #[inline]
+    fn width_hint(&self) -> usize {
+        UTFSafe::width(self.as_str())
+    }
+}
+
+impl<B: CursorControl> ListItem<B> for Text<B> {
+    fn render_into(&self, mut builder: LineBuilder<B>) {
+        match self.style.clone() {
+            Some(style) => builder.push_styled(&self.text, style),
+            None => builder.push(&self.text),
+        };
+    }
+
+    #[inline]
+    fn width_hint(&self) -> usize {
+        self.width
+    }
+}
+
+impl<B: CursorControl> ListItem<B> for StyledLine<B> {
+    fn render_into(&self, mut builder: LineBuilder<B>) {
+        for text in self.inner.iter() {
+            let not_full = match text.style.clone() {
+                Some(style) => builder.push_styled(&text.text, style),
+                None => builder.push(&text.text),
+            };
+            if !not_full {
+                return;
+            }
+        }
+    }
+
+    #[inline]
+    fn width_hint(&self) -> usize {
+        self.inner.iter().fold(0, |sum, text| sum + text.width)
+    }
+}
@@ -0,0 +1,68 @@
+use super::{Text, Writable};
+use crate::{
+    backend::{CursorControl, StyleControl, TextPrint},
+    layout::{IterLines, Rect},
+    utils::{UTFSafe, WriteChunks},
+};
+
+/// Bordered modal with title displaying message centered within the screen rect
+/// The message is wrapped fresh on every render to the current inner width (reflows on resize)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message<B: CursorControl + StyleControl> {
+    title: String,
+    text: Text<B>,
+}
+
+impl<B: CursorControl + StyleControl> Message<B> {
+    pub const MAX_WIDTH: usize = 60;
+
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            text: Text::raw(message.into()),
+        }
+    }
+
+    pub fn with_style(mut self, style: <B as TextPrint>::Style) -> Self {
+        self.text.set_style(Some(style));
+        self
+    }
+
+    #[inline]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    #[inline]
+    pub fn message(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// inner rect (without borders) of the modal within the screen
+    pub fn rect(&self, screen: Rect) -> Rect {
+        let width = std::cmp::max(self.text.width(), self.title.width() + 2);
+        let inner_width = width
+            .min(Self::MAX_WIDTH)
+            .min(screen.width.saturating_sub(2));
+        if inner_width == 0 || screen.height < 3 {
+            return Rect::new(screen.row, screen.col, 0, 0);
+        }
+        let rows = std::cmp::max(1, WriteChunks::new(self.text.as_str(), inner_width).count());
+        let height = u16::try_from(rows + 2).unwrap_or(u16::MAX);
+        screen.center(height, inner_width + 2).with_borders()
+    }
+
+    /// renders the modal returning the inner rect used
+    pub fn render(&self, screen: Rect, backend: &mut B) -> Rect {
+        let rect = self.rect(screen);
+        if rect.width == 0 || rect.height == 0 {
+            return rect;
+        }
+        rect.draw_borders(None, None, backend);
+        rect.border_title(&self.title, backend);
+        let mut lines = rect.into_iter();
+        self.text.wrap(&mut lines, backend);
+        lines.clear_to_end(backend);
+        rect
+    }
+}
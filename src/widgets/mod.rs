@@ -1,16 +1,37 @@
+mod cache;
+mod layers;
+mod list_item;
+mod message;
+mod prompt;
+mod scrollbar;
+mod spinner;
 mod state;
+mod transform;
 
 use crate::{
-    backend::Backend,
+    backend::{CursorControl, StyleKey, TextPrint},
     layout::{IterLines, Line, RectIter},
-    StrChunks, UTFSafe, WriteChunks,
+    StrChunks, UTFSafe, WordChunks, WriteChunks,
 };
+pub use cache::TextCache;
+pub use layers::Layers;
+pub use list_item::ListItem;
+pub use message::Message;
+pub use prompt::{Prompt, PromptEvent};
+pub use scrollbar::{ScrollAction, Scrollbar};
+pub use spinner::{Spinner, BRAILLE_FRAMES, LINE_FRAMES};
 pub use state::State;
-use std::fmt::Display;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+pub use transform::TextTransform;
 use unicode_width::UnicodeWidthChar;
 
 /// Trait that allows faster rendering without checks and can reduce complexity
-pub trait Writable<B: Backend>: Display {
+pub trait Writable<B: CursorControl>: Display {
     /// check if the line can be rendered as ascii - no control chars should be included
     fn is_simple(&self) -> bool;
     /// width when rendered
@@ -23,6 +44,15 @@ pub trait Writable<B: Backend>: Display {
     fn print_at(&self, line: Line, backend: &mut B);
     /// wraps within rect
     fn wrap(&self, lines: &mut impl IterLines, backend: &mut B);
+    /// continues wrapping from the current cursor position with remaining width on the line,
+    /// start_remaining 0 starts on the next line - no padding is added after the text
+    /// returns the remaining width on the last used line (None if lines ran out)
+    fn wrap_from(
+        &self,
+        lines: &mut impl IterLines,
+        start_remaining: usize,
+        backend: &mut B,
+    ) -> Option<usize>;
     /// # Safety
     /// print truncated
     unsafe fn print_truncated(&self, width: usize, backend: &mut B);
@@ -33,33 +63,107 @@ pub trait Writable<B: Backend>: Display {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// prints bounded by line, trailing space is padded with bg_style (keeps highlight to the line end)
+    fn print_at_with_bg(&self, line: Line, bg_style: <B as TextPrint>::Style, backend: &mut B) {
+        let Line { width, row, col } = line;
+        backend.go_to(row, col);
+        let text_width = self.width();
+        if text_width > width {
+            unsafe { self.print_truncated(width, backend) };
+            return;
+        }
+        self.print(backend);
+        if text_width != width {
+            backend.pad_styled(width - text_width, bg_style);
+        }
+    }
 }
 
 /// Represents word with additional meta data such as width, style and number of chars, useful when rendering multiple times the same string
-#[derive(Clone, PartialEq, Debug, Default)]
-pub struct Text<B: Backend> {
+/// char_len and width are cached from text (new_unchecked should keep the invariant)
+/// equality and hashing use the text, style and tab stops
+#[derive(Clone, Debug, Default)]
+pub struct Text<B: CursorControl> {
     text: String,
     char_len: usize,
     width: usize,
-    style: Option<<B as Backend>::Style>,
+    style: Option<<B as TextPrint>::Style>,
+    tab_width: Option<usize>,
+    tab_offset: usize,
 }
 
-impl<B: Backend> Text<B> {
-    pub fn new(text: String, style: Option<<B as Backend>::Style>) -> Self {
+impl<B: CursorControl> PartialEq for Text<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+            && self.style == other.style
+            && self.tab_width == other.tab_width
+            && self.tab_offset == other.tab_offset
+    }
+}
+
+impl<B: CursorControl> Eq for Text<B> where <B as TextPrint>::Style: Eq {}
+
+impl<B: CursorControl> Hash for Text<B>
+where
+    <B as TextPrint>::Style: StyleKey,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        match self.style.as_ref() {
+            Some(style) => {
+                state.write_u8(1);
+                style.hash_key(state);
+            }
+            None => state.write_u8(0),
+        }
+        self.tab_width.hash(state);
+        self.tab_offset.hash(state);
+    }
+}
+
+impl<B: CursorControl> Text<B> {
+    pub fn new(text: String, style: Option<<B as TextPrint>::Style>) -> Self {
         Self {
             char_len: text.char_len(),
             width: text.width(),
             style,
             text,
+            tab_width: None,
+            tab_offset: 0,
         }
     }
 
+    /// expands '\t' to the next multiple of tab columns at render time
+    pub fn with_tab_width(
+        text: String,
+        style: Option<<B as TextPrint>::Style>,
+        tab: usize,
+    ) -> Self {
+        let mut text = Self::new(text, style);
+        text.set_tab_stops(tab, 0);
+        text
+    }
+
+    /// offset is the column the text starts at, relative to the tab grid
+    fn set_tab_stops(&mut self, tab: usize, offset: usize) {
+        if !self.text.contains('\t') {
+            return;
+        }
+        let tab = tab.max(1);
+        self.width = tabbed_width(&self.text, tab, offset);
+        self.tab_width = Some(tab);
+        self.tab_offset = offset;
+    }
+
     pub fn raw(text: String) -> Self {
         Self {
             char_len: text.char_len(),
             width: text.width(),
             style: None,
             text,
+            tab_width: None,
+            tab_offset: 0,
         }
     }
 
@@ -67,13 +171,15 @@ impl<B: Backend> Text<B> {
         text: String,
         char_len: usize,
         width: usize,
-        style: Option<<B as Backend>::Style>,
+        style: Option<<B as TextPrint>::Style>,
     ) -> Self {
         Self {
             text,
             width,
             char_len,
             style,
+            tab_width: None,
+            tab_offset: 0,
         }
     }
 
@@ -83,17 +189,44 @@ impl<B: Backend> Text<B> {
     }
 
     #[inline]
-    pub fn style(&self) -> Option<<B as Backend>::Style> {
+    pub fn style(&self) -> Option<<B as TextPrint>::Style> {
         self.style.clone()
     }
 
+    /// compares only the text ignoring styles
+    #[inline]
+    pub fn content_eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+
     #[inline]
-    pub fn set_style(&mut self, style: Option<<B as Backend>::Style>) {
+    pub fn set_style(&mut self, style: Option<<B as TextPrint>::Style>) {
         self.style = style;
     }
 
+    /// renders text with transform applied at print time - truncated based on transformed width
+    pub fn render_transformed(&self, line: Line, transform: TextTransform, backend: &mut B) {
+        let Line { row, col, width } = line;
+        if width == 0 {
+            return;
+        }
+        let transformed = transform.apply(&self.text);
+        let (pad_width, text) = transformed.truncate_width(width);
+        backend.go_to(row, col);
+        match self.style.clone() {
+            Some(style) => backend.print_styled(text, style),
+            None => backend.print(text),
+        }
+        if pad_width != 0 {
+            backend.pad(pad_width);
+        }
+    }
+
     #[inline]
     pub fn simple_wrap(&self, lines: &mut RectIter, backend: &mut B) {
+        if lines.width() == 0 {
+            return;
+        }
         let max_width = match lines.move_cursor(backend) {
             Some(width) => width,
             None => return,
@@ -143,8 +276,46 @@ impl<B: Backend> Text<B> {
         }
     }
 
+    /// wraps within rect breaking lines on whitespace (the breaking whitespace is not rendered),
+    /// only words wider than the line are split - each used line is padded to the end
+    pub fn wrap_words(&self, lines: &mut impl IterLines, backend: &mut B) {
+        let max_width = lines.width();
+        if max_width == 0 {
+            return;
+        }
+        let text = self.display_text();
+        let mut chunks = WordChunks::new(&text, max_width).peekable();
+        if chunks.peek().is_none() {
+            if lines.move_cursor(backend).is_some() {
+                backend.pad(max_width);
+            }
+            return;
+        }
+        for StrChunks { text, width } in chunks {
+            if lines.move_cursor(backend).is_none() {
+                return;
+            }
+            // char wider than the line can not be rendered
+            if width > max_width {
+                backend.pad(max_width);
+                continue;
+            }
+            self.print_str(text, backend);
+            if width < max_width {
+                backend.pad(max_width - width);
+            }
+        }
+    }
+
     #[inline]
     fn wrap_with_remainder(&self, lines: &mut impl IterLines, backend: &mut B) -> Option<usize> {
+        if lines.width() == 0 {
+            return None;
+        }
+        if let Some(tab) = self.tab_width {
+            let width = lines.move_cursor(backend)?;
+            return self.wrap_tabbed(tab, lines, width, backend);
+        }
         if self.is_simple() {
             self.wrap_with_remainder_simple(lines, backend)
         } else {
@@ -152,12 +323,75 @@ impl<B: Backend> Text<B> {
         }
     }
 
+    /// tab stops are counted from the row start, a tab crossing the row edge pads it and ends there
+    fn wrap_tabbed(
+        &self,
+        tab: usize,
+        lines: &mut impl IterLines,
+        mut width: usize,
+        backend: &mut B,
+    ) -> Option<usize> {
+        let mut col = lines.width().saturating_sub(width);
+        let mut buffer = String::new();
+        for ch in self.text.chars() {
+            let ch_width = match ch {
+                '\t' => tab - col % tab,
+                _ => match UnicodeWidthChar::width(ch) {
+                    Some(ch_width) => ch_width,
+                    None => continue,
+                },
+            };
+            if ch_width > width {
+                self.print_str(&buffer, backend);
+                buffer.clear();
+                if width != 0 {
+                    backend.pad(width);
+                }
+                width = lines.move_cursor(backend)?;
+                col = 0;
+                if ch == '\t' {
+                    continue;
+                }
+            }
+            match ch {
+                '\t' => buffer.extend(std::iter::repeat(' ').take(ch_width)),
+                _ => buffer.push(ch),
+            }
+            width = width.saturating_sub(ch_width);
+            col += ch_width;
+        }
+        self.print_str(&buffer, backend);
+        Some(width)
+    }
+
+    #[inline]
+    fn print_str(&self, text: &str, backend: &mut B) {
+        if text.is_empty() {
+            return;
+        }
+        match self.style.clone() {
+            Some(style) => backend.print_styled(text, style),
+            None => backend.print(text),
+        }
+    }
+
+    /// text as displayed - with tabs expanded if tab width is set
+    fn display_text(&self) -> Cow<'_, str> {
+        match self.tab_width {
+            Some(tab) => Cow::Owned(expand_tabs(&self.text, tab, self.tab_offset)),
+            None => Cow::Borrowed(&self.text),
+        }
+    }
+
     #[inline]
     pub fn wrap_with_remainder_simple(
         &self,
         lines: &mut impl IterLines,
         backend: &mut B,
     ) -> Option<usize> {
+        if lines.width() == 0 {
+            return None;
+        }
         let max_width = lines.move_cursor(backend)?;
         if max_width > self.width {
             match self.style.clone() {
@@ -242,10 +476,10 @@ impl<B: Backend> Text<B> {
     }
 }
 
-impl<B: Backend> Writable<B> for Text<B> {
+impl<B: CursorControl> Writable<B> for Text<B> {
     #[inline(always)]
     fn is_simple(&self) -> bool {
-        self.char_len == self.text.len()
+        self.char_len == self.text.len() && self.tab_width.is_none()
     }
 
     #[inline(always)]
@@ -264,9 +498,10 @@ impl<B: Backend> Writable<B> for Text<B> {
     }
 
     fn print(&self, backend: &mut B) {
+        let text = self.display_text();
         match self.style.clone() {
-            Some(style) => backend.print_styled(&self.text, style),
-            None => backend.print(&self.text),
+            Some(style) => backend.print_styled(text, style),
+            None => backend.print(text),
         }
     }
 
@@ -277,7 +512,8 @@ impl<B: Backend> Writable<B> for Text<B> {
                 None => backend.print(self.text.get_unchecked(..width)),
             }
         } else {
-            let (remaining_w, text) = self.text.truncate_width(width);
+            let display_text = self.display_text();
+            let (remaining_w, text) = display_text.truncate_width(width);
             match self.style.clone() {
                 Some(style) => backend.print_styled(text, style),
                 None => backend.print(text),
@@ -297,7 +533,8 @@ impl<B: Backend> Writable<B> for Text<B> {
                 None => backend.print(self.text.get_unchecked(self.len() - width..)),
             }
         } else {
-            let (remaining_w, text) = self.text.truncate_width_start(width);
+            let display_text = self.display_text();
+            let (remaining_w, text) = display_text.truncate_width_start(width);
             if remaining_w != 0 {
                 backend.pad(remaining_w);
             }
@@ -328,15 +565,428 @@ impl<B: Backend> Writable<B> for Text<B> {
             _ => (),
         }
     }
+
+    fn wrap_from(
+        &self,
+        lines: &mut impl IterLines,
+        mut width: usize,
+        backend: &mut B,
+    ) -> Option<usize> {
+        if lines.width() == 0 {
+            return None;
+        }
+        if let Some(tab) = self.tab_width {
+            if width == 0 {
+                width = lines.move_cursor(backend)?;
+            }
+            return self.wrap_tabbed(tab, lines, width, backend);
+        }
+        if self.width <= width {
+            self.print(backend);
+            return Some(width - self.width);
+        }
+        if width == 0 {
+            return self.wrap_with_remainder(lines, backend);
+        }
+        if self.is_simple() {
+            let mut remaining = self.width;
+            let mut start = 0;
+            match self.style.clone() {
+                Some(style) => loop {
+                    if remaining > width {
+                        backend.print_styled(&self.text[start..start + width], style.clone());
+                        remaining -= width;
+                        start += width;
+                    } else {
+                        backend.print_styled(&self.text[start..], style.clone());
+                        return Some(width - remaining);
+                    }
+                    width = lines.move_cursor(backend)?;
+                },
+                None => loop {
+                    if remaining > width {
+                        backend.print(&self.text[start..start + width]);
+                        remaining -= width;
+                        start += width;
+                    } else {
+                        backend.print(&self.text[start..]);
+                        return Some(width - remaining);
+                    }
+                    width = lines.move_cursor(backend)?;
+                },
+            }
+        }
+        match self.style.clone() {
+            Some(style) => {
+                for ch in self.text.chars() {
+                    let ch_width = match UnicodeWidthChar::width(ch) {
+                        Some(ch_width) => ch_width,
+                        None => continue,
+                    };
+                    if ch_width > width {
+                        if width != 0 {
+                            backend.pad(width);
+                        }
+                        width = lines.move_cursor(backend)?;
+                    }
+                    backend.print_styled(ch, style.clone());
+                    width = width.saturating_sub(ch_width);
+                }
+            }
+            None => {
+                for ch in self.text.chars() {
+                    let ch_width = match UnicodeWidthChar::width(ch) {
+                        Some(ch_width) => ch_width,
+                        None => continue,
+                    };
+                    if ch_width > width {
+                        if width != 0 {
+                            backend.pad(width);
+                        }
+                        width = lines.move_cursor(backend)?;
+                    }
+                    backend.print(ch);
+                    width = width.saturating_sub(ch_width);
+                }
+            }
+        }
+        Some(width)
+    }
 }
 
 /// Collection of styled texts, useful when rendering multiple times the same string, as it holds meta data for width / charcer len of words
-#[derive(Clone, PartialEq, Default, Debug)]
-pub struct StyledLine<B: Backend> {
+#[derive(Clone, Default, Debug)]
+pub struct StyledLine<B: CursorControl> {
     inner: Vec<Text<B>>,
+    tab_width: Option<usize>,
+}
+
+impl<B: CursorControl> PartialEq for StyledLine<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.tab_width == other.tab_width
+    }
+}
+
+impl<B: CursorControl> Eq for StyledLine<B> where <B as TextPrint>::Style: Eq {}
+
+impl<B: CursorControl> Hash for StyledLine<B>
+where
+    <B as TextPrint>::Style: StyleKey,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+        self.tab_width.hash(state);
+    }
+}
+
+impl<B: CursorControl> StyledLine<B> {
+    /// expands '\t' to the next multiple of tab columns (counted from the line start) at render time
+    pub fn with_tab_width(mut inner: Vec<Text<B>>, tab: usize) -> Self {
+        let mut offset = 0;
+        for text in inner.iter_mut() {
+            text.set_tab_stops(tab, offset);
+            offset += text.width;
+        }
+        Self {
+            inner,
+            tab_width: Some(tab),
+        }
+    }
+
+    /// if the line has tab width, tabs in text are expanded from the current end of the line
+    pub fn push(&mut self, mut text: Text<B>) {
+        if let Some(tab) = self.tab_width {
+            text.set_tab_stops(tab, self.width());
+        }
+        self.inner.push(text);
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.push(Text::raw(text.to_owned()));
+    }
+
+    pub fn push_styled(&mut self, text: &str, style: <B as TextPrint>::Style) {
+        self.push(Text::new(text.to_owned(), Some(style)));
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    pub fn segments(&self) -> &[Text<B>] {
+        &self.inner
+    }
+
+    /// compares only the text ignoring styles and segmentation
+    pub fn content_eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .inner
+                .iter()
+                .flat_map(|text| text.text.chars())
+                .eq(other.inner.iter().flat_map(|text| text.text.chars()))
+    }
+
+    /// prints aligned to the right of the line (padded on the left),
+    /// if wider than the line the start is truncated
+    pub fn print_at_right(&self, line: Line, backend: &mut B) {
+        let Line { row, col, width } = line;
+        if width == 0 {
+            return;
+        }
+        backend.go_to(row, col);
+        let text_width = self.width();
+        if text_width > width {
+            unsafe { self.print_truncated_start(width, backend) };
+            return;
+        }
+        if text_width != width {
+            backend.pad(width - text_width);
+        }
+        self.print(backend);
+    }
+
+    /// prints centered within the line, if wider than the line the end is truncated
+    pub fn print_at_center(&self, line: Line, backend: &mut B) {
+        let Line { row, col, width } = line;
+        if width == 0 {
+            return;
+        }
+        backend.go_to(row, col);
+        let text_width = self.width();
+        if text_width > width {
+            unsafe { self.print_truncated(width, backend) };
+            return;
+        }
+        let right_pad = (width - text_width) / 2;
+        let left_pad = width - text_width - right_pad;
+        if left_pad != 0 {
+            backend.pad(left_pad);
+        }
+        self.print(backend);
+        if right_pad != 0 {
+            backend.pad(right_pad);
+        }
+    }
+
+    /// prints line scrolled horizontally - skips col_offset display cols from the left
+    /// (wide char cut by the offset is replaced by padding) and truncates at the right
+    pub fn print_at_offset(&self, line: Line, col_offset: usize, backend: &mut B) {
+        if col_offset == 0 {
+            return self.print_at(line, backend);
+        }
+        let Line {
+            row,
+            col,
+            mut width,
+        } = line;
+        backend.go_to(row, col);
+        let mut skip = col_offset;
+        let mut iter = self.inner.iter();
+        for text in iter.by_ref() {
+            if text.width <= skip {
+                skip -= text.width;
+                continue;
+            }
+            let display_text = text.display_text();
+            let mut tail_start = display_text.len();
+            let mut gap = 0;
+            for (idx, ch) in display_text.char_indices() {
+                if skip == 0 {
+                    tail_start = idx;
+                    break;
+                }
+                let ch_width = UnicodeWidthChar::width(ch).unwrap_or_default();
+                if ch_width > skip {
+                    gap = ch_width - skip;
+                    tail_start = idx + ch.len_utf8();
+                    break;
+                }
+                skip -= ch_width;
+            }
+            let gap = gap.min(width);
+            if gap != 0 {
+                backend.pad(gap);
+                width -= gap;
+            }
+            let visible = &display_text[tail_start..];
+            let (remaining_w, tail) = visible.truncate_width(width);
+            text.print_str(tail, backend);
+            if tail.len() != visible.len() {
+                if remaining_w != 0 {
+                    backend.pad(remaining_w);
+                }
+                return;
+            }
+            width = remaining_w;
+            break;
+        }
+        for text in iter {
+            if width < text.width {
+                unsafe { text.print_truncated(width, backend) };
+                return;
+            }
+            width -= text.width;
+            text.print(backend);
+        }
+        if width != 0 {
+            backend.pad(width);
+        }
+    }
+
+    /// renders segments with transform applied at print time (letter spacing is kept between segments)
+    pub fn render_transformed(&self, line: Line, transform: TextTransform, backend: &mut B) {
+        let Line {
+            row,
+            col,
+            mut width,
+        } = line;
+        if width == 0 {
+            return;
+        }
+        backend.go_to(row, col);
+        for (idx, text) in self.inner.iter().enumerate() {
+            let mut transformed = transform.apply(text.as_str());
+            if idx != 0 {
+                transformed.insert_str(0, &" ".repeat(transform.separator()));
+            }
+            let (remaining, truncated) = transformed.truncate_width(width);
+            let is_truncated = truncated.len() != transformed.len();
+            match text.style.clone() {
+                Some(style) => backend.print_styled(truncated, style),
+                None => backend.print(truncated),
+            }
+            width = remaining;
+            if is_truncated {
+                break;
+            }
+        }
+        if width != 0 {
+            backend.pad(width);
+        }
+    }
+
+    /// builds line from inline markup "{bold}error:{/} failed", tag styles are provided by the resolver
+    /// "{/}" closes the last opened tag, "{{" and "}}" are literal braces, malformed tags are kept as text
+    pub fn from_markup(
+        markup: &str,
+        resolver: impl Fn(&str) -> Option<<B as TextPrint>::Style>,
+    ) -> Self {
+        let mut inner = Vec::new();
+        let mut styles: Vec<<B as TextPrint>::Style> = Vec::new();
+        let mut buffer = String::new();
+        let mut rest = markup;
+        while let Some(idx) = rest.find(['{', '}']) {
+            buffer.push_str(&rest[..idx]);
+            rest = &rest[idx..];
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                buffer.push_str(&rest[..1]);
+                rest = &rest[2..];
+                continue;
+            }
+            if rest.starts_with('}') {
+                buffer.push('}');
+                rest = &rest[1..];
+                continue;
+            }
+            let Some(end) = rest.find('}') else {
+                break;
+            };
+            let tag = &rest[1..end];
+            if tag == "/" && !styles.is_empty() {
+                push_markup_text(&mut inner, &mut buffer, styles.pop());
+            } else if let Some(style) = resolver(tag) {
+                push_markup_text(&mut inner, &mut buffer, styles.last().cloned());
+                styles.push(style);
+            } else {
+                buffer.push_str(&rest[..=end]);
+            }
+            rest = &rest[end + 1..];
+        }
+        buffer.push_str(rest);
+        push_markup_text(&mut inner, &mut buffer, styles.pop());
+        Self {
+            inner,
+            tab_width: None,
+        }
+    }
+
+    /// builds line with char idx ranges (as produced by matchers) highlighted
+    /// ranges are clamped to the text, overlapping and adjacent ranges are merged
+    pub fn from_match(
+        text: &str,
+        match_ranges: &[Range<usize>],
+        base: Option<<B as TextPrint>::Style>,
+        highlight: <B as TextPrint>::Style,
+    ) -> Self {
+        let char_len = text.char_len();
+        let mut ranges: Vec<Range<usize>> = match_ranges
+            .iter()
+            .map(|range| range.start.min(char_len)..range.end.min(char_len))
+            .filter(|range| !range.is_empty())
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        let mut ranges = ranges.into_iter().peekable();
+        let mut inner = Vec::new();
+        let mut buffer = String::new();
+        let mut chars = text.chars();
+        let mut idx = 0;
+        while let Some(mut range) = ranges.next() {
+            while let Some(next) = ranges.next_if(|next| next.start <= range.end) {
+                range.end = range.end.max(next.end);
+            }
+            buffer.extend(chars.by_ref().take(range.start - idx));
+            push_markup_text(&mut inner, &mut buffer, base.clone());
+            buffer.extend(chars.by_ref().take(range.end - range.start));
+            push_markup_text(&mut inner, &mut buffer, Some(highlight.clone()));
+            idx = range.end;
+        }
+        buffer.extend(chars);
+        push_markup_text(&mut inner, &mut buffer, base);
+        Self {
+            inner,
+            tab_width: None,
+        }
+    }
+}
+
+fn push_markup_text<B: CursorControl>(
+    inner: &mut Vec<Text<B>>,
+    buffer: &mut String,
+    style: Option<<B as TextPrint>::Style>,
+) {
+    if !buffer.is_empty() {
+        inner.push(Text::new(std::mem::take(buffer), style));
+    }
 }
 
-impl<B: Backend> Writable<B> for StyledLine<B> {
+fn tabbed_width(text: &str, tab: usize, offset: usize) -> usize {
+    text.chars().fold(0, |col, ch| match ch {
+        '\t' => col + tab - (offset + col) % tab,
+        _ => col + UnicodeWidthChar::width(ch).unwrap_or_default(),
+    })
+}
+
+fn expand_tabs(text: &str, tab: usize, offset: usize) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut col = 0;
+    for ch in text.chars() {
+        match ch {
+            '\t' => {
+                let tab_width = tab - (offset + col) % tab;
+                expanded.extend(std::iter::repeat(' ').take(tab_width));
+                col += tab_width;
+            }
+            _ => {
+                expanded.push(ch);
+                col += UnicodeWidthChar::width(ch).unwrap_or_default();
+            }
+        }
+    }
+    expanded
+}
+
+impl<B: CursorControl> Writable<B> for StyledLine<B> {
     fn is_simple(&self) -> bool {
         self.inner.iter().all(|text| text.is_simple())
     }
@@ -409,142 +1059,51 @@ impl<B: Backend> Writable<B> for StyledLine<B> {
     }
 
     fn wrap(&self, lines: &mut impl IterLines, backend: &mut B) {
-        let mut width = match lines.move_cursor(backend) {
-            Some(width) => width,
-            None => return,
+        if lines.width() == 0 {
+            return;
+        }
+        let Some(width) = lines.move_cursor(backend) else {
+            return;
         };
-        for word in self.inner.iter() {
-            if word.width > width {
-                if width == 0 {
-                    width = match word.wrap_with_remainder(lines, backend) {
-                        Some(new_width) => new_width,
-                        None => return,
-                    }
-                } else if word.is_simple() {
-                    let mut remaining = word.width;
-                    let mut start = 0;
-                    match word.style.clone() {
-                        Some(style) => loop {
-                            if remaining > width {
-                                backend
-                                    .print_styled(&word.text[start..start + width], style.clone());
-                                remaining -= width;
-                                start += width;
-                            } else {
-                                backend.print_styled(&word.text[start..], style.clone());
-                                width -= remaining;
-                                break;
-                            }
-                            match lines.move_cursor(backend) {
-                                Some(max_width) => width = max_width,
-                                None => return,
-                            };
-                        },
-                        None => loop {
-                            if remaining > width {
-                                backend.print(&word.text[start..start + width]);
-                                remaining -= width;
-                                start += width;
-                            } else {
-                                backend.print(&word.text[start..]);
-                                width -= remaining;
-                                break;
-                            }
-                            match lines.move_cursor(backend) {
-                                Some(max_width) => width = max_width,
-                                None => return,
-                            };
-                        },
-                    };
-                } else {
-                    match word.style.clone() {
-                        Some(style) => {
-                            for ch in word.text.chars() {
-                                let ch_width = match UnicodeWidthChar::width(ch) {
-                                    Some(ch_width) => ch_width,
-                                    None => continue,
-                                };
-                                if ch_width > width {
-                                    if width != 0 {
-                                        backend.pad(width);
-                                    }
-                                    width = match lines.move_cursor(backend) {
-                                        Some(new_width) => {
-                                            backend.print_styled(ch, style.clone());
-                                            new_width - ch_width
-                                        }
-                                        None => {
-                                            if width != 0 {
-                                                backend.pad(width);
-                                            };
-                                            return;
-                                        }
-                                    }
-                                } else {
-                                    backend.print_styled(ch, style.clone());
-                                    width -= ch_width;
-                                }
-                            }
-                        }
-                        None => {
-                            for ch in word.text.chars() {
-                                let ch_width = match UnicodeWidthChar::width(ch) {
-                                    Some(ch_width) => ch_width,
-                                    None => continue,
-                                };
-                                if ch_width > width {
-                                    if width != 0 {
-                                        backend.pad(width);
-                                    }
-                                    width = match lines.move_cursor(backend) {
-                                        Some(new_width) => {
-                                            backend.print(ch);
-                                            new_width - ch_width
-                                        }
-                                        None => {
-                                            if width != 0 {
-                                                backend.pad(width);
-                                            };
-                                            return;
-                                        }
-                                    }
-                                } else {
-                                    backend.print(ch);
-                                    width -= ch_width;
-                                }
-                            }
-                        }
-                    }
-                }
-            } else {
-                width -= word.width;
-                word.print(backend);
-            }
+        match self.wrap_from(lines, width, backend) {
+            Some(pad_width) if pad_width != 0 => backend.pad(pad_width),
+            _ => (),
         }
-        if width != 0 {
-            backend.pad(width);
+    }
+
+    fn wrap_from(
+        &self,
+        lines: &mut impl IterLines,
+        mut width: usize,
+        backend: &mut B,
+    ) -> Option<usize> {
+        for text in self.inner.iter() {
+            width = text.wrap_from(lines, width, backend)?;
         }
+        Some(width)
     }
 }
 
-impl<B: Backend> Display for Text<B> {
+impl<B: CursorControl> Display for Text<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.text)
     }
 }
 
-impl<B: Backend> From<String> for Text<B> {
+impl<B: CursorControl> From<String> for Text<B> {
     fn from(text: String) -> Self {
         Self {
             char_len: text.char_len(),
             width: text.width(),
             text,
             style: None,
+            tab_width: None,
+            tab_offset: 0,
         }
     }
 }
 
-impl<B: Backend> From<char> for Text<B> {
+impl<B: CursorControl> From<char> for Text<B> {
     #[inline]
     fn from(value: char) -> Self {
         Self {
@@ -552,23 +1111,27 @@ impl<B: Backend> From<char> for Text<B> {
             width: UnicodeWidthChar::width(value).unwrap_or_default(),
             text: value.to_string(),
             style: None,
+            tab_width: None,
+            tab_offset: 0,
         }
     }
 }
 
-impl<B: Backend> From<(String, <B as Backend>::Style)> for Text<B> {
+impl<B: CursorControl> From<(String, <B as TextPrint>::Style)> for Text<B> {
     #[inline]
-    fn from((text, style): (String, <B as Backend>::Style)) -> Self {
+    fn from((text, style): (String, <B as TextPrint>::Style)) -> Self {
         Self {
             char_len: text.char_len(),
             width: text.width(),
             text,
             style: Some(style),
+            tab_width: None,
+            tab_offset: 0,
         }
     }
 }
 
-impl<B: Backend> Display for StyledLine<B> {
+impl<B: CursorControl> Display for StyledLine<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for text in self.inner.iter() {
             text.fmt(f)?;
@@ -577,24 +1140,29 @@ impl<B: Backend> Display for StyledLine<B> {
     }
 }
 
-impl<B: Backend> From<Vec<Text<B>>> for StyledLine<B> {
+impl<B: CursorControl> From<Vec<Text<B>>> for StyledLine<B> {
     fn from(inner: Vec<Text<B>>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            tab_width: None,
+        }
     }
 }
 
-impl<B: Backend> From<String> for StyledLine<B> {
+impl<B: CursorControl> From<String> for StyledLine<B> {
     fn from(text: String) -> Self {
         Self {
             inner: vec![text.into()],
+            tab_width: None,
         }
     }
 }
 
-impl<B: Backend> From<(String, <B as Backend>::Style)> for StyledLine<B> {
-    fn from(text: (String, <B as Backend>::Style)) -> Self {
+impl<B: CursorControl> From<(String, <B as TextPrint>::Style)> for StyledLine<B> {
+    fn from(text: (String, <B as TextPrint>::Style)) -> Self {
         Self {
             inner: vec![text.into()],
+            tab_width: None,
         }
     }
 }
@@ -0,0 +1,120 @@
+use crate::{
+    backend::{CursorControl, StyleControl, TextPrint},
+    layout::Rect,
+    text_field::TextField,
+    utils::UTFSafe,
+};
+
+#[cfg(feature = "crossterm_backend")]
+use crossterm::event::{KeyCode, KeyEvent};
+
+#[derive(Debug, PartialEq)]
+pub enum PromptEvent {
+    Skipped,
+    Edited,
+    Submitted(String),
+    Cancelled,
+}
+
+/// Bordered modal with title, asking the user for single line input
+/// Centered within the screen rect - on resize call resize to recenter (text is preserved)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompt<B: CursorControl + StyleControl> {
+    title: String,
+    field: TextField,
+    rect: Rect,
+    cursor_style: <B as TextPrint>::Style,
+    select_style: <B as TextPrint>::Style,
+}
+
+impl<B: CursorControl + StyleControl> Prompt<B> {
+    pub const MIN_WIDTH: usize = 30;
+    pub const HEIGHT: u16 = 3;
+
+    pub fn new(title: impl Into<String>, initial: Option<String>, screen: Rect) -> Self {
+        let title = title.into();
+        let rect = Self::calc_rect(&title, screen);
+        Self {
+            title,
+            field: TextField::new(initial.unwrap_or_default()),
+            rect,
+            cursor_style: B::reversed_style(),
+            select_style: B::underline_style(None),
+        }
+    }
+
+    pub fn with_styles(
+        mut self,
+        cursor_style: <B as TextPrint>::Style,
+        select_style: <B as TextPrint>::Style,
+    ) -> Self {
+        self.cursor_style = cursor_style;
+        self.select_style = select_style;
+        self
+    }
+
+    #[inline]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    #[inline]
+    pub fn field(&self) -> &TextField {
+        &self.field
+    }
+
+    #[inline]
+    pub fn field_mut(&mut self) -> &mut TextField {
+        &mut self.field
+    }
+
+    /// inner rect of the prompt (without borders)
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// recenters the prompt within the new screen
+    pub fn resize(&mut self, screen: Rect) {
+        self.rect = Self::calc_rect(&self.title, screen);
+    }
+
+    pub fn render(&self, backend: &mut B) {
+        let Some(line) = self.rect.get_line(0) else {
+            return;
+        };
+        self.rect.draw_borders(None, None, backend);
+        self.rect.border_title(&self.title, backend);
+        self.field.widget(
+            line,
+            self.cursor_style.clone(),
+            self.select_style.clone(),
+            backend,
+        );
+    }
+
+    fn calc_rect(title: &str, screen: Rect) -> Rect {
+        // title is rendered on the border, field prefix " >> " + cursor
+        let width = std::cmp::max(Self::MIN_WIDTH, title.width() + 2) + 2;
+        let rect = screen.center(Self::HEIGHT, width);
+        if rect.height < Self::HEIGHT || rect.width < 3 {
+            return Rect::new(rect.row, rect.col, 0, 0);
+        }
+        rect.with_borders()
+    }
+}
+
+#[cfg(feature = "crossterm_backend")]
+impl<B: CursorControl + StyleControl> Prompt<B> {
+    /// Enter submits the text (field is emptied and can be reused), Esc cancels
+    pub fn map(&mut self, key: KeyEvent) -> PromptEvent {
+        match key.code {
+            KeyCode::Enter => PromptEvent::Submitted(self.field.text_take()),
+            KeyCode::Esc => PromptEvent::Cancelled,
+            _ => match self.field.map(key) {
+                Some(status) if status.is_updated() => PromptEvent::Edited,
+                _ => PromptEvent::Skipped,
+            },
+        }
+    }
+}
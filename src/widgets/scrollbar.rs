@@ -1,74 +1,311 @@
+use super::State;
+use crate::{
+    backend::{CursorControl, StyleControl, TextPrint},
+    layout::{Line, Rect},
+};
 use std::ops::Range;
 
-#[derive(Debug, PartialEq)]
-pub struct ScrollBar {
-    x: u16,
-    y: u16,
-    screen: usize,
+/// Result of hit testing the scrollbar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollAction {
+    PageUp,
+    PageDown,
+    /// fraction of the scrollable range 0.0 (top) - 1.0 (bottom)
+    DragTo(f64),
+}
+
+/// Vertical scrollbar, holds the geometry and content metrics from the last render,
+/// so hit testing matches exactly what was drawn.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Scrollbar {
+    row: u16,
+    col: u16,
+    width: usize,
+    height: u16,
     at_line: usize,
     full_len: usize,
-    range: Range<usize>,
+    thumb: Range<u16>,
+    /// row within the thumb where it was grabbed (set by hit on the thumb)
+    grab_offset: u16,
 }
 
-impl ScrollBar {
-    pub fn new(x: u16, y: u16, screen: usize, at_line: usize, full_len: usize) -> Option<Self> {
-        if full_len <= screen {
-            return None;
+impl Scrollbar {
+    /// calculates geometry for the scrollbar within rect (usually single column)
+    /// rect height is considered the visible screen
+    pub fn new(rect: Rect, at_line: usize, full_len: usize) -> Self {
+        let mut scrollbar = Self::default();
+        scrollbar.update(rect, at_line, full_len);
+        scrollbar
+    }
+
+    /// updates geometry and metrics, if content fits the screen the scrollbar is hidden
+    pub fn update(&mut self, rect: Rect, at_line: usize, full_len: usize) {
+        let height = rect.height as usize;
+        self.row = rect.row;
+        self.col = rect.col;
+        self.width = rect.width;
+        self.height = rect.height;
+        self.full_len = full_len;
+        if full_len <= height || rect.width == 0 {
+            self.at_line = 0;
+            self.thumb = 0..0;
+            return;
         }
-        let scale = (screen * 100) / full_len;
-        let screen_part = (screen * scale) / 100;
-        let start = (at_line * scale) / 100;
-        let range = start..(start + screen_part);
-        Some(Self { x, y, screen, at_line, full_len, range })
+        let max_at_line = full_len - height;
+        self.at_line = std::cmp::min(at_line, max_at_line);
+        let thumb_len = std::cmp::max(1, (height * height + full_len / 2) / full_len);
+        let track_len = height - thumb_len;
+        let start = (self.at_line * track_len + max_at_line / 2) / max_at_line;
+        self.thumb = start as u16..(start + thumb_len) as u16;
     }
 
-    fn check(&self, idx: usize) -> bool {
-        self.range.start <= idx && idx < self.range.end
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        !self.thumb.is_empty()
     }
-}
 
-pub struct ScrollIter {
-    idx: usize,
-    screen: usize,
-    at_line: usize,
-    full_len: usize,
-    range: Range<usize>,
-}
+    /// returns thumb rows relative to the scrollbar
+    #[inline]
+    pub fn thumb(&self) -> Range<u16> {
+        self.thumb.clone()
+    }
 
-impl ScrollIter {
-    pub fn new(screen: usize, at_line: usize, full_len: usize) -> Option<Self> {
-        if full_len <= screen {
-            return None;
+    /// updates geometry and renders the scrollbar - thumb is padded with style
+    pub fn render<B: CursorControl>(
+        &mut self,
+        rect: Rect,
+        at_line: usize,
+        full_len: usize,
+        thumb_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        self.update(rect, at_line, full_len);
+        if !self.is_visible() {
+            return;
         }
-        let scale = (screen * 100) / full_len;
-        let screen_part = (screen * scale) / 100;
-        let start = (at_line * scale) / 100;
-        let range = start..(start + screen_part);
-        Some(Self { idx: 0, screen, at_line, full_len, range })
+        for (rel_idx, line) in rect.into_iter().enumerate() {
+            let Line { row, col, width } = line;
+            backend.go_to(row, col);
+            match self.thumb.contains(&(rel_idx as u16)) {
+                true => backend.pad_styled(width, thumb_style.clone()),
+                false => backend.pad(width),
+            }
+        }
+    }
+
+    pub fn contains_position(&self, row: u16, column: u16) -> bool {
+        self.is_visible()
+            && self.col <= column
+            && self.row <= row
+            && row < self.row + self.height
+            && column < self.col + self.width as u16
     }
 
-    fn check(&self, idx: usize) -> bool {
-        self.range.start <= idx && idx < self.range.end
+    /// maps click position to action, None if outside of the rendered scrollbar
+    /// click on the thumb grabs it (following drag_to keeps the grab offset) without moving it
+    pub fn hit(&mut self, row: u16, column: u16) -> Option<ScrollAction> {
+        if !self.contains_position(row, column) {
+            return None;
+        }
+        let rel_row = row - self.row;
+        if rel_row < self.thumb.start {
+            Some(ScrollAction::PageUp)
+        } else if rel_row >= self.thumb.end {
+            Some(ScrollAction::PageDown)
+        } else {
+            self.grab_offset = rel_row - self.thumb.start;
+            self.drag_to(row)
+        }
     }
 
-    pub fn skip(&mut self) {
-        self.idx += 1;
+    /// maps row to drag action regardless of column (allows dragging outside of the bar)
+    /// thumb start follows the row minus the grab offset, clamped to the track
+    pub fn drag_to(&self, row: u16) -> Option<ScrollAction> {
+        if !self.is_visible() {
+            return None;
+        }
+        let thumb_len = self.thumb.end - self.thumb.start;
+        let track_len = self.height - thumb_len;
+        let start = std::cmp::min(row.saturating_sub(self.row + self.grab_offset), track_len);
+        let max_at_line = self.full_len - self.height as usize;
+        if start == self.thumb.start {
+            // thumb rows are rounded - keep the current position
+            return Some(ScrollAction::DragTo(
+                self.at_line as f64 / max_at_line as f64,
+            ));
+        }
+        Some(ScrollAction::DragTo(start as f64 / track_len as f64))
     }
 
-    pub fn check_next(&mut self) -> bool {
-        let result = self.range.start <= self.idx && self.idx < self.range.end;
-        self.idx += 1;
-        result
+    /// applies action on list state, selection is kept within the new view
+    pub fn apply<B: CursorControl + StyleControl>(
+        action: ScrollAction,
+        state: &mut State<B>,
+        option_len: usize,
+        height: usize,
+    ) {
+        if height == 0 {
+            return;
+        }
+        let max_at_line = option_len.saturating_sub(height);
+        state.at_line = match action {
+            ScrollAction::PageUp => state.at_line.saturating_sub(height),
+            ScrollAction::PageDown => std::cmp::min(state.at_line + height, max_at_line),
+            ScrollAction::DragTo(fraction) => {
+                let fraction = fraction.clamp(0.0, 1.0);
+                std::cmp::min(
+                    (fraction * max_at_line as f64).round() as usize,
+                    max_at_line,
+                )
+            }
+        };
+        if state.selected < state.at_line {
+            state.selected = state.at_line;
+        } else if state.selected >= state.at_line + height {
+            state.selected = state.at_line + height - 1;
+        }
+        if option_len != 0 && state.selected >= option_len {
+            state.selected = option_len - 1;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ScrollBar;
+    use super::{ScrollAction, Scrollbar};
+    use crate::{
+        backend::{MockedBackend, MockedStyle, ScreenControl, StyleExt},
+        layout::Rect,
+        widgets::State,
+    };
 
     #[test]
     fn test_scroll() {
-        let sb = ScrollBar::new(0, 0, 40, 20, 134).unwrap();
-        assert_eq!(sb, ScrollBar { x: 0, y: 0, screen: 40, at_line: 20, full_len: 134, range: 5..16 })
+        let sb = Scrollbar::new(Rect::new(0, 0, 1, 40), 20, 134);
+        assert_eq!(sb.thumb(), 6..18);
+        assert!(Scrollbar::new(Rect::new(0, 0, 1, 40), 0, 40)
+            .thumb()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_thumb_extremes() {
+        let rect = Rect::new(2, 10, 1, 10);
+        assert_eq!(Scrollbar::new(rect, 0, 100).thumb(), 0..1);
+        assert_eq!(Scrollbar::new(rect, 90, 100).thumb(), 9..10);
+        assert_eq!(Scrollbar::new(rect, 200, 100).thumb(), 9..10);
+    }
+
+    #[test]
+    fn test_hit() {
+        let mut sb = Scrollbar::new(Rect::new(2, 10, 1, 10), 45, 100);
+        assert_eq!(sb.thumb(), 5..6);
+        assert_eq!(sb.hit(2, 10), Some(ScrollAction::PageUp));
+        assert_eq!(sb.hit(6, 10), Some(ScrollAction::PageUp));
+        assert_eq!(sb.hit(8, 10), Some(ScrollAction::PageDown));
+        assert_eq!(sb.hit(11, 10), Some(ScrollAction::PageDown));
+        assert_eq!(sb.hit(7, 10), Some(ScrollAction::DragTo(0.5)));
+        assert_eq!(sb.hit(7, 11), None);
+        assert_eq!(sb.hit(12, 10), None);
+        assert_eq!(sb.hit(1, 10), None);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut state = State::<MockedBackend>::new();
+        Scrollbar::apply(ScrollAction::PageDown, &mut state, 100, 10);
+        assert_eq!(state.at_line, 10);
+        assert_eq!(state.selected, 10);
+        Scrollbar::apply(ScrollAction::PageUp, &mut state, 100, 10);
+        assert_eq!(state.at_line, 0);
+        assert_eq!(state.selected, 9);
+        Scrollbar::apply(ScrollAction::DragTo(1.0), &mut state, 100, 10);
+        assert_eq!(state.at_line, 90);
+        assert_eq!(state.selected, 90);
+        Scrollbar::apply(ScrollAction::PageDown, &mut state, 100, 10);
+        assert_eq!(state.at_line, 90);
+        Scrollbar::apply(ScrollAction::DragTo(0.0), &mut state, 100, 10);
+        assert_eq!(state.at_line, 0);
+        assert_eq!(state.selected, 9);
+    }
+
+    #[test]
+    fn test_grab_keeps_position() {
+        let rect = Rect::new(2, 10, 1, 10);
+        let mut sb = Scrollbar::new(rect, 45, 100);
+        let mut state = State::<MockedBackend>::new();
+        state.at_line = 45;
+        // grabbing does not move the thumb
+        let action = sb.hit(7, 10).unwrap();
+        Scrollbar::apply(action, &mut state, 100, 10);
+        assert_eq!(state.at_line, 45);
+        // thumb follows the drag by rows
+        Scrollbar::apply(sb.drag_to(8).unwrap(), &mut state, 100, 10);
+        assert_eq!(state.at_line, 60);
+        sb.update(rect, state.at_line, 100);
+        assert_eq!(sb.thumb(), 6..7);
+        Scrollbar::apply(sb.drag_to(8).unwrap(), &mut state, 100, 10);
+        assert_eq!(state.at_line, 60);
+
+        // grab offset within longer thumb
+        let mut sb = Scrollbar::new(rect, 0, 20);
+        assert_eq!(sb.thumb(), 0..5);
+        assert_eq!(sb.hit(5, 10), Some(ScrollAction::DragTo(0.0)));
+        assert_eq!(sb.drag_to(6), Some(ScrollAction::DragTo(0.2)));
+        assert_eq!(sb.drag_to(2), Some(ScrollAction::DragTo(0.0)));
+        assert_eq!(sb.drag_to(40), Some(ScrollAction::DragTo(1.0)));
+    }
+
+    #[test]
+    fn test_drag_round_trip() {
+        let rect = Rect::new(0, 0, 1, 10);
+        let sb = Scrollbar::new(rect, 0, 100);
+        let mut state = State::<MockedBackend>::new();
+        let action = sb.drag_to(9).unwrap();
+        Scrollbar::apply(action, &mut state, 100, 10);
+        assert_eq!(state.at_line, 90);
+        assert_eq!(sb.drag_to(50), Some(ScrollAction::DragTo(1.0)));
+        let sb = Scrollbar::new(rect, state.at_line, 100);
+        assert_eq!(sb.thumb(), 9..10);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut backend = MockedBackend::init();
+        let mut sb = Scrollbar::default();
+        sb.render(
+            Rect::new(0, 5, 1, 3),
+            2,
+            6,
+            MockedStyle::reversed(),
+            &mut backend,
+        );
+        assert_eq!(sb.thumb(), 1..3);
+        assert_eq!(
+            backend.drain(),
+            [
+                (MockedStyle::default(), "<<go to row: 0 col: 5>>".to_owned()),
+                (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+                (MockedStyle::default(), "<<go to row: 1 col: 5>>".to_owned()),
+                (
+                    MockedStyle::default(),
+                    format!("<<padding: 1, styled: {:?}>>", MockedStyle::reversed())
+                ),
+                (MockedStyle::default(), "<<go to row: 2 col: 5>>".to_owned()),
+                (
+                    MockedStyle::default(),
+                    format!("<<padding: 1, styled: {:?}>>", MockedStyle::reversed())
+                ),
+            ]
+        );
+        sb.render(
+            Rect::new(0, 5, 1, 3),
+            0,
+            3,
+            MockedStyle::reversed(),
+            &mut backend,
+        );
+        assert!(backend.drain().is_empty());
+        assert_eq!(sb.hit(0, 5), None);
     }
 }
@@ -0,0 +1,45 @@
+pub const BRAILLE_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+pub const LINE_FRAMES: &[char] = &['|', '/', '-', '\\'];
+
+/// Stateful progress spinner - call tick on each frame/update to advance
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spinner {
+    frames: &'static [char],
+    idx: usize,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinner {
+    pub const fn new() -> Self {
+        Self::with_frames(BRAILLE_FRAMES)
+    }
+
+    /// frames should be single width chars (empty frames fallback to braille)
+    pub const fn with_frames(frames: &'static [char]) -> Self {
+        let frames = match frames.is_empty() {
+            true => BRAILLE_FRAMES,
+            false => frames,
+        };
+        Self { frames, idx: 0 }
+    }
+
+    #[inline]
+    pub fn tick(&mut self) {
+        self.idx = (self.idx + 1) % self.frames.len();
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.idx = 0;
+    }
+
+    #[inline]
+    pub fn frame(&self) -> char {
+        self.frames[self.idx]
+    }
+}
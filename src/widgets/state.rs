@@ -1,47 +1,63 @@
+use super::{ListItem, Scrollbar};
 use crate::{
-    backend::Backend,
-    layout::{DoublePaddedRectIter, IterLines, LineBuilder, Rect},
+    backend::{CursorControl, StyleControl, TextPrint},
+    layout::{DoublePaddedRectIter, IterLines, Line, LineBuilder, Rect},
+    utils::UTFSafe,
 };
+use std::collections::HashSet;
 
 #[derive(PartialEq, Debug)]
-pub struct State<B: Backend> {
+pub struct State<B: CursorControl + StyleControl> {
     pub at_line: usize,
     pub selected: usize,
-    pub highlight: <B as Backend>::Style,
+    pub highlight: <B as TextPrint>::Style,
+    pub disabled_style: <B as TextPrint>::Style,
+    disabled: HashSet<usize>,
+    /// multi selection (checked options)
+    chosen: HashSet<usize>,
 }
 
-impl<B: Backend> Clone for State<B> {
+impl<B: CursorControl + StyleControl> Clone for State<B> {
     fn clone(&self) -> Self {
         Self {
             at_line: self.at_line,
             selected: self.selected,
             highlight: self.highlight.clone(),
+            disabled_style: self.disabled_style.clone(),
+            disabled: self.disabled.clone(),
+            chosen: self.chosen.clone(),
         }
     }
 }
 
-impl<B: Backend> Default for State<B> {
+impl<B: CursorControl + StyleControl> Default for State<B> {
     fn default() -> Self {
         Self::new()
     }
 }
 
 #[allow(dead_code)]
-impl<B: Backend> State<B> {
+impl<B: CursorControl + StyleControl> State<B> {
     pub fn new() -> Self {
         let highlight = B::reversed_style();
         Self {
             at_line: 0,
             selected: 0,
             highlight,
+            disabled_style: B::dim_style(),
+            disabled: HashSet::new(),
+            chosen: HashSet::new(),
         }
     }
 
-    pub fn with_highlight(highlight: <B as Backend>::Style) -> Self {
+    pub fn with_highlight(highlight: <B as TextPrint>::Style) -> Self {
         Self {
             at_line: 0,
             selected: 0,
             highlight,
+            disabled_style: B::dim_style(),
+            disabled: HashSet::new(),
+            chosen: HashSet::new(),
         }
     }
 
@@ -51,25 +67,153 @@ impl<B: Backend> State<B> {
         self.selected = 0;
     }
 
+    /// disabled options are rendered dimmed and skipped by navigation,
+    /// disabled selection is kept until the next navigation call moves it to an enabled option;
+    /// use `selection` to read it, as it returns None while the selected option is disabled
+    pub fn set_disabled(&mut self, indices: HashSet<usize>) {
+        self.disabled = indices;
+    }
+
+    #[inline]
+    pub fn is_disabled(&self, idx: usize) -> bool {
+        self.disabled.contains(&idx)
+    }
+
+    /// adds / removes selected option (if not disabled) from multi selection
+    pub fn toggle_selected(&mut self) {
+        if self.is_disabled(self.selected) {
+            return;
+        }
+        if !self.chosen.remove(&self.selected) {
+            self.chosen.insert(self.selected);
+        }
+    }
+
+    /// options chosen in multi selection
+    pub fn selected_set(&self) -> &HashSet<usize> {
+        &self.chosen
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.chosen.clear();
+    }
+
+    /// drops indices outside of options (call after the list shrinks)
+    pub fn prune(&mut self, option_len: usize) {
+        self.chosen.retain(|idx| *idx < option_len);
+        self.disabled.retain(|idx| *idx < option_len);
+        if self.selected >= option_len {
+            self.selected = option_len.saturating_sub(1);
+        }
+    }
+
+    /// selected option if it is in range and not disabled,
+    /// prefer it over `selected` as the selection is re-homed only on navigation
+    pub fn selection(&self, option_len: usize) -> Option<usize> {
+        if self.selected >= option_len || self.is_disabled(self.selected) {
+            return None;
+        }
+        Some(self.selected)
+    }
+
     pub fn select(&mut self, idx: usize, option_len: usize) {
-        if option_len > idx {
+        if option_len > idx && !self.is_disabled(idx) {
             self.selected = idx;
         }
     }
 
+    /// if all options are disabled the selection is not changed
     pub fn next(&mut self, option_len: usize) {
-        self.selected += 1;
-        if self.selected >= option_len {
-            self.selected = 0;
-        };
+        if self.disabled.is_empty() {
+            self.selected += 1;
+            if self.selected >= option_len {
+                self.selected = 0;
+            };
+            return;
+        }
+        let mut idx = self.selected;
+        for _ in 0..option_len {
+            idx += 1;
+            if idx >= option_len {
+                idx = 0;
+            }
+            if !self.is_disabled(idx) {
+                self.selected = idx;
+                return;
+            }
+        }
     }
 
+    /// if all options are disabled the selection is not changed
     pub fn prev(&mut self, option_len: usize) {
-        if self.selected > 0 {
-            self.selected -= 1;
-        } else if option_len > 0 {
-            self.selected = option_len - 1;
+        if self.disabled.is_empty() {
+            if self.selected > 0 {
+                self.selected -= 1;
+            } else if option_len > 0 {
+                self.selected = option_len - 1;
+            };
+            return;
+        }
+        let mut idx = self.selected;
+        for _ in 0..option_len {
+            idx = match idx == 0 || idx > option_len {
+                true => option_len - 1,
+                false => idx - 1,
+            };
+            if !self.is_disabled(idx) {
+                self.selected = idx;
+                return;
+            }
+        }
+    }
+
+    /// moves selection by page without wrapping (clamped to the last option)
+    pub fn page_down(&mut self, option_len: usize, page: usize) {
+        if option_len == 0 || page == 0 {
+            return;
+        }
+        let idx = std::cmp::min(self.selected.saturating_add(page), option_len - 1);
+        self.select_nearest(idx, option_len);
+    }
+
+    /// moves selection by page without wrapping (clamped to the first option)
+    pub fn page_up(&mut self, option_len: usize, page: usize) {
+        if option_len == 0 || page == 0 {
+            return;
+        }
+        let idx = std::cmp::min(self.selected.saturating_sub(page), option_len - 1);
+        self.select_nearest(idx, option_len);
+    }
+
+    /// selects the first option that is not disabled (unchanged if all are disabled)
+    pub fn first(&mut self, option_len: usize) {
+        if let Some(idx) = (0..option_len).find(|idx| !self.is_disabled(*idx)) {
+            self.selected = idx;
+        }
+    }
+
+    /// selects the last option that is not disabled
+    pub fn last(&mut self, option_len: usize) {
+        if let Some(idx) = (0..option_len).rev().find(|idx| !self.is_disabled(*idx)) {
+            self.selected = idx;
+        }
+    }
+
+    /// selects idx or the closest option that is not disabled (prefers the direction of move)
+    fn select_nearest(&mut self, idx: usize, option_len: usize) {
+        if !self.is_disabled(idx) {
+            self.selected = idx;
+            return;
+        }
+        let after = (idx + 1..option_len).find(|idx| !self.is_disabled(*idx));
+        let before = (0..idx).rev().find(|idx| !self.is_disabled(*idx));
+        let nearest = match idx >= self.selected {
+            true => after.or(before),
+            false => before.or(after),
         };
+        if let Some(idx) = nearest {
+            self.selected = idx;
+        }
     }
 
     #[inline]
@@ -81,6 +225,65 @@ impl<B: Backend> State<B> {
         };
     }
 
+    /// pages in units of page size (rows * columns) - at_line is the first option of the page
+    #[inline]
+    pub fn update_at_page(&mut self, page: usize) {
+        if page == 0 {
+            return;
+        }
+        if self.at_line > self.selected || self.selected - self.at_line >= page {
+            self.at_line = self.selected - self.selected % page;
+        }
+    }
+
+    /// moves selection to the previous column (column-major layout)
+    pub fn left(&mut self, rows: usize, option_len: usize) {
+        if rows == 0 || self.selected < rows {
+            return;
+        }
+        self.select(self.selected - rows, option_len);
+    }
+
+    /// moves selection to the next column (column-major layout)
+    /// if the next column is shorter selects the last option
+    pub fn right(&mut self, rows: usize, option_len: usize) {
+        if rows == 0 || option_len == 0 {
+            return;
+        }
+        let idx = self.selected + rows;
+        if idx < option_len {
+            self.select(idx, option_len);
+        } else if (option_len - 1) / rows > self.selected / rows {
+            self.select(option_len - 1, option_len);
+        }
+    }
+
+    /// renders options in even columns (column-major) - paging is done in units of rows * cols
+    pub fn render_list_columns<'a>(
+        &mut self,
+        options: impl Iterator<Item = &'a str>,
+        rect: Rect,
+        cols: usize,
+        backend: &mut B,
+    ) {
+        let cols = std::cmp::max(1, cols);
+        self.update_at_page(rect.height as usize * cols);
+        let mut options = options.enumerate().skip(self.at_line);
+        for column in rect.split_even_horizontal(cols) {
+            let mut lines = column.into_iter();
+            for line in lines.by_ref() {
+                match options.next() {
+                    Some((idx, text)) => self.render_option(idx, text, line, backend),
+                    None => {
+                        line.render_empty(backend);
+                        break;
+                    }
+                }
+            }
+            lines.clear_to_end(backend);
+        }
+    }
+
     #[inline]
     pub fn render_list_complex<T>(
         &mut self,
@@ -124,7 +327,7 @@ impl<B: Backend> State<B> {
     #[inline]
     pub fn render_list_styled<'a>(
         &mut self,
-        options: impl Iterator<Item = (&'a str, <B as Backend>::Style)>,
+        options: impl Iterator<Item = (&'a str, <B as TextPrint>::Style)>,
         rect: &Rect,
         backend: &mut B,
     ) {
@@ -140,24 +343,207 @@ impl<B: Backend> State<B> {
         lines.clear_to_end(backend);
     }
 
-    pub fn render_list<'a>(
+    /// options chosen in multi selection are rendered with chosen_style
+    /// (merged with highlight on the selected option)
+    pub fn render_list_multi<'a>(
         &mut self,
         options: impl Iterator<Item = &'a str>,
         rect: Rect,
+        chosen_style: <B as TextPrint>::Style,
         backend: &mut B,
     ) {
         self.update_at_line(rect.height as usize);
         let mut lines = rect.into_iter();
         for (idx, text) in options.enumerate().skip(self.at_line) {
             let Some(line) = lines.next() else { break };
-            match idx == self.selected {
-                true => line.render_styled(text, self.highlight.clone(), backend),
-                false => line.render(text, backend),
+            if !self.chosen.contains(&idx) {
+                self.render_option(idx, text, line, backend);
+                continue;
             }
+            let style = match idx == self.selected {
+                true => B::merge_style(chosen_style.clone(), self.highlight.clone()),
+                false => chosen_style.clone(),
+            };
+            line.render_styled(text, style, backend);
         }
         lines.clear_to_end(backend);
     }
 
+    /// zebra striped list, stripes are based on option index (stable on scroll)
+    /// highlight is merged on top of the stripe style
+    pub fn render_list_striped<'a>(
+        &mut self,
+        options: impl Iterator<Item = &'a str>,
+        rect: Rect,
+        even_style: <B as TextPrint>::Style,
+        odd_style: <B as TextPrint>::Style,
+        backend: &mut B,
+    ) {
+        self.update_at_line(rect.height as usize);
+        let mut lines = rect.into_iter();
+        for (idx, text) in options.enumerate().skip(self.at_line) {
+            let Some(line) = lines.next() else { break };
+            let mut style = match idx % 2 == 0 {
+                true => even_style.clone(),
+                false => odd_style.clone(),
+            };
+            if idx == self.selected {
+                style = B::merge_style(style, self.highlight.clone());
+            }
+            line.render_styled(text, style, backend);
+        }
+        lines.clear_to_end(backend);
+    }
+
+    pub fn render_list<'a>(
+        &mut self,
+        options: impl Iterator<Item = &'a str>,
+        rect: Rect,
+        backend: &mut B,
+    ) {
+        self.render_list_lines(options, rect.into_iter(), backend);
+    }
+
+    /// same as render_list, but renders into any lines (e.g. RectIter::centered)
+    pub fn render_list_lines<'a>(
+        &mut self,
+        options: impl Iterator<Item = &'a str>,
+        mut lines: impl IterLines,
+        backend: &mut B,
+    ) {
+        self.update_at_line(lines.len());
+        for (idx, text) in options.enumerate().skip(self.at_line) {
+            let Some(line) = lines.next() else { break };
+            self.render_option(idx, text, line, backend);
+        }
+        lines.clear_to_end(backend);
+    }
+
+    /// items render directly into the line, highlight is applied as backend style while rendering
+    /// and the previous backend style is restored after each highlighted item
+    pub fn render_items<'a, I: ListItem<B> + ?Sized + 'a>(
+        &mut self,
+        items: impl Iterator<Item = &'a I>,
+        rect: Rect,
+        backend: &mut B,
+    ) {
+        self.update_at_line(rect.height as usize);
+        let mut lines = rect.into_iter();
+        for (idx, item) in items.enumerate().skip(self.at_line) {
+            let Some(line) = lines.next() else { break };
+            let style = if self.is_disabled(idx) {
+                self.disabled_style.clone()
+            } else if idx == self.selected {
+                self.highlight.clone()
+            } else {
+                item.render_into(line.unsafe_builder(backend));
+                continue;
+            };
+            let restore_style = backend.get_style();
+            backend.set_style(style);
+            item.render_into(line.unsafe_builder(backend));
+            backend.set_style(restore_style);
+        }
+        lines.clear_to_end(backend);
+    }
+
+    /// draws scroll track ('░') with thumb ('█') on the rightmost column of the rect
+    /// if all options fit, renders full thumb or nothing (hide_when_unneeded)
+    pub fn render_scrollbar(
+        &self,
+        total: usize,
+        rect: &Rect,
+        hide_when_unneeded: bool,
+        backend: &mut B,
+    ) {
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let col = rect.col + (rect.width - 1) as u16;
+        let scrollbar = Scrollbar::new(
+            Rect::new(rect.row, col, 1, rect.height),
+            self.at_line,
+            total,
+        );
+        let thumb = match scrollbar.is_visible() {
+            true => scrollbar.thumb(),
+            false if hide_when_unneeded => return,
+            false => 0..rect.height,
+        };
+        for (idx, row) in (rect.row..rect.row + rect.height).enumerate() {
+            let glyph = match thumb.contains(&(idx as u16)) {
+                true => '█',
+                false => '░',
+            };
+            backend.print_at(row, col, glyph);
+        }
+    }
+
+    /// options wider than the rect are truncated in the middle with ellipsis
+    pub fn render_list_middle_ellipsis<'a>(
+        &mut self,
+        options: impl Iterator<Item = &'a str>,
+        rect: Rect,
+        ellipsis: &str,
+        backend: &mut B,
+    ) {
+        self.update_at_line(rect.height as usize);
+        let mut lines = rect.into_iter();
+        for (idx, text) in options.enumerate().skip(self.at_line) {
+            let Some(line) = lines.next() else { break };
+            let text = text.truncate_width_middle(line.width, ellipsis);
+            self.render_option(idx, &text, line, backend);
+        }
+        lines.clear_to_end(backend);
+    }
+
+    /// renders only the previously and newly selected rows if the list was not scrolled
+    /// otherwise fallbacks to render_list - returns the rendered rows
+    pub fn render_diff<'a>(
+        &mut self,
+        prev_selected: usize,
+        prev_at_line: usize,
+        options: impl Iterator<Item = &'a str>,
+        rect: Rect,
+        backend: &mut B,
+    ) -> Vec<u16> {
+        self.update_at_line(rect.height as usize);
+        if self.at_line != prev_at_line {
+            self.render_list(options, rect, backend);
+            return rect.into_iter().map(|line| line.row).collect();
+        }
+        let mut rendered = Vec::new();
+        if prev_selected == self.selected {
+            return rendered;
+        }
+        let last_idx = std::cmp::max(prev_selected, self.selected);
+        for (idx, text) in options.enumerate().skip(self.at_line) {
+            if idx > last_idx {
+                break;
+            }
+            if idx != prev_selected && idx != self.selected {
+                continue;
+            }
+            let Some(line) = rect.get_line((idx - self.at_line) as u16) else {
+                break;
+            };
+            rendered.push(line.row);
+            self.render_option(idx, text, line, backend);
+        }
+        rendered
+    }
+
+    #[inline]
+    fn render_option(&self, idx: usize, text: &str, line: Line, backend: &mut B) {
+        if self.is_disabled(idx) {
+            line.render_styled(text, self.disabled_style.clone(), backend);
+        } else if idx == self.selected {
+            line.render_styled(text, self.highlight.clone(), backend);
+        } else {
+            line.render(text, backend);
+        }
+    }
+
     pub fn render_list_padded<'a>(
         &mut self,
         options: impl Iterator<Item = &'a str>,
@@ -1,7 +1,8 @@
 use crate::{
-    backend::{Backend, MockedBackend, MockedStyle, StyleExt},
-    layout::{Line, Rect},
-    widgets::{State, Writable},
+    backend::{prelude::*, BufferBackend, FrameGuard, MockedBackend, MockedStyle, RenderStats},
+    layout::{IterLines, Line, LineBuilder, Rect},
+    utils::UTFSafe,
+    widgets::{Layers, ListItem, Message, Prompt, State, TextCache, TextTransform, Writable},
 };
 
 use super::{StyledLine, Text};
@@ -268,6 +269,69 @@ fn test_line_wrap_complex() {
     );
 }
 
+#[test]
+fn test_wrap_words() {
+    let mut backend = MockedBackend::init();
+    let rect = Rect::new(0, 0, 10, 5);
+    let text: Text<MockedBackend> = Text::new(
+        "the quick brown  fox jumps over".to_owned(),
+        Some(MockedStyle::fg(3)),
+    );
+    text.wrap_words(&mut rect.into_iter(), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::fg(3), "the quick".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::fg(3), "brown  fox".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+            (MockedStyle::fg(3), "jumps over".to_owned()),
+        ]
+    );
+
+    let long_token: Text<MockedBackend> = Text::from("abcdefghijklmnopqrst".to_owned());
+    long_token.wrap_words(&mut rect.into_iter(), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "abcdefghij".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "klmnopqrst".to_owned()),
+        ]
+    );
+
+    // runs out of lines
+    let rect = Rect::new(0, 0, 10, 1);
+    text.wrap_words(&mut rect.into_iter(), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::fg(3), "the quick".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+
+    // char wider than the line is skipped, the rest of the text is kept
+    let rect = Rect::new(0, 0, 1, 3);
+    let wide: Text<MockedBackend> = Text::from("a🦀b".to_owned());
+    wide.wrap_words(&mut rect.into_iter(), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "a".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+            (MockedStyle::default(), "b".to_owned()),
+        ]
+    );
+}
+
 #[test]
 fn test_line_wrap_simple() {
     let mut backend = MockedBackend::init();
@@ -369,3 +433,1452 @@ fn base_state() {
         ]
     );
 }
+
+#[test]
+fn striped_state() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["tres", "duo", "unus", "nihil"];
+    let rect = Rect::new(0, 0, 5, 2);
+    let even = MockedStyle::bg(1);
+    let odd = MockedStyle::bg(2);
+    let mut selected = MockedStyle::bg(1);
+    selected.add_reverse();
+
+    state.render_list_striped(
+        options.into_iter(),
+        rect,
+        even.clone(),
+        odd.clone(),
+        &mut backend,
+    );
+    let styled_text = backend
+        .drain()
+        .into_iter()
+        .filter(|(_, text)| !text.starts_with("<<"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        styled_text,
+        vec![
+            (selected.clone(), "tres".to_owned()),
+            (odd.clone(), "duo".to_owned()),
+        ]
+    );
+
+    state.select(2, options.len());
+    state.render_list_striped(
+        options.into_iter(),
+        rect,
+        even.clone(),
+        odd.clone(),
+        &mut backend,
+    );
+    assert_eq!(state.at_line, 1);
+    let styled_text = backend
+        .drain()
+        .into_iter()
+        .filter(|(_, text)| !text.starts_with("<<"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        styled_text,
+        vec![
+            (odd.clone(), "duo".to_owned()),
+            (selected, "unus".to_owned()),
+        ]
+    );
+
+    state.select(3, options.len());
+    state.render_list_striped(
+        options.into_iter(),
+        rect,
+        even.clone(),
+        odd.clone(),
+        &mut backend,
+    );
+    assert_eq!(state.at_line, 2);
+    let styled_text = backend
+        .drain()
+        .into_iter()
+        .filter(|(_, text)| !text.starts_with("<<"))
+        .collect::<Vec<_>>();
+    let mut selected_odd = odd;
+    selected_odd.add_reverse();
+    assert_eq!(
+        styled_text,
+        vec![
+            (even, "unus".to_owned()),
+            (selected_odd, "nihil".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn text_wrap_excluding_cols() {
+    let mut backend = MockedBackend::init();
+    let text = Text::<MockedBackend>::from("abcdefghijkl".to_owned());
+    let rect = Rect::new(0, 0, 6, 4);
+    let mut lines = rect.into_iter().excluding_cols(5..6);
+    text.wrap(&mut lines, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "abcde".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "fghij".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+            (MockedStyle::default(), "kl".to_owned()),
+            (MockedStyle::default(), "<<padding: 3>>".to_owned()),
+        ]
+    );
+    lines.clear_to_end(&mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 3 col: 0>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 5>>".to_owned()),
+        ]
+    );
+
+    let text = Text::<MockedBackend>::from("a🚀bcde🚀fg".to_owned());
+    let mut lines = rect.into_iter().excluding_cols(0..1);
+    assert_eq!(lines.width(), 5);
+    text.wrap(&mut lines, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 1>>".to_owned()),
+            (MockedStyle::default(), "a🚀bc".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 1>>".to_owned()),
+            (MockedStyle::default(), "de🚀f".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 1>>".to_owned()),
+            (MockedStyle::default(), "g".to_owned()),
+            (MockedStyle::default(), "<<padding: 4>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn layers_order() {
+    let mut backend = MockedBackend::init();
+    let mut layers = Layers::new();
+    layers.push(
+        2,
+        Rect::new(2, 2, 5, 5),
+        |rect, backend: &mut MockedBackend| backend.print(format!("modal {}", rect.row)),
+    );
+    layers.push(0, Rect::new(0, 0, 10, 10), |_, backend| {
+        backend.print("background")
+    });
+    layers.push_transparent(1, Rect::new(0, 0, 10, 1), |_, backend| {
+        backend.print("status")
+    });
+    layers.push(2, Rect::new(3, 3, 2, 2), |_, backend| {
+        backend.print("popup")
+    });
+    assert_eq!(layers.len(), 4);
+    layers.render(&mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<freeze>>".to_owned()),
+            (MockedStyle::default(), "background".to_owned()),
+            (MockedStyle::default(), "status".to_owned()),
+            (MockedStyle::default(), "modal 2".to_owned()),
+            (MockedStyle::default(), "popup".to_owned()),
+            (MockedStyle::default(), "<<unfreeze>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn layers_covered_skip() {
+    let mut backend = MockedBackend::init();
+    let mut layers = Layers::new();
+    layers.push(
+        3,
+        Rect::new(0, 0, 10, 10),
+        |_, backend: &mut MockedBackend| backend.print("full screen"),
+    );
+    layers.push(1, Rect::new(2, 2, 5, 5), |_, backend| {
+        backend.print("covered")
+    });
+    layers.push(2, Rect::new(8, 8, 5, 5), |_, backend| {
+        backend.print("overflow")
+    });
+    layers.push_transparent(4, Rect::new(0, 0, 10, 10), |_, backend| {
+        backend.print("overlay")
+    });
+    layers.render(&mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<freeze>>".to_owned()),
+            (MockedStyle::default(), "overflow".to_owned()),
+            (MockedStyle::default(), "full screen".to_owned()),
+            (MockedStyle::default(), "overlay".to_owned()),
+            (MockedStyle::default(), "<<unfreeze>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn layers_in_frame() {
+    let mut backend = MockedBackend::init();
+    let mut layers = Layers::new();
+    layers.push(
+        1,
+        Rect::new(2, 2, 5, 5),
+        |_, backend: &mut MockedBackend| backend.print("modal"),
+    );
+    layers.push(0, Rect::new(0, 0, 10, 10), |_, backend| {
+        backend.print("background")
+    });
+    let mut frame = FrameGuard::new(&mut backend);
+    frame.print("before layers");
+    layers.render_frame(&mut frame);
+    frame.print("after layers");
+    drop(frame);
+    // single freeze / unfreeze bracket of the frame
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<freeze>>".to_owned()),
+            (MockedStyle::default(), "before layers".to_owned()),
+            (MockedStyle::default(), "background".to_owned()),
+            (MockedStyle::default(), "modal".to_owned()),
+            (MockedStyle::default(), "after layers".to_owned()),
+            (MockedStyle::default(), "<<unfreeze>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn text_cache() {
+    let mut cache = TextCache::<MockedBackend>::with_capacity(2);
+    let text = cache.get_or_insert("asd🚀");
+    assert_eq!(text.width(), 5);
+    assert_eq!(text.char_len(), 4);
+    assert_eq!(text.as_str(), "asd🚀");
+    cache.get_or_insert("asd🚀");
+    assert_eq!(cache.len(), 1);
+    cache.get_or_insert("label");
+    assert_eq!(cache.len(), 2);
+    // capacity reached - cache is reset
+    assert_eq!(cache.get_or_insert("menu").width(), 4);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get("label").is_none());
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn text_underline_color() {
+    let mut backend = MockedBackend::init();
+    let text = Text::<MockedBackend>::new(
+        "mispeled".to_owned(),
+        Some(MockedStyle::undercurled(Some(1))),
+    );
+    text.print(&mut backend);
+    let mut style = MockedStyle::fg(4);
+    style.underline(Some(2));
+    let text = Text::<MockedBackend>::new("link".to_owned(), Some(style));
+    text.print(&mut backend);
+    let data = backend.drain();
+    assert_eq!(
+        data[0],
+        (MockedStyle::undercurled(Some(1)), "mispeled".to_owned())
+    );
+    assert_ne!(data[0].0, MockedStyle::undercurled(Some(2)));
+    assert_ne!(data[0].0, MockedStyle::undercurled(None));
+    let mut expected = MockedStyle::underlined(Some(2));
+    expected.set_fg(Some(4));
+    assert_eq!(data[1], (expected, "link".to_owned()));
+}
+
+#[test]
+fn prompt_render() {
+    let mut backend = MockedBackend::init();
+    let mut prompt =
+        Prompt::<MockedBackend>::new("Name", Some("idiom".to_owned()), Rect::new(0, 0, 40, 10));
+    assert_eq!(prompt.rect(), Rect::new(3, 4, 32, 3).with_borders());
+    prompt.render(&mut backend);
+    let data = backend.drain();
+    let title_idx = data.iter().position(|(_, text)| text == "Name").unwrap();
+    assert_eq!(data[title_idx - 1].1, "<<go to row: 3 col: 5>>");
+    assert_eq!(
+        data[title_idx + 1..],
+        [
+            (MockedStyle::default(), "<<go to row: 4 col: 5>>".to_owned()),
+            (MockedStyle::default(), " >> ".to_owned()),
+            (MockedStyle::default(), "idiom".to_owned()),
+            (MockedStyle::reversed(), " ".to_owned()),
+            (MockedStyle::default(), "<<padding: 20>>".to_owned()),
+        ]
+    );
+
+    prompt.field_mut().push_char('!');
+    prompt.resize(Rect::new(0, 0, 60, 20));
+    assert_eq!(prompt.rect(), Rect::new(8, 14, 32, 3).with_borders());
+    assert_eq!(prompt.field().as_str(), "idiom!");
+}
+
+#[test]
+fn prompt_render_no_space() {
+    let mut backend = MockedBackend::init();
+    let prompt = Prompt::<MockedBackend>::new("Name", None, Rect::new(0, 0, 40, 2));
+    assert_eq!(prompt.rect().height, 0);
+    prompt.render(&mut backend);
+    assert!(backend.drain().is_empty());
+}
+
+#[cfg(feature = "crossterm_backend")]
+#[test]
+fn prompt_map() {
+    use crate::widgets::PromptEvent;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    let mut prompt = Prompt::<MockedBackend>::new("Name", None, Rect::new(0, 0, 40, 10));
+    assert_eq!(
+        prompt.map(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty())),
+        PromptEvent::Edited
+    );
+    assert_eq!(
+        prompt.map(KeyEvent::new(KeyCode::Right, KeyModifiers::empty())),
+        PromptEvent::Skipped
+    );
+    assert_eq!(
+        prompt.map(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())),
+        PromptEvent::Submitted("a".to_owned())
+    );
+    assert!(prompt.field().is_empty());
+    assert_eq!(
+        prompt.map(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty())),
+        PromptEvent::Cancelled
+    );
+}
+
+#[test]
+fn state_render_diff() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["tres", "duo", "unus", "nihil"];
+    let rect = Rect::new(0, 0, 4, 3);
+    state.render_list(options.into_iter(), rect, &mut backend);
+    backend.drain();
+
+    let (prev_selected, prev_at_line) = (state.selected, state.at_line);
+    state.next(options.len());
+    let rows = state.render_diff(
+        prev_selected,
+        prev_at_line,
+        options.into_iter(),
+        rect,
+        &mut backend,
+    );
+    assert_eq!(rows, [0, 1]);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "tres".to_owned()),
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::reversed(), "duo".to_owned()),
+            (MockedStyle::reversed(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+        ]
+    );
+
+    let (prev_selected, prev_at_line) = (state.selected, state.at_line);
+    let rows = state.render_diff(
+        prev_selected,
+        prev_at_line,
+        options.into_iter(),
+        rect,
+        &mut backend,
+    );
+    assert!(rows.is_empty());
+    assert!(backend.drain().is_empty());
+
+    // scroll
+    state.select(3, options.len());
+    let rows = state.render_diff(
+        prev_selected,
+        prev_at_line,
+        options.into_iter(),
+        rect,
+        &mut backend,
+    );
+    assert_eq!(state.at_line, 1);
+    assert_eq!(rows, [0, 1, 2]);
+    let mut full_backend = MockedBackend::init();
+    state.render_list(options.into_iter(), rect, &mut full_backend);
+    assert_eq!(backend.drain(), full_backend.drain());
+}
+
+#[test]
+fn state_disabled_navigation() {
+    let mut state = MState::new();
+    let option_len = 6;
+    state.set_disabled([1, 2, 5].into_iter().collect());
+    state.next(option_len);
+    assert_eq!(state.selected, 3);
+    state.next(option_len);
+    assert_eq!(state.selected, 4);
+    // wraps past disabled end
+    state.next(option_len);
+    assert_eq!(state.selected, 0);
+    state.prev(option_len);
+    assert_eq!(state.selected, 4);
+    state.prev(option_len);
+    state.prev(option_len);
+    assert_eq!(state.selected, 0);
+    state.select(2, option_len);
+    assert_eq!(state.selected, 0);
+    assert_eq!(state.selection(option_len), Some(0));
+
+    // wraps past disabled start
+    state.set_disabled([0, 1].into_iter().collect());
+    state.prev(option_len);
+    assert_eq!(state.selected, 5);
+    state.next(option_len);
+    assert_eq!(state.selected, 2);
+}
+
+#[test]
+fn state_all_disabled() {
+    let mut state = MState::new();
+    state.set_disabled([0, 1, 2].into_iter().collect());
+    state.next(3);
+    assert_eq!(state.selected, 0);
+    state.prev(3);
+    assert_eq!(state.selected, 0);
+    assert_eq!(state.selection(3), None);
+    assert_eq!(MState::new().selection(0), None);
+    // re-homed on the next navigation once an option is enabled
+    state.set_disabled([0, 1].into_iter().collect());
+    assert_eq!(state.selection(3), None);
+    state.next(3);
+    assert_eq!(state.selection(3), Some(2));
+}
+
+#[test]
+fn state_render_disabled() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["tres", "duo", "unus"];
+    state.set_disabled([0].into_iter().collect());
+    // disabled selection is kept until navigation
+    assert_eq!(state.selected, 0);
+    assert_eq!(state.selection(options.len()), None);
+    state.render_list(options.into_iter(), Rect::new(0, 0, 4, 2), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::dim(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::dim(), "tres".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "duo".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn text_wrap_from_flow() {
+    let mut backend = MockedBackend::init();
+    let texts = [
+        Text::<MockedBackend>::from("abc".to_owned()),
+        Text::new("defg".to_owned(), Some(MockedStyle::bold())),
+        Text::from("h🚀i".to_owned()),
+    ];
+    let rect = Rect::new(0, 0, 5, 3);
+    let mut lines = rect.into_iter();
+    let mut remaining = 0;
+    for text in texts.iter() {
+        remaining = text.wrap_from(&mut lines, remaining, &mut backend).unwrap();
+    }
+    assert_eq!(remaining, 4);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "abc".to_owned()),
+            (MockedStyle::bold(), "de".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "fg".to_owned()),
+            (MockedStyle::default(), "h".to_owned()),
+            (MockedStyle::default(), "🚀".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+            (MockedStyle::default(), "i".to_owned()),
+        ]
+    );
+
+    // lines ran out
+    let mut lines = Rect::new(0, 0, 2, 1).into_iter();
+    assert_eq!(texts[1].wrap_from(&mut lines, 0, &mut backend), None);
+    let line = StyledLine::<MockedBackend>::from(vec![
+        Text::from("abc".to_owned()),
+        Text::new("defg".to_owned(), Some(MockedStyle::bold())),
+        Text::from("h🚀i".to_owned()),
+    ]);
+    let mut lines = Rect::new(0, 0, 5, 3).into_iter();
+    assert_eq!(line.wrap_from(&mut lines, 0, &mut backend), Some(4));
+}
+
+#[test]
+fn print_at_with_bg() {
+    let mut backend = MockedBackend::init();
+    let line = StyledLine::<MockedBackend>::from(vec![
+        Text::new("ab".to_owned(), Some(MockedStyle::bg(1))),
+        Text::from("c".to_owned()),
+    ]);
+    line.print_at_with_bg(
+        Rect::new(1, 2, 6, 1).get_line(0).unwrap(),
+        MockedStyle::bg(1),
+        &mut backend,
+    );
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::bg(1), "ab".to_owned()),
+            (MockedStyle::default(), "c".to_owned()),
+            (MockedStyle::default(), "<<padding: 3, styled: MockedStyle { fg: None, bg: Some(1), underline_color: None, attrs: [] }>>".to_owned()),
+        ]
+    );
+
+    let text = Text::<MockedBackend>::from("abcdef".to_owned());
+    text.print_at_with_bg(
+        Rect::new(0, 0, 4, 1).get_line(0).unwrap(),
+        MockedStyle::bg(1),
+        &mut backend,
+    );
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "abcd".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn styled_line_builder() {
+    let mut line = StyledLine::<MockedBackend>::default();
+    line.push_str("fn ");
+    line.push_styled("main🦀", MockedStyle::bold());
+    line.push(Text::from('('));
+    assert_eq!(line.width(), 10);
+    assert_eq!(line.char_len(), 9);
+    assert_eq!(line.len(), 12);
+    assert_eq!(line.segments().len(), 3);
+    assert_eq!(line.segments()[1].style, Some(MockedStyle::bold()));
+    assert_eq!(line.to_string(), "fn main🦀(");
+    line.clear();
+    assert!(line.is_empty());
+    assert_eq!(line.width(), 0);
+    assert!(line.segments().is_empty());
+    line.push_str("x");
+    assert_eq!(line.width(), 1);
+}
+
+#[test]
+fn styled_line_print_aligned() {
+    let mut backend = MockedBackend::init();
+    let line = StyledLine::<MockedBackend>::from(vec![
+        Text::from("ab".to_owned()),
+        Text::new("cd".to_owned(), Some(MockedStyle::bold())),
+        Text::from("e".to_owned()),
+    ]);
+    let at = |width| Line {
+        row: 1,
+        col: 2,
+        width,
+    };
+    line.print_at_right(at(8), &mut backend);
+    line.print_at_center(at(8), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 3>>".to_owned()),
+            (MockedStyle::default(), "ab".to_owned()),
+            (MockedStyle::bold(), "cd".to_owned()),
+            (MockedStyle::default(), "e".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            (MockedStyle::default(), "ab".to_owned()),
+            (MockedStyle::bold(), "cd".to_owned()),
+            (MockedStyle::default(), "e".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+
+    // truncated on the side opposite to alignment
+    line.print_at_right(at(3), &mut backend);
+    line.print_at_center(at(3), &mut backend);
+    line.print_at_right(at(0), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::bold(), "cd".to_owned()),
+            (MockedStyle::default(), "e".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "ab".to_owned()),
+            (MockedStyle::bold(), "c".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn styled_line_from_markup() {
+    let resolver = |tag: &str| match tag {
+        "bold" => Some(MockedStyle::bold()),
+        "red" => Some(MockedStyle::fg(1)),
+        _ => None,
+    };
+    let line = StyledLine::<MockedBackend>::from_markup(
+        "{bold}error:{/} something {red}failed{/}",
+        resolver,
+    );
+    assert_eq!(
+        line.inner,
+        vec![
+            Text::new("error:".to_owned(), Some(MockedStyle::bold())),
+            Text::raw(" something ".to_owned()),
+            Text::new("failed".to_owned(), Some(MockedStyle::fg(1))),
+        ]
+    );
+
+    // nested tags restore outer style on close
+    let line = StyledLine::<MockedBackend>::from_markup("{bold}a{red}b{/}c{/}d", resolver);
+    assert_eq!(
+        line.inner,
+        vec![
+            Text::new("a".to_owned(), Some(MockedStyle::bold())),
+            Text::new("b".to_owned(), Some(MockedStyle::fg(1))),
+            Text::new("c".to_owned(), Some(MockedStyle::bold())),
+            Text::raw("d".to_owned()),
+        ]
+    );
+
+    // literal braces and malformed tags
+    let line =
+        StyledLine::<MockedBackend>::from_markup("{{x}} {unknown}{/} }{bold}🚀 {open", resolver);
+    assert_eq!(
+        line.inner,
+        vec![
+            Text::raw("{x} {unknown}{/} }".to_owned()),
+            Text::new("🚀 {open".to_owned(), Some(MockedStyle::bold())),
+        ]
+    );
+    assert_eq!(line.width(), 26);
+}
+
+#[test]
+fn styled_line_from_match() {
+    let hl = MockedStyle::bold();
+    let segments = |line: StyledLine<MockedBackend>| {
+        line.segments()
+            .iter()
+            .map(|text| (text.style(), text.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let line = StyledLine::<MockedBackend>::from_match("hello", &[0..1, 1..2], None, hl.clone());
+    assert_eq!(
+        segments(line),
+        vec![
+            (Some(hl.clone()), "he".to_owned()),
+            (None, "llo".to_owned())
+        ]
+    );
+
+    let line = StyledLine::<MockedBackend>::from_match("a🦀b", &[1..2, 2..2], None, hl.clone());
+    assert_eq!(line.width(), 4);
+    assert_eq!(
+        segments(line),
+        vec![
+            (None, "a".to_owned()),
+            (Some(hl.clone()), "🦀".to_owned()),
+            (None, "b".to_owned())
+        ]
+    );
+
+    // adjacent / overlapping merge, out of bounds is clamped
+    let base = MockedStyle::fg(1);
+    let line = StyledLine::<MockedBackend>::from_match(
+        "abcdef",
+        &[3..5, 1..2, 2..4, 9..12],
+        Some(base.clone()),
+        hl.clone(),
+    );
+    assert_eq!(
+        segments(line),
+        vec![
+            (Some(base.clone()), "a".to_owned()),
+            (Some(hl.clone()), "bcde".to_owned()),
+            (Some(base), "f".to_owned())
+        ]
+    );
+    let line = StyledLine::<MockedBackend>::from_match("ab", &[1..7, 5..9], None, hl.clone());
+    assert_eq!(
+        segments(line),
+        vec![(None, "a".to_owned()), (Some(hl), "b".to_owned())]
+    );
+}
+
+#[test]
+fn styled_line_print_at_offset() {
+    let mut backend = MockedBackend::init();
+    let line = StyledLine::<MockedBackend>::from(vec![
+        Text::from("ab".to_owned()),
+        Text::new("字字字".to_owned(), Some(MockedStyle::bold())),
+        Text::from("cd".to_owned()),
+    ]);
+    let view = Line {
+        row: 1,
+        col: 2,
+        width: 6,
+    };
+
+    // offset in the middle of a wide char
+    line.print_at_offset(view.clone(), 3, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::bold(), "字字".to_owned()),
+            (MockedStyle::default(), "c".to_owned()),
+        ]
+    );
+
+    line.print_at_offset(view.clone(), 4, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::bold(), "字字".to_owned()),
+            (MockedStyle::default(), "cd".to_owned()),
+        ]
+    );
+
+    // right edge cutting a wide char
+    line.print_at_offset(view.clone(), 1, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "b".to_owned()),
+            (MockedStyle::bold(), "字字".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+
+    line.print_at_offset(view, 20, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 6>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn zero_width_wrap() {
+    let mut backend = MockedBackend::init();
+    let rect = Rect::new(0, 0, 0, 3);
+    let text = Text::<MockedBackend>::from("abcdef".to_owned());
+    let complex = Text::<MockedBackend>::from("a🚀b".to_owned());
+    text.wrap(&mut rect.into_iter(), &mut backend);
+    complex.wrap(&mut rect.into_iter(), &mut backend);
+    text.simple_wrap(&mut rect.into_iter(), &mut backend);
+    assert_eq!(text.wrap_from(&mut rect.into_iter(), 0, &mut backend), None);
+    assert_eq!(
+        text.wrap_with_remainder_simple(&mut rect.into_iter(), &mut backend),
+        None
+    );
+    let line = StyledLine::<MockedBackend>::from(vec![text, complex]);
+    line.wrap(&mut rect.into_iter(), &mut backend);
+    assert!(backend.drain().is_empty());
+}
+
+#[test]
+fn text_tab_expansion() {
+    let mut backend = MockedBackend::init();
+    let text = Text::<MockedBackend>::with_tab_width("\tab\tc".to_owned(), None, 4);
+    assert_eq!(text.width(), 9);
+    assert_eq!(text.to_string(), "\tab\tc");
+    text.print_at(
+        Line {
+            row: 0,
+            col: 0,
+            width: 12,
+        },
+        &mut backend,
+    );
+    text.print_at(
+        Line {
+            row: 1,
+            col: 0,
+            width: 5,
+        },
+        &mut backend,
+    );
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "    ab  c".to_owned()),
+            (MockedStyle::default(), "<<padding: 3>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "    a".to_owned()),
+        ]
+    );
+
+    let rect = Rect::new(0, 0, 5, 3);
+    text.wrap(&mut rect.into_iter(), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "    a".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "b   c".to_owned()),
+        ]
+    );
+
+    // tab crossing the row edge pads to the end and the text continues on the next row
+    let text = Text::<MockedBackend>::with_tab_width("abc\td".to_owned(), None, 8);
+    assert_eq!(text.width(), 9);
+    text.wrap(&mut rect.into_iter(), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "abc".to_owned()),
+            (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "d".to_owned()),
+            (MockedStyle::default(), "<<padding: 4>>".to_owned()),
+        ]
+    );
+
+    // tab stops are counted from the line start
+    let line = StyledLine::<MockedBackend>::with_tab_width(
+        vec![
+            Text::from("ab".to_owned()),
+            Text::new("\tc".to_owned(), Some(MockedStyle::bold())),
+        ],
+        4,
+    );
+    assert_eq!(line.width(), 5);
+    line.print(&mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "ab".to_owned()),
+            (MockedStyle::bold(), "  c".to_owned()),
+        ]
+    );
+
+    // pushed segments continue on the line tab grid
+    let mut line = line;
+    line.push_str("\td");
+    line.push(Text::from("\te".to_owned()));
+    assert_eq!(line.width(), 13);
+    line.print(&mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "ab".to_owned()),
+            (MockedStyle::bold(), "  c".to_owned()),
+            (MockedStyle::default(), "   d".to_owned()),
+            (MockedStyle::default(), "   e".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn message_reflow_on_resize() {
+    let text = "The quick brown fox jumps over the lazy dog, twice or thrice";
+    let message = Message::<BufferBackend>::new("Info", text);
+    let mut backend = BufferBackend::new(40, 10);
+    let rect = message.render(Rect::new(0, 0, 40, 10), &mut backend);
+    assert_eq!((rect.width, rect.height), (38, 2));
+    assert_eq!(
+        backend.render_to_string(),
+        [
+            "",
+            "",
+            "",
+            "┌Info──────────────────────────────────┐",
+            "│The quick brown fox jumps over the laz│",
+            "│y dog, twice or thrice                │",
+            "└──────────────────────────────────────┘",
+            "",
+            "",
+            "",
+        ]
+        .join("\n")
+    );
+
+    // terminal resized
+    let mut backend = BufferBackend::new(20, 10);
+    let rect = message.render(Rect::new(0, 0, 20, 10), &mut backend);
+    assert_eq!((rect.width, rect.height), (18, 4));
+    assert_eq!(
+        backend.render_to_string(),
+        [
+            "",
+            "",
+            "┌Info──────────────┐",
+            "│The quick brown fo│",
+            "│x jumps over the l│",
+            "│azy dog, twice or │",
+            "│thrice            │",
+            "└──────────────────┘",
+            "",
+            "",
+        ]
+        .join("\n")
+    );
+
+    let short = Message::<BufferBackend>::new("Title", "ok");
+    assert_eq!(short.rect(Rect::new(0, 0, 40, 10)).width, 7);
+    assert_eq!(short.rect(Rect::new(0, 0, 2, 10)).width, 0);
+}
+
+#[test]
+fn text_transform_width() {
+    assert_eq!(TextTransform::LetterSpaced(2).apply("abc"), "a  b  c");
+    assert_eq!(
+        UTFSafe::width(TextTransform::LetterSpaced(1).apply("a🚀b").as_str()),
+        6
+    );
+    assert_eq!(TextTransform::LetterSpaced(3).apply(""), "");
+    assert_eq!(TextTransform::Uppercase.apply("straße"), "STRASSE");
+    assert_eq!(TextTransform::Lowercase.apply("NaMe"), "name");
+}
+
+#[test]
+fn text_render_transformed() {
+    let mut backend = MockedBackend::init();
+    let text = Text::<MockedBackend>::new("Header".to_owned(), Some(MockedStyle::bold()));
+    let line = Line {
+        row: 1,
+        col: 0,
+        width: 14,
+    };
+    text.render_transformed(line.clone(), TextTransform::LetterSpaced(1), &mut backend);
+    text.render_transformed(line.clone(), TextTransform::LetterSpaced(2), &mut backend);
+    text.render_transformed(line, TextTransform::Uppercase, &mut backend);
+    assert_eq!(text.as_str(), "Header");
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "H e a d e r".to_owned()),
+            (MockedStyle::default(), "<<padding: 3>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "H  e  a  d  e ".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "HEADER".to_owned()),
+            (MockedStyle::default(), "<<padding: 8>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn styled_line_render_transformed() {
+    let mut backend = MockedBackend::init();
+    let line = StyledLine::<MockedBackend>::from(vec![
+        Text::new("ab".to_owned(), Some(MockedStyle::bold())),
+        Text::from("cd".to_owned()),
+        Text::from("ef".to_owned()),
+    ]);
+    line.render_transformed(
+        Line {
+            row: 0,
+            col: 0,
+            width: 8,
+        },
+        TextTransform::LetterSpaced(1),
+        &mut backend,
+    );
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "a b".to_owned()),
+            (MockedStyle::default(), " c d".to_owned()),
+            (MockedStyle::default(), " ".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn state_columns_navigation() {
+    let mut state = MState::new();
+    let (rows, option_len) = (3, 7);
+    state.select(4, option_len);
+    state.right(rows, option_len);
+    // last column is shorter - selects last option
+    assert_eq!(state.selected, 6);
+    state.right(rows, option_len);
+    assert_eq!(state.selected, 6);
+    state.left(rows, option_len);
+    assert_eq!(state.selected, 3);
+    state.left(rows, option_len);
+    assert_eq!(state.selected, 0);
+    state.left(rows, option_len);
+    assert_eq!(state.selected, 0);
+    state.select(2, option_len);
+    state.right(rows, option_len);
+    assert_eq!(state.selected, 5);
+    state.right(rows, option_len);
+    assert_eq!(state.selected, 6);
+}
+
+#[test]
+fn state_render_list_columns() {
+    let options = ["a", "b", "c", "d", "e", "f", "g"];
+    let rect = Rect::new(0, 0, 6, 3);
+    let mut state = State::<BufferBackend>::new();
+    let mut backend = BufferBackend::new(6, 3);
+    state.select(6, options.len());
+    state.render_list_columns(options.into_iter(), rect, 3, &mut backend);
+    assert_eq!(state.at_line, 0);
+    assert_eq!(backend.render_to_string(), "a d g\nb e\nc f");
+
+    // paging by rows * cols
+    let options = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+    let rect = Rect::new(0, 0, 4, 2);
+    let mut backend = BufferBackend::new(4, 2);
+    state.select(9, options.len());
+    state.render_list_columns(options.into_iter(), rect, 2, &mut backend);
+    assert_eq!(state.at_line, 8);
+    assert_eq!(backend.render_to_string(), "8\n9");
+    state.select(5, options.len());
+    state.render_list_columns(options.into_iter(), rect, 2, &mut backend);
+    assert_eq!(state.at_line, 4);
+    assert_eq!(backend.render_to_string(), "4 6\n5 7");
+}
+
+#[test]
+fn state_render_list_centered() {
+    let options = ["tres", "duo", "unus"];
+    let rect = Rect::new(0, 0, 6, 7);
+    let mut state = State::<BufferBackend>::new();
+    let mut backend = BufferBackend::new(6, 7);
+    for row in 0..7 {
+        backend.go_to(row, 0);
+        backend.print("xxxxxx");
+    }
+    state.next(options.len());
+    let lines = rect.into_iter().centered(options.len());
+    state.render_list_lines(options.into_iter(), lines, &mut backend);
+    assert_eq!(state.at_line, 0);
+    assert_eq!(
+        backend.to_string_lines(),
+        ["      ", "      ", "tres  ", "duo   ", "unus  ", "      ", "      "]
+    );
+}
+
+#[test]
+fn state_render_list_columns_highlight() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["a", "b", "c"];
+    state.select(2, options.len());
+    state.render_list_columns(options.into_iter(), Rect::new(0, 0, 4, 2), 2, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::default(), "a".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "b".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 2>>".to_owned()),
+            (MockedStyle::reversed(), "c".to_owned()),
+            (MockedStyle::reversed(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 2>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+        ]
+    );
+}
+
+fn hash_of(value: &impl std::hash::Hash) -> u64 {
+    use std::hash::{DefaultHasher, Hasher};
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn text_eq_hash() {
+    let text = Text::<MockedBackend>::new("text".to_owned(), Some(MockedStyle::bold()));
+    let same = Text::<MockedBackend>::new("text".to_owned(), Some(MockedStyle::bold()));
+    let unstyled = Text::<MockedBackend>::from("text".to_owned());
+    let other_style = Text::<MockedBackend>::new("text".to_owned(), Some(MockedStyle::ital()));
+    assert_eq!(text, same);
+    assert_eq!(hash_of(&text), hash_of(&same));
+    assert_ne!(text, unstyled);
+    assert_ne!(hash_of(&text), hash_of(&unstyled));
+    assert_ne!(text, other_style);
+    assert!(text.content_eq(&unstyled));
+    assert!(text.content_eq(&other_style));
+    assert!(!text.content_eq(&Text::from("txt".to_owned())));
+
+    // tab stops change rendering so they take part in equality and hashing
+    let plain = Text::<MockedBackend>::from("\tx".to_owned());
+    let tabbed = Text::<MockedBackend>::with_tab_width("\tx".to_owned(), None, 4);
+    let wider = Text::<MockedBackend>::with_tab_width("\tx".to_owned(), None, 8);
+    assert_eq!(
+        tabbed,
+        Text::<MockedBackend>::with_tab_width("\tx".to_owned(), None, 4)
+    );
+    assert_ne!(plain, tabbed);
+    assert_ne!(hash_of(&plain), hash_of(&tabbed));
+    assert_ne!(tabbed, wider);
+    assert_ne!(hash_of(&tabbed), hash_of(&wider));
+    assert!(plain.content_eq(&tabbed));
+}
+
+#[test]
+fn styled_line_eq_hash() {
+    let line = StyledLine::<MockedBackend>::from(vec![
+        Text::new("styled".to_owned(), Some(MockedStyle::bold())),
+        Text::from(" line".to_owned()),
+    ]);
+    let same = StyledLine::<MockedBackend>::from(vec![
+        Text::new("styled".to_owned(), Some(MockedStyle::bold())),
+        Text::from(" line".to_owned()),
+    ]);
+    let restyled = StyledLine::<MockedBackend>::from(vec![
+        Text::from("sty".to_owned()),
+        Text::new("led line".to_owned(), Some(MockedStyle::reversed())),
+    ]);
+    assert_eq!(line, same);
+    assert_eq!(hash_of(&line), hash_of(&same));
+    assert_ne!(line, restyled);
+    assert_ne!(hash_of(&line), hash_of(&restyled));
+    assert!(line.content_eq(&restyled));
+    assert!(!line.content_eq(&StyledLine::from(vec![Text::from("styled".to_owned())])));
+}
+
+struct Entry {
+    name: &'static str,
+    size: &'static str,
+}
+
+impl ListItem<MockedBackend> for Entry {
+    fn render_into(&self, mut builder: LineBuilder<MockedBackend>) {
+        builder.push_styled("> ", MockedStyle::fg(1));
+        if builder.push(self.name) && builder.push(" ") {
+            builder.push(self.size);
+        }
+    }
+
+    fn width_hint(&self) -> usize {
+        3 + self.name.len() + self.size.len()
+    }
+}
+
+#[test]
+fn state_render_items() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let items = [
+        Entry {
+            name: "src",
+            size: "4K",
+        },
+        Entry {
+            name: "README.md",
+            size: "1K",
+        },
+    ];
+    assert_eq!(items[1].width_hint(), 14);
+    state.select(1, items.len());
+    state.render_items(items.iter(), Rect::new(0, 0, 10, 3), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::fg(1), "> ".to_owned()),
+            (MockedStyle::default(), "src".to_owned()),
+            (MockedStyle::default(), " ".to_owned()),
+            (MockedStyle::default(), "4K".to_owned()),
+            (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::fg(1), "> ".to_owned()),
+            (MockedStyle::reversed(), "README.m".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+            (MockedStyle::default(), "<<padding: 10>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn state_render_items_builtin() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let items = ["tres", "duo", "unus"];
+    let rect = Rect::new(0, 0, 4, 2);
+    state.render_items(items.iter(), rect, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::reversed(), "tres".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "duo".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+
+    let lines = [
+        StyledLine::<MockedBackend>::from(vec![
+            Text::new("ab".to_owned(), Some(MockedStyle::bold())),
+            Text::from("cd".to_owned()),
+        ]),
+        StyledLine::from(vec![Text::from("ef".to_owned())]),
+    ];
+    assert_eq!(lines[0].width_hint(), 4);
+    state.render_items(lines.iter(), Rect::new(0, 0, 3, 2), &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "ab".to_owned()),
+            (MockedStyle::reversed(), "c".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "ef".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+        ]
+    );
+
+    // style set before rendering is restored after the highlighted item
+    backend.set_style(MockedStyle::fg(3));
+    backend.drain();
+    state.render_items(items.iter(), rect, &mut backend);
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::reversed(), "tres".to_owned()),
+            (MockedStyle::fg(3), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::fg(3), "duo".to_owned()),
+            (MockedStyle::fg(3), "<<padding: 1>>".to_owned()),
+        ]
+    );
+    assert_eq!(backend.get_style(), MockedStyle::fg(3));
+}
+
+#[test]
+fn state_multi_selection() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["a", "b", "c"];
+    state.toggle_selected();
+    state.next(options.len());
+    state.next(options.len());
+    state.toggle_selected();
+    state.prev(options.len());
+    assert_eq!(state.selected_set(), &[0, 2].into_iter().collect());
+    state.select(0, options.len());
+    state.render_list_multi(
+        options.into_iter(),
+        Rect::new(0, 0, 2, 3),
+        MockedStyle::bold(),
+        &mut backend,
+    );
+    let mut chosen_selected = MockedStyle::bold();
+    chosen_selected.add_reverse();
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (chosen_selected.clone(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (chosen_selected.clone(), "a".to_owned()),
+            (chosen_selected, "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "b".to_owned()),
+            (MockedStyle::default(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::bold(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 2 col: 0>>".to_owned()),
+            (MockedStyle::bold(), "c".to_owned()),
+            (MockedStyle::bold(), "<<padding: 1>>".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+        ]
+    );
+
+    state.toggle_selected();
+    assert_eq!(state.selected_set(), &[2].into_iter().collect());
+    state.select(2, options.len());
+    state.prune(2);
+    assert!(state.selected_set().is_empty());
+    assert_eq!(state.selected, 1);
+    state.toggle_selected();
+    state.clear_selection();
+    assert!(state.selected_set().is_empty());
+}
+
+#[test]
+fn state_render_list_middle_ellipsis() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["/home/user/file.rs", "/tmp/a"];
+    state.render_list_middle_ellipsis(
+        options.into_iter(),
+        Rect::new(0, 0, 8, 2),
+        "..",
+        &mut backend,
+    );
+    assert_eq!(
+        backend.drain(),
+        vec![
+            (MockedStyle::reversed(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 0 col: 0>>".to_owned()),
+            (MockedStyle::reversed(), "/ho...rs".to_owned()),
+            (MockedStyle::default(), "<<set style>>".to_owned()),
+            (MockedStyle::default(), "<<go to row: 1 col: 0>>".to_owned()),
+            (MockedStyle::default(), "/tmp/a".to_owned()),
+            (MockedStyle::default(), "<<padding: 2>>".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn state_render_scrollbar() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let rect = Rect::new(2, 0, 20, 10);
+    let scrollbar = |thumb: std::ops::Range<u16>| {
+        (2..12)
+            .flat_map(|row| {
+                let glyph = if thumb.contains(&row) { "█" } else { "░" };
+                [
+                    (
+                        MockedStyle::default(),
+                        format!("<<go to row: {row} col: 19>>"),
+                    ),
+                    (MockedStyle::default(), glyph.to_owned()),
+                ]
+            })
+            .collect::<Vec<_>>()
+    };
+    state.at_line = 50;
+    state.render_scrollbar(100, &rect, true, &mut backend);
+    assert_eq!(backend.drain(), scrollbar(7..8));
+    state.at_line = 95;
+    state.render_scrollbar(100, &rect, true, &mut backend);
+    assert_eq!(backend.drain(), scrollbar(11..12));
+    state.at_line = 0;
+    state.render_scrollbar(20, &rect, true, &mut backend);
+    assert_eq!(backend.drain(), scrollbar(2..7));
+
+    // everything fits
+    state.render_scrollbar(10, &rect, true, &mut backend);
+    assert!(backend.drain().is_empty());
+    state.render_scrollbar(3, &rect, false, &mut backend);
+    assert_eq!(backend.drain(), scrollbar(2..12));
+}
+
+#[test]
+fn state_paging() {
+    let mut state = MState::new();
+    state.page_down(25, 10);
+    assert_eq!(state.selected, 10);
+    state.page_down(25, 10);
+    assert_eq!(state.selected, 20);
+    state.page_down(25, 10);
+    assert_eq!(state.selected, 24);
+    state.page_down(25, 10);
+    assert_eq!(state.selected, 24);
+    state.page_up(25, 10);
+    assert_eq!(state.selected, 14);
+    state.page_up(25, 20);
+    assert_eq!(state.selected, 0);
+    state.page_up(25, 10);
+    assert_eq!(state.selected, 0);
+    state.page_down(0, 10);
+    assert_eq!(state.selected, 0);
+
+    state.last(25);
+    assert_eq!(state.selected, 24);
+    state.first(25);
+    assert_eq!(state.selected, 0);
+
+    // disabled are skipped in the direction of move
+    state.set_disabled([0, 10, 24].into_iter().collect());
+    state.page_down(25, 10);
+    assert_eq!(state.selected, 11);
+    state.page_down(25, 20);
+    assert_eq!(state.selected, 23);
+    state.last(25);
+    assert_eq!(state.selected, 23);
+    state.page_up(25, 13);
+    assert_eq!(state.selected, 9);
+    state.page_up(25, 9);
+    assert_eq!(state.selected, 1);
+    state.first(25);
+    assert_eq!(state.selected, 1);
+
+    // all disabled - selection is kept
+    state.set_disabled((0..25).collect());
+    state.first(25);
+    assert_eq!(state.selected, 1);
+    state.first(0);
+    assert_eq!(state.selected, 1);
+}
+
+#[test]
+fn render_stats() {
+    let mut backend = MockedBackend::init();
+    let mut state = MState::new();
+    let options = ["tres", "duo", "unus"];
+    backend.print("stale frame");
+    backend.freeze();
+    state.render_list(options.into_iter(), Rect::new(0, 0, 4, 3), &mut backend);
+    Line {
+        row: 4,
+        col: 0,
+        width: 6,
+    }
+    .render_styled("sum", MockedStyle::bold(), &mut backend);
+    backend.print_styled_at(5, 0, "!", MockedStyle::bold());
+    assert_eq!(
+        backend.stats(),
+        Some(&RenderStats {
+            prints: 4,
+            styled_prints: 1,
+            go_tos: 5,
+            padded_cells: 4,
+            bytes: 19,
+        })
+    );
+    backend.freeze();
+    assert_eq!(backend.stats(), Some(&RenderStats::default()));
+    assert_eq!(BufferBackend::new(1, 1).stats(), None);
+}
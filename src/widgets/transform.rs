@@ -0,0 +1,37 @@
+/// Print time transformation of text (the stored text is not changed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextTransform {
+    Uppercase,
+    Lowercase,
+    /// inserts number of spaces between chars
+    LetterSpaced(u8),
+}
+
+impl TextTransform {
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Uppercase => text.to_uppercase(),
+            Self::Lowercase => text.to_lowercase(),
+            Self::LetterSpaced(spacing) => {
+                let spacing = *spacing as usize;
+                let mut spaced = String::with_capacity(text.len() * (spacing + 1));
+                for (idx, ch) in text.chars().enumerate() {
+                    if idx != 0 {
+                        spaced.extend(std::iter::repeat(' ').take(spacing));
+                    }
+                    spaced.push(ch);
+                }
+                spaced
+            }
+        }
+    }
+
+    /// separator between segments of StyledLine
+    #[inline]
+    pub fn separator(&self) -> usize {
+        match self {
+            Self::LetterSpaced(spacing) => *spacing as usize,
+            Self::Uppercase | Self::Lowercase => 0,
+        }
+    }
+}